@@ -0,0 +1,448 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// External imports
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+
+// Internal imports
+use card;
+use card::Card;
+use consts;
+use isomorphism;
+use isomorphism::HandIndexer;
+use range::Range;
+use rng::Rng;
+use strength;
+use types;
+use types::Street;
+
+const MAGIC: &[u8; 4] = b"PKAB";
+const VERSION: u8 = 1;
+
+/**
+ * Config holds the parameters used to build an abstraction
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Config {
+    pub buckets: usize,
+    pub bins: usize, // Bins of the hand strength histograms
+    pub runouts: usize, // Runouts sampled for every histogram
+    pub opponents: usize, // Opponent hands sampled to compute the strength on the river
+    pub iterations: usize, // Maximum iterations of k-means
+    pub seed: u64,
+}
+
+impl Config {
+    /**
+     * new creates a configuration for a number of buckets with reasonable defaults
+     */
+    pub fn new(buckets: usize) -> Config {
+        Config {
+            buckets,
+            bins: 10,
+            runouts: 50,
+            opponents: 50,
+            iterations: 50,
+            seed: 0,
+        }
+    }
+}
+
+/**
+ * Distance is the metric used to compare the features of two hands
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Distance {
+    Euclidean,
+    EarthMovers, // For histograms, where moving mass to a near bin costs less than to a far one
+}
+
+impl Distance {
+    /**
+     * between gets the distance between two points
+     */
+    pub fn between(&self, a: &[f64], b: &[f64]) -> f64 {
+        match *self {
+            Distance::Euclidean => {
+                a.iter().zip(b).map(|(a, b)| (a - b) * (a - b)).sum::<f64>().sqrt()
+            }
+            Distance::EarthMovers => earth_movers_distance(a, b),
+        }
+    }
+}
+
+/**
+ * earth_movers_distance gets the minimum work needed to turn a histogram into another one, moving
+ * mass between bins at a cost of 1 per bin. Both histograms must have the same total mass.
+ */
+pub fn earth_movers_distance(a: &[f64], b: &[f64]) -> f64 {
+    let mut carried = 0.0;
+    let mut work = 0.0;
+    for (a, b) in a.iter().zip(b) {
+        carried += a - b;
+        work += carried.abs();
+    }
+    work
+}
+
+/**
+ * histogram gets the distribution of the strength of a hand on the river over random runouts of
+ * the board. The strength is the equity against a random hand, and it's split on bins of the same
+ * width. The same seed gives the same histogram.
+ */
+pub fn histogram(hole: types::Hole,
+                 board: &[&Card],
+                 bins: usize,
+                 runouts: usize,
+                 opponents: usize,
+                 seed: u64)
+                 -> Vec<f64> {
+    let mut rng = Rng::new(seed);
+    let range = Range::uniform();
+    let mut deck: Vec<Card> = card::deck()
+        .into_iter()
+        .filter(|card| !hole.contains(&card) && !board.contains(&card))
+        .collect();
+
+    let mut histogram = vec![0.0; bins];
+    for _ in 0..runouts {
+        rng.shuffle(&mut deck);
+        let mut river = board.to_vec();
+        river.extend(deck[..consts::TABLE_SIZE - board.len()].iter());
+        let equity = strength::sampled_equity(hole, &river, &range, opponents, rng.next_u64());
+        histogram[((equity * bins as f64) as usize).min(bins - 1)] += 1.0 / runouts as f64;
+    }
+    histogram
+}
+
+/**
+ * Clusters is the result of k-means: the center of every cluster and the cluster of every point
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Clusters {
+    pub centroids: Vec<Vec<f64>>,
+    pub assignments: Vec<usize>,
+}
+
+/**
+ * kmeans groups the points on k clusters. Centers are initialized with k-means++ and refined
+ * until no point changes its cluster or the iterations run out. Every center is the mean of its
+ * points, which for histograms is a histogram too. The same seed gives the same clusters.
+ */
+pub fn kmeans(points: &[Vec<f64>],
+              k: usize,
+              distance: Distance,
+              iterations: usize,
+              seed: u64)
+              -> Clusters {
+    assert!(k > 0 && k <= points.len(), "There must be between 1 and one cluster per point");
+    let mut rng = Rng::new(seed);
+
+    // k-means++: every new center is picked with a probability proportional to the square of the
+    // distance to the nearest center
+    let mut centroids = vec![points[rng.below(points.len())].clone()];
+    while centroids.len() < k {
+        let weights: Vec<f64> = points.iter()
+            .map(|point| {
+                let nearest = nearest(&centroids, point, distance).1;
+                nearest * nearest
+            })
+            .collect();
+        let total: f64 = weights.iter().sum();
+        let mut target = rng.next_f64() * total;
+        let mut chosen = points.len() - 1;
+        for (i, weight) in weights.iter().enumerate() {
+            if target < *weight {
+                chosen = i;
+                break;
+            }
+            target -= weight;
+        }
+        centroids.push(points[chosen].clone());
+    }
+
+    // Points are assigned to the seeded centers even without iterations
+    let mut assignments = vec![usize::MAX; points.len()];
+    for _ in 0..iterations.max(1) {
+        let mut changed = false;
+        for (point, assignment) in points.iter().zip(assignments.iter_mut()) {
+            let cluster = nearest(&centroids, point, distance).0;
+            changed |= *assignment != cluster;
+            *assignment = cluster;
+        }
+        if !changed {
+            break;
+        }
+
+        // Clusters without points keep their center
+        let mut sums = vec![vec![0.0; points[0].len()]; k];
+        let mut counts = vec![0; k];
+        for (point, assignment) in points.iter().zip(&assignments) {
+            counts[*assignment] += 1;
+            for (sum, value) in sums[*assignment].iter_mut().zip(point) {
+                *sum += value;
+            }
+        }
+        for (cluster, sum) in sums.into_iter().enumerate() {
+            if counts[cluster] > 0 {
+                centroids[cluster] = sum.iter().map(|sum| sum / counts[cluster] as f64).collect();
+            }
+        }
+    }
+
+    Clusters {
+        centroids,
+        assignments,
+    }
+}
+
+/**
+ * nearest gets the closest center to a point and its distance
+ */
+fn nearest(centroids: &[Vec<f64>], point: &[f64], distance: Distance) -> (usize, f64) {
+    centroids.iter()
+        .map(|centroid| distance.between(centroid, point))
+        .enumerate()
+        .fold((0, f64::INFINITY), |best, (i, d)| if d < best.1 { (i, d) } else { best })
+}
+
+/**
+ * Abstraction maps the hands of a street to buckets of hands that play alike. Hands are keyed by
+ * their isomorphic index and buckets are sorted by strength, so bucket 0 has the weakest hands.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Abstraction {
+    street: Street,
+    buckets: usize,
+    assignments: BTreeMap<u64, usize>,
+}
+
+impl Abstraction {
+    /**
+     * build clusters the hands with the given isomorphic indices. Hands before the river are
+     * described by their strength histograms, compared with the earth mover's distance, and hands
+     * on the river by their strength. All the hands of a street are the indices from 0 to
+     * HandIndexer::for_street(street).size(round) on the last round. Without hands or buckets the
+     * abstraction is empty. It panics unless the configuration samples some opponent hands and,
+     * before the river, some runouts on some bins.
+     */
+    pub fn build(street: Street, indices: &[u64], config: &Config) -> Abstraction {
+        assert!(config.opponents > 0, "At least one opponent hand must be sampled");
+        assert!(street == Street::River || (config.runouts > 0 && config.bins > 0),
+                "At least one runout and one bin are needed before the river");
+
+        let buckets = config.buckets.min(indices.len());
+        if buckets == 0 {
+            return Abstraction {
+                street,
+                buckets,
+                assignments: BTreeMap::new(),
+            };
+        }
+
+        let range = Range::uniform();
+        let indexer = HandIndexer::for_street(street);
+        let features: Vec<Vec<f64>> = indices.iter()
+            .map(|index| {
+                let mut hole = indexer.unindex(indexer.rounds() - 1, *index);
+                let board = hole.split_off(consts::HOLE_SIZE);
+                let hole = [&hole[0], &hole[1]];
+                let board: Vec<&Card> = board.iter().collect();
+                let seed = config.seed.wrapping_add(*index);
+                if street == Street::River {
+                    vec![strength::sampled_equity(hole, &board, &range, config.opponents, seed)]
+                } else {
+                    histogram(hole, &board, config.bins, config.runouts, config.opponents, seed)
+                }
+            })
+            .collect();
+
+        let distance = if street == Street::River {
+            Distance::Euclidean
+        } else {
+            Distance::EarthMovers
+        };
+        let clusters = kmeans(&features, buckets, distance, config.iterations, config.seed);
+
+        // Relabel the clusters by the mean strength of their centers
+        let mut order: Vec<usize> = (0..buckets).collect();
+        let strength = |centroid: &Vec<f64>| -> f64 {
+            let bins = centroid.len() as f64;
+            centroid.iter().enumerate().map(|(i, mass)| mass * (i as f64 + 0.5) / bins).sum()
+        };
+        let strengths: Vec<f64> = clusters.centroids.iter().map(strength).collect();
+        order.sort_by(|a, b| strengths[*a].total_cmp(&strengths[*b]));
+        let mut labels = vec![0; buckets];
+        for (label, cluster) in order.iter().enumerate() {
+            labels[*cluster] = label;
+        }
+
+        Abstraction {
+            street,
+            buckets,
+            assignments: indices.iter()
+                .zip(&clusters.assignments)
+                .map(|(index, cluster)| (*index, labels[*cluster]))
+                .collect(),
+        }
+    }
+
+    /**
+     * street gets the street of the hands of the abstraction
+     */
+    pub fn street(&self) -> Street {
+        self.street
+    }
+
+    /**
+     * buckets gets the number of buckets
+     */
+    pub fn buckets(&self) -> usize {
+        self.buckets
+    }
+
+    /**
+     * len gets the number of hands with a bucket
+     */
+    pub fn len(&self) -> usize {
+        self.assignments.len()
+    }
+
+    /**
+     * is_empty checks if no hand has a bucket
+     */
+    pub fn is_empty(&self) -> bool {
+        self.assignments.is_empty()
+    }
+
+    /**
+     * bucket_of gets the bucket of a hand given its isomorphic index, if it was clustered
+     */
+    pub fn bucket_of(&self, index: u64) -> Option<usize> {
+        self.assignments.get(&index).cloned()
+    }
+
+    /**
+     * bucket gets the bucket of the hole cards on a board of the street of the abstraction
+     */
+    pub fn bucket(&self, hole: types::Hole, board: &[&Card]) -> Option<usize> {
+        if board.len() != self.street.board_size() {
+            return None;
+        }
+        self.bucket_of(isomorphism::index(hole, board))
+    }
+
+    /**
+     * write stores the abstraction: a header with the street and the number of buckets and hands,
+     * followed by the index and the bucket of every hand, in little endian
+     */
+    pub fn write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION, self.street as u8])?;
+        writer.write_all(&(self.buckets as u32).to_le_bytes())?;
+        writer.write_all(&(self.assignments.len() as u64).to_le_bytes())?;
+        for (index, bucket) in &self.assignments {
+            writer.write_all(&index.to_le_bytes())?;
+            writer.write_all(&(*bucket as u32).to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    /**
+     * read loads an abstraction written by write
+     */
+    pub fn read<R: Read>(reader: &mut R) -> Result<Abstraction, String> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).map_err(|error| error.to_string())?;
+        if bytes.len() < 18 || &bytes[..4] != MAGIC {
+            return Err("Not an abstraction file".to_string());
+        }
+        if bytes[4] != VERSION {
+            return Err(format!("Unsupported abstraction version {}", bytes[4]));
+        }
+        let street = match bytes[5] {
+            0 => Street::Preflop,
+            1 => Street::Flop,
+            2 => Street::Turn,
+            3 => Street::River,
+            street => return Err(format!("Invalid street {}", street)),
+        };
+        let buckets = u32::from_le_bytes([bytes[6], bytes[7], bytes[8], bytes[9]]) as usize;
+        let mut count = [0; 8];
+        count.copy_from_slice(&bytes[10..18]);
+        let count = u64::from_le_bytes(count);
+        if count.checked_mul(12) != Some(bytes.len() as u64 - 18) {
+            return Err("Truncated abstraction file".to_string());
+        }
+
+        let mut assignments = BTreeMap::new();
+        for entry in bytes[18..].chunks(12) {
+            let mut index = [0; 8];
+            index.copy_from_slice(&entry[..8]);
+            let bucket = u32::from_le_bytes([entry[8], entry[9], entry[10], entry[11]]) as usize;
+            if bucket >= buckets {
+                return Err(format!("Invalid bucket {}", bucket));
+            }
+            assignments.insert(u64::from_le_bytes(index), bucket);
+        }
+
+        Ok(Abstraction {
+            street,
+            buckets,
+            assignments,
+        })
+    }
+
+    /**
+     * save writes the abstraction to a file
+     */
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut file = File::create(path)?;
+        self.write(&mut file)
+    }
+
+    /**
+     * load reads an abstraction from a file
+     */
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Abstraction, String> {
+        let mut file = File::open(path).map_err(|error| error.to_string())?;
+        Abstraction::read(&mut file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::earth_movers_distance;
+
+    #[test]
+    fn test_earth_movers_distance() {
+        assert_eq!(earth_movers_distance(&[1.0, 0.0, 0.0], &[0.0, 0.0, 1.0]), 2.0);
+        assert_eq!(earth_movers_distance(&[0.0, 0.0, 1.0], &[1.0, 0.0, 0.0]), 2.0);
+        assert_eq!(earth_movers_distance(&[0.5, 0.5, 0.0], &[0.0, 0.5, 0.5]), 1.0);
+        assert_eq!(earth_movers_distance(&[0.2, 0.8], &[0.2, 0.8]), 0.0);
+    }
+}
@@ -0,0 +1,145 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// Internal imports
+use bot::Bot;
+use bot::HandResult;
+use card;
+use engine::HandState;
+use engine::TableConfig;
+use rng::Rng;
+
+/**
+ * ArenaConfig holds how a match between two bots is played
+ */
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct ArenaConfig {
+    pub table: TableConfig,
+    pub hands: usize, // Deals to play, twice as many hands with duplicate deals
+    pub duplicate: bool,
+    pub seed: u64,
+}
+
+impl ArenaConfig {
+    /**
+     * new creates a match of duplicate deals with a big blind of 100 chips and stacks of 100 big
+     * blinds
+     */
+    pub fn new(hands: usize, seed: u64) -> ArenaConfig {
+        ArenaConfig {
+            table: TableConfig::new(100, 10000),
+            hands,
+            duplicate: true,
+            seed,
+        }
+    }
+}
+
+/**
+ * MatchResult is the outcome of a match from the point of view of the first bot
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct MatchResult {
+    pub hands: usize, // Hands played, both sides of a duplicate deal included
+    pub winnings: i64, // Chips won by the first bot
+    pub bb_per_100: f64, // Big blinds won by the first bot every 100 hands
+    pub confidence: f64, // Half width of the 95% confidence interval of bb_per_100
+}
+
+/**
+ * play_hand plays a single hand between two bots, the first one on seat 0. Illegal actions are
+ * coerced to legal ones. Both bots are told when the hand starts and how it ends.
+ */
+pub fn play_hand(mut bots: [&mut dyn Bot; 2],
+                 config: &TableConfig,
+                 button: usize,
+                 deck: &[card::Card])
+                 -> HandResult {
+    let mut state = HandState::new(*config, button, deck);
+    for (seat, bot) in bots.iter_mut().enumerate() {
+        bot.hand_started(&state.observation(seat));
+    }
+    while let Some(seat) = state.to_act() {
+        let observation = state.observation(seat);
+        let action = observation.legal.coerce(&bots[seat].act(&observation));
+        state.apply(action).expect("Coerced actions are legal");
+    }
+
+    let result = state.result().expect("The hand is over").clone();
+    for (seat, bot) in bots.iter_mut().enumerate() {
+        bot.hand_over(seat, &result);
+    }
+    result
+}
+
+/**
+ * play runs a match between two bots. The button moves every deal, and with duplicate deals every
+ * deal is played again with the bots swapping their seats, so both bots get the same cards and
+ * luck cancels out. The same seed deals the same cards.
+ */
+pub fn play(first: &mut dyn Bot, second: &mut dyn Bot, config: &ArenaConfig) -> MatchResult {
+    let mut rng = Rng::new(config.seed);
+    let mut deck = card::deck();
+    let mut samples = Vec::with_capacity(config.hands); // Winnings per hand of every deal
+    let mut winnings = 0;
+
+    for deal in 0..config.hands {
+        rng.shuffle(&mut deck);
+        let button = deal % 2;
+        let result = play_hand([&mut *first, &mut *second], &config.table, button, &deck);
+        let mut won = result.winnings[0];
+        let mut hands = 1;
+
+        if config.duplicate {
+            let result = play_hand([&mut *second, &mut *first], &config.table, button, &deck);
+            won += result.winnings[1];
+            hands += 1;
+        }
+        winnings += won;
+        samples.push(won as f64 / hands as f64);
+    }
+
+    let (mean, error) = mean_and_error(&samples);
+    let scale = 100.0 / config.table.big_blind as f64;
+    MatchResult {
+        hands: config.hands * if config.duplicate { 2 } else { 1 },
+        winnings,
+        bb_per_100: mean * scale,
+        confidence: 1.96 * error * scale,
+    }
+}
+
+/**
+ * mean_and_error gets the mean of some samples and its standard error
+ */
+fn mean_and_error(samples: &[f64]) -> (f64, f64) {
+    if samples.is_empty() {
+        return (0.0, 0.0);
+    }
+    let count = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / count;
+    if samples.len() < 2 {
+        return (mean, 0.0);
+    }
+    let variance = samples.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / (count - 1.0);
+    (mean, (variance / count).sqrt())
+}
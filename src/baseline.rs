@@ -0,0 +1,306 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// Internal imports
+use bot::{Action, Bot, Chips, Observation};
+use card::Card;
+use hand;
+use hand::HandType;
+use outs;
+use outs::Draw;
+use preflop;
+use preflop::HandClass;
+use range::Range;
+use rng::Rng;
+use types::Street;
+
+/**
+ * RandomBot picks any of the legal actions with the same probability, raising a random amount
+ */
+pub struct RandomBot {
+    rng: Rng,
+}
+
+impl RandomBot {
+    /**
+     * new creates a random bot, which always plays the same given the same seed
+     */
+    pub fn new(seed: u64) -> RandomBot {
+        RandomBot { rng: Rng::new(seed) }
+    }
+}
+
+impl Bot for RandomBot {
+    fn name(&self) -> String {
+        "random".to_string()
+    }
+
+    fn act(&mut self, observation: &Observation) -> Action {
+        let legal = observation.legal;
+        let mut actions = Vec::new();
+        if legal.fold() {
+            actions.push(Action::Fold);
+        }
+        if legal.check {
+            actions.push(Action::Check);
+        }
+        if legal.call.is_some() {
+            actions.push(Action::Call);
+        }
+        if let Some((min, max)) = legal.raise {
+            actions.push(Action::Raise(min + self.rng.below((max - min + 1) as usize) as Chips));
+        }
+
+        if actions.is_empty() {
+            return Action::Fold;
+        }
+        actions[self.rng.below(actions.len())]
+    }
+}
+
+/**
+ * CallingStation checks or calls every bet, whatever its cards. It doesn't need a seed as it
+ * never chooses.
+ */
+#[derive(Default)]
+pub struct CallingStation;
+
+impl CallingStation {
+    /**
+     * new creates a calling station
+     */
+    pub fn new() -> CallingStation {
+        CallingStation
+    }
+}
+
+impl Bot for CallingStation {
+    fn name(&self) -> String {
+        "calling station".to_string()
+    }
+
+    fn act(&mut self, observation: &Observation) -> Action {
+        if observation.legal.check { Action::Check } else { Action::Call }
+    }
+}
+
+/**
+ * Tier groups the starting hands by how the tight aggressive bot plays them preflop
+ */
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum Tier {
+    Trash, // Folded, or checked on the big blind
+    Opening, // Raised first in, folded to a raise
+    Strong, // Raised first in, calls a raise
+    Premium, // Raised and reraised
+}
+
+/**
+ * Made is the strength of a made hand after the flop
+ */
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum Made {
+    Nothing, // The hole cards don't improve the board
+    Weak, // A pair below the top card of the board
+    Good, // Top pair or an overpair
+    Strong, // Two pair or better, using the hole cards
+}
+
+const PREMIUM: &str = "TT+, AQs+, AKo";
+const STRONG: &str = "77+, ATs+, KJs+, QJs, AJo+, KQo";
+const OPENING: &str = "22+, A2s+, K8s+, Q9s+, J9s+, T8s+, 97s+, 86s+, 76s, 65s, A7o+, KTo+, QTo+, \
+                       JTo";
+const BET: f64 = 0.67; // Bets after the flop, as a fraction of the pot
+const BLUFF: f64 = 0.5; // Probability of a continuation bet or a semi-bluff
+const DRAW_ODDS: f64 = 0.35; // Highest share of the pot paid to call with a draw
+const WEAK_ODDS: f64 = 0.2; // Highest share of the pot paid to call with a weak pair
+
+/**
+ * TightAggressive plays few starting hands and bets them. Preflop it raises its opening hands
+ * first in, calls a raise with strong hands and reraises premium ones. After the flop it bets
+ * made hands, calls with draws and weak pairs at a good price, continuation bets and semi-bluffs
+ * half of the time and gives up otherwise.
+ */
+pub struct TightAggressive {
+    rng: Rng,
+    tiers: Vec<Tier>, // Tier of every hand class, indexed like HandClass::index
+}
+
+impl TightAggressive {
+    /**
+     * new creates a tight aggressive bot, which always plays the same given the same seed
+     */
+    pub fn new(seed: u64) -> TightAggressive {
+        let mut tiers = vec![Tier::Trash; preflop::CLASSES];
+        let ranges = [(Tier::Opening, OPENING), (Tier::Strong, STRONG), (Tier::Premium, PREMIUM)];
+        for &(tier, range) in &ranges {
+            let range = Range::parse(range).expect("The ranges of the tiers are valid");
+            for (class, frequency) in preflop::frequencies(&range).iter().enumerate() {
+                if *frequency > 0.5 {
+                    tiers[class] = tier;
+                }
+            }
+        }
+        TightAggressive {
+            rng: Rng::new(seed),
+            tiers,
+        }
+    }
+
+    /**
+     * tier gets how a starting hand is played
+     */
+    pub fn tier(&self, class: HandClass) -> Tier {
+        self.tiers[class.index()]
+    }
+
+    fn preflop(&mut self, observation: &Observation) -> Action {
+        let hole = observation.hole;
+        let tier = self.tier(HandClass::of(&hole[0], &hole[1]));
+        let legal = observation.legal;
+        let passive = if legal.check { Action::Check } else { Action::Fold };
+
+        match raises(observation) {
+            0 if tier >= Tier::Opening => raise(observation, 1.0),
+            1 if tier == Tier::Premium => raise(observation, 1.0),
+            1 if tier == Tier::Strong => Action::Call,
+            _ if tier == Tier::Premium => {
+                if raises(observation) < 3 { raise(observation, 1.0) } else { Action::Call }
+            }
+            _ => passive,
+        }
+    }
+
+    fn postflop(&mut self, observation: &Observation) -> Action {
+        let hole = [&observation.hole[0], &observation.hole[1]];
+        let board: Vec<&Card> = observation.board.iter().collect();
+        let made = made(hole, &board);
+        let draw = outs::draws(hole, &board).iter().any(|draw| {
+            matches!(*draw, Draw::FlushDraw | Draw::OpenEnded | Draw::ComboDraw)
+        });
+
+        let legal = observation.legal;
+        match legal.call {
+            Some(call) => {
+                let price = call as f64 / (observation.pot + call) as f64;
+                match made {
+                    Made::Strong if raises(observation) < 2 => raise(observation, BET),
+                    Made::Strong | Made::Good => Action::Call,
+                    _ if draw && price <= DRAW_ODDS => Action::Call,
+                    Made::Weak if price <= WEAK_ODDS => Action::Call,
+                    _ => Action::Fold,
+                }
+            }
+            None => {
+                let aggressor =
+                    observation.street == Street::Flop && preflop_aggressor(observation);
+                let bluff = (draw || aggressor) && self.rng.next_f64() < BLUFF;
+                if made >= Made::Good || bluff {
+                    raise(observation, BET)
+                } else {
+                    Action::Check
+                }
+            }
+        }
+    }
+}
+
+impl Bot for TightAggressive {
+    fn name(&self) -> String {
+        "tight aggressive".to_string()
+    }
+
+    fn act(&mut self, observation: &Observation) -> Action {
+        if observation.street == Street::Preflop {
+            self.preflop(observation)
+        } else {
+            self.postflop(observation)
+        }
+    }
+}
+
+/**
+ * made classifies the made hand of a player after the flop, by how much the hole cards improve
+ * the board. A pair on a paired board is not two pair for the player.
+ */
+pub fn made(hole: [&Card; 2], board: &[&Card]) -> Made {
+    let mut cards = hole.to_vec();
+    cards.extend_from_slice(board);
+    let hand_type = hand::hand_type(&cards);
+    let board_type = hand::hand_type(board);
+    if hand_type <= board_type {
+        return Made::Nothing;
+    }
+    let two_pair = hand_type == HandType::TwoPair && board_type == HandType::HighCard;
+    if hand_type > HandType::TwoPair || two_pair {
+        return Made::Strong;
+    }
+
+    // A pair of the player, made with a hole card or a pocket pair, maybe next to one of the board
+    let top = board.iter().map(|card| card.value).max().expect("There is a board");
+    let paired = if hole[0].value == hole[1].value {
+        hole[0].value
+    } else {
+        hole.iter()
+            .map(|card| card.value)
+            .find(|value| board.iter().any(|card| card.value == *value))
+            .expect("A hole card makes the pair")
+    };
+    if paired >= top { Made::Good } else { Made::Weak }
+}
+
+/**
+ * raises counts the raises of the current street, the first bet included
+ */
+fn raises(observation: &Observation) -> usize {
+    observation.history
+        .iter()
+        .filter(|event| event.street == observation.street)
+        .filter(|event| matches!(event.action, Action::Raise(_)))
+        .count()
+}
+
+/**
+ * preflop_aggressor tells if the player made the last raise before the flop
+ */
+fn preflop_aggressor(observation: &Observation) -> bool {
+    observation.history
+        .iter()
+        .rev()
+        .find(|event| event.street == Street::Preflop && matches!(event.action, Action::Raise(_)))
+        .is_some_and(|event| event.seat == observation.seat)
+}
+
+/**
+ * raise bets a fraction of the pot after calling, or calls when raising is not allowed
+ */
+fn raise(observation: &Observation, fraction: f64) -> Action {
+    let legal = observation.legal;
+    let call = legal.call.unwrap_or(0);
+    let size = (fraction * (observation.pot + call) as f64).round() as Chips;
+    let to = observation.bets[observation.seat] + call + size;
+    match legal.raise {
+        Some((min, max)) => Action::Raise(to.max(min).min(max)),
+        None if legal.check => Action::Check,
+        None => Action::Call,
+    }
+}
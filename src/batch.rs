@@ -0,0 +1,282 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// External imports
+use std::fmt::Write;
+
+// Internal imports
+use card::Card;
+use consts;
+
+/**
+ * Deal is a showdown read from a batch file
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Deal {
+    pub line: usize, // Line where the deal starts
+    pub player1: String,
+    pub player2: String,
+    pub table: String,
+    pub expected: Option<String>, // Expected result, if given
+}
+
+/**
+ * Format is any of the supported outputs of a batch
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Text,
+    Csv,
+    JsonLines,
+}
+
+impl Format {
+    /**
+     * from_name gets the format with the given name: "text", "csv" or "jsonl"
+     */
+    pub fn from_name(name: &str) -> Option<Format> {
+        match name {
+            "text" => Some(Format::Text),
+            "csv" => Some(Format::Csv),
+            "jsonl" => Some(Format::JsonLines),
+            _ => None,
+        }
+    }
+}
+
+/**
+ * parse_deals reads the deals of a batch. A deal is either on the CodinGame format, three lines
+ * with the hole cards of both players and the community cards, or a single line with the 9 cards.
+ * When expected is set the result of every deal follows it: on the next line for the CodinGame
+ * format or after the cards for a single line. Empty lines are ignored.
+ */
+pub fn parse_deals(input: &str, expected: bool) -> Result<Vec<Deal>, String> {
+    let cards_per_deal = 2 * consts::HOLE_SIZE + consts::TABLE_SIZE;
+    let lines_per_deal = if expected { 4 } else { 3 };
+
+    let mut deals = Vec::new();
+    let mut pending: Vec<(usize, &str)> = Vec::new();
+    for (number, line) in input.lines().enumerate().map(|(i, line)| (i + 1, line.trim())) {
+        if line.is_empty() {
+            continue;
+        }
+
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        if pending.is_empty() && tokens.len() >= cards_per_deal {
+            let rest = tokens[cards_per_deal..].join(" ");
+            if expected && rest.is_empty() {
+                return Err(format!("line {}: missing expected result", number));
+            }
+            if !expected && !rest.is_empty() {
+                return Err(format!("line {}: unexpected text after the cards", number));
+            }
+
+            deals.push(validate(Deal {
+                line: number,
+                player1: tokens[..2].join(" "),
+                player2: tokens[2..4].join(" "),
+                table: tokens[4..cards_per_deal].join(" "),
+                expected: if expected { Some(rest) } else { None },
+            })?);
+            continue;
+        }
+
+        pending.push((number, line));
+        if pending.len() == lines_per_deal {
+            deals.push(validate(Deal {
+                line: pending[0].0,
+                player1: pending[0].1.to_string(),
+                player2: pending[1].1.to_string(),
+                table: pending[2].1.to_string(),
+                expected: pending.get(3).map(|line| line.1.to_string()),
+            })?);
+            pending.clear();
+        }
+    }
+
+    if !pending.is_empty() {
+        return Err(format!("line {}: incomplete deal", pending[0].0));
+    }
+    Ok(deals)
+}
+
+/**
+ * validate checks that every player has 2 cards, that there are 5 community cards and that every
+ * card is valid and dealt only once. Cards are written in uppercase, like on CodinGame.
+ */
+fn validate(deal: Deal) -> Result<Deal, String> {
+    let groups = [(&deal.player1, consts::HOLE_SIZE),
+                  (&deal.player2, consts::HOLE_SIZE),
+                  (&deal.table, consts::TABLE_SIZE)];
+
+    let mut dealt: Vec<Card> = Vec::new();
+    for &(cards, size) in groups.iter() {
+        let cards: Vec<&str> = cards.split_whitespace().collect();
+        if cards.len() != size {
+            return Err(format!("line {}: expected {} cards, found {}",
+                               deal.line,
+                               size,
+                               cards.len()));
+        }
+
+        for text in cards {
+            let card = match Card::parse(text) {
+                Some(card) if !text.chars().any(char::is_lowercase) => card,
+                _ => return Err(format!("line {}: invalid card '{}'", deal.line, text)),
+            };
+            if dealt.contains(&card) {
+                return Err(format!("line {}: repeated card '{}'", deal.line, text));
+            }
+            dealt.push(card);
+        }
+    }
+
+    Ok(deal)
+}
+
+/**
+ * header gets the first line of the output, if the format has one
+ */
+pub fn header(format: Format, expected: bool) -> Option<String> {
+    match format {
+        Format::Csv if expected => {
+            Some(String::from("line,player1,player2,table,result,expected,match"))
+        }
+        Format::Csv => Some(String::from("line,player1,player2,table,result")),
+        _ => None,
+    }
+}
+
+/**
+ * format_result gets the line of the output for a deal and its result
+ */
+pub fn format_result(deal: &Deal, result: &str, format: Format) -> String {
+    match format {
+        Format::Text => result.to_string(),
+        Format::Csv => {
+            let mut line = format!("{},{},{},{},{}",
+                                   deal.line,
+                                   deal.player1,
+                                   deal.player2,
+                                   deal.table,
+                                   result);
+            if let Some(ref expected) = deal.expected {
+                write!(line, ",{},{}", expected, expected == result).unwrap();
+            }
+            line
+        }
+        Format::JsonLines => {
+            let mut line = format!("{{\"line\":{},\"player1\":{},\"player2\":{},\"table\":{},\
+                                    \"result\":{}",
+                                   deal.line,
+                                   json_string(&deal.player1),
+                                   json_string(&deal.player2),
+                                   json_string(&deal.table),
+                                   json_string(result));
+            if let Some(ref expected) = deal.expected {
+                write!(line,
+                       ",\"expected\":{},\"match\":{}",
+                       json_string(expected),
+                       expected == result)
+                    .unwrap();
+            }
+            line.push('}');
+            line
+        }
+    }
+}
+
+/**
+ * json_string quotes and escapes a string for JSON
+ */
+fn json_string(value: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            c if (c as u32) < 0x20 => write!(quoted, "\\u{:04x}", c as u32).unwrap(),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_deals;
+    use super::format_result;
+    use super::Format;
+
+    #[test]
+    fn test_parse_formats() {
+        let input = "8D 7C\n7D 6C\nKS 9D 5C 3S 2D\n\nTC JC AD 4S 2H 7H TH QS KC\n";
+        let deals = parse_deals(input, false).unwrap();
+
+        assert_eq!(deals.len(), 2);
+        assert_eq!(deals[0].line, 1);
+        assert_eq!(deals[0].table, "KS 9D 5C 3S 2D");
+        assert_eq!(deals[1].line, 5);
+        assert_eq!(deals[1].player2, "AD 4S");
+        assert_eq!(deals[1].expected, None);
+    }
+
+    #[test]
+    fn test_parse_expected() {
+        let input = "8D 7C\n7D 6C\nKS 9D 5C 3S 2D\n1 HIGH_CARD K9875\nTC JC AD 4S 2H 7H TH QS KC \
+                     1 PAIR TTKQJ\n";
+        let deals = parse_deals(input, true).unwrap();
+
+        assert_eq!(deals[0].expected, Some(String::from("1 HIGH_CARD K9875")));
+        assert_eq!(deals[1].expected, Some(String::from("1 PAIR TTKQJ")));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse_deals("8D 7C\n7D 6C\n", false),
+                   Err(String::from("line 1: incomplete deal")));
+        assert_eq!(parse_deals("8D 7C\n7D 6X\nKS 9D 5C 3S 2D\n", false),
+                   Err(String::from("line 1: invalid card '6X'")));
+        assert_eq!(parse_deals("8D 7C 9C\n7D 6C\nKS 9D 5C 3S 2D\n", false),
+                   Err(String::from("line 1: expected 2 cards, found 3")));
+        assert_eq!(parse_deals("AS AS\nAS KS\nAS QS JS TS 2C\n", false),
+                   Err(String::from("line 1: repeated card 'AS'")));
+        assert_eq!(parse_deals("8D 7C\n7D 6C\nKS 9D 5C 3S 8D\n", false),
+                   Err(String::from("line 1: repeated card '8D'")));
+        assert_eq!(parse_deals("8d 7C\n7D 6C\nKS 9D 5C 3S 2D\n", false),
+                   Err(String::from("line 1: invalid card '8d'")));
+    }
+
+    #[test]
+    fn test_format_result() {
+        let deals = parse_deals("TC JC AD 4S 2H 7H TH QS KC 1 PAIR TTKQJ", true).unwrap();
+
+        assert_eq!(format_result(&deals[0], "1 PAIR TTKQJ", Format::Text), "1 PAIR TTKQJ");
+        assert_eq!(format_result(&deals[0], "DRAW", Format::Csv),
+                   "1,TC JC,AD 4S,2H 7H TH QS KC,DRAW,1 PAIR TTKQJ,false");
+        assert_eq!(format_result(&deals[0], "1 PAIR TTKQJ", Format::JsonLines),
+                   "{\"line\":1,\"player1\":\"TC JC\",\"player2\":\"AD 4S\",\
+                    \"table\":\"2H 7H TH QS KC\",\"result\":\"1 PAIR TTKQJ\",\
+                    \"expected\":\"1 PAIR TTKQJ\",\"match\":true}");
+    }
+}
@@ -0,0 +1,101 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+extern crate poker;
+
+use std::env;
+use std::fs::File;
+use std::io;
+use std::io::Read;
+use std::process;
+
+use poker::batch;
+use poker::cli::fail;
+
+const USAGE: &str = "Usage: batch [--format text|csv|jsonl] [--expected] [FILE]";
+
+/**
+ * Evaluates every deal of a file (or the standard input) and writes the results. With --expected
+ * every result is compared with the expected one and the program exits with an error if any of
+ * them does not match.
+ */
+fn main() {
+    let mut format = batch::Format::Text;
+    let mut expected = false;
+    let mut path = None;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                let name = args.next().unwrap_or_else(|| fail(USAGE));
+                format = batch::Format::from_name(&name)
+                    .unwrap_or_else(|| fail(&format!("Unknown format '{}'\n{}", name, USAGE)));
+            }
+            "--expected" => expected = true,
+            "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ if path.is_none() && !arg.starts_with("--") => path = Some(arg),
+            _ => fail(USAGE),
+        }
+    }
+
+    let mut input = String::new();
+    let read = match path {
+        Some(ref path) => File::open(path).and_then(|mut file| file.read_to_string(&mut input)),
+        None => io::stdin().read_to_string(&mut input),
+    };
+    if let Err(error) = read {
+        fail(&format!("Unable to read the deals: {}", error));
+    }
+
+    let deals = batch::parse_deals(&input, expected).unwrap_or_else(|error| fail(&error));
+
+    if let Some(header) = batch::header(format, expected) {
+        println!("{}", header);
+    }
+
+    let mut mismatches = Vec::new();
+    for deal in &deals {
+        let result = poker::play(deal.player1.clone(), deal.player2.clone(), deal.table.clone());
+        println!("{}", batch::format_result(deal, &result, format));
+
+        if let Some(ref expected) = deal.expected {
+            if *expected != result {
+                mismatches.push(format!("  line {}: expected '{}', got '{}'",
+                                        deal.line,
+                                        expected,
+                                        result));
+            }
+        }
+    }
+
+    if !mismatches.is_empty() {
+        eprintln!("{} of {} deals do not match:", mismatches.len(), deals.len());
+        for mismatch in mismatches {
+            eprintln!("{}", mismatch);
+        }
+        process::exit(1);
+    }
+}
@@ -0,0 +1,71 @@
+extern crate poker;
+
+use std::env;
+use std::io;
+
+use poker::baseline::{CallingStation, RandomBot, TightAggressive};
+use poker::bot::{Action, Bot, Observation};
+use poker::cli::fail;
+use poker::cli::number;
+use poker::protocol;
+
+const USAGE: &str = "Usage: botclient [--bot call|fold|random|tag] [--seed N] \
+                     [--stdio | ADDRESS]";
+
+/**
+ * Folder checks when it can and folds to any bet
+ */
+struct Folder;
+
+impl Bot for Folder {
+    fn name(&self) -> String {
+        "folder".to_string()
+    }
+
+    fn act(&mut self, _observation: &Observation) -> Action {
+        Action::Fold
+    }
+}
+
+/**
+ * Plays a reference bot on a table hosted by the botserver binary, connecting to its address
+ * (127.0.0.1:9000 by default) or talking on the standard input and output when run by it
+ */
+fn main() {
+    let mut kind = "call".to_string();
+    let mut seed = 1;
+    let mut address = "127.0.0.1:9000".to_string();
+    let mut stdio = false;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--bot" => kind = args.next().unwrap_or_else(|| fail(USAGE)),
+            "--seed" => seed = number(args.next(), USAGE),
+            "--stdio" => stdio = true,
+            "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ if !arg.starts_with("--") => address = arg,
+            _ => fail(USAGE),
+        }
+    }
+
+    let mut bot: Box<dyn Bot> = match kind.as_str() {
+        "call" => Box::new(CallingStation::new()),
+        "fold" => Box::new(Folder),
+        "random" => Box::new(RandomBot::new(seed)),
+        "tag" => Box::new(TightAggressive::new(seed)),
+        _ => fail(USAGE),
+    };
+    let result = if stdio {
+        let stdin = io::stdin();
+        protocol::run_client(&mut *bot, stdin.lock(), io::stdout())
+    } else {
+        protocol::connect(&mut *bot, address.as_str())
+    };
+    if let Err(error) = result {
+        fail(&error);
+    }
+}
@@ -0,0 +1,91 @@
+extern crate poker;
+
+use std::env;
+use std::net::TcpListener;
+use std::time::Duration;
+
+use poker::arena;
+use poker::arena::ArenaConfig;
+use poker::bot::Bot;
+use poker::cli::fail;
+use poker::cli::number;
+use poker::protocol::RemoteBot;
+
+const USAGE: &str = "Usage: botserver [--port N] [--hands N] [--big-blind N] [--stack N] \
+                     [--timeout MS] [--seed N] [--no-duplicate] [--exec COMMAND]...";
+
+/**
+ * Hosts a heads-up match between two bots. Bots given with --exec run as subprocesses talking on
+ * their standard input and output, the rest connect to the port on localhost.
+ */
+fn main() {
+    let mut config = ArenaConfig::new(1000, 1);
+    let mut port = 9000;
+    let mut timeout = 1000;
+    let mut commands = Vec::new();
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--port" => port = number(args.next(), USAGE),
+            "--hands" => config.hands = number(args.next(), USAGE),
+            "--big-blind" => {
+                config.table.big_blind = number(args.next(), USAGE);
+                config.table.small_blind = config.table.big_blind / 2;
+            }
+            "--stack" => config.table.stack = number(args.next(), USAGE),
+            "--timeout" => timeout = number(args.next(), USAGE),
+            "--seed" => config.seed = number(args.next(), USAGE),
+            "--no-duplicate" => config.duplicate = false,
+            "--exec" => commands.push(args.next().unwrap_or_else(|| fail(USAGE))),
+            "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => fail(USAGE),
+        }
+    }
+    if commands.len() > 2 {
+        fail("A match is played by two bots");
+    }
+
+    let timeout = Duration::from_millis(timeout);
+    let mut bots = Vec::new();
+    for command in &commands {
+        bots.push(RemoteBot::spawn(command, timeout).unwrap_or_else(|error| fail(&error)));
+    }
+    if bots.len() < 2 {
+        let listener = TcpListener::bind(("127.0.0.1", port))
+            .unwrap_or_else(|error| fail(&error.to_string()));
+        eprintln!("Waiting for {} bots on port {}", 2 - bots.len(), port);
+        while bots.len() < 2 {
+            let (stream, address) =
+                listener.accept().unwrap_or_else(|error| fail(&error.to_string()));
+            match RemoteBot::connect(stream, timeout) {
+                Ok(bot) => {
+                    eprintln!("{} connected from {}", bot.name(), address);
+                    bots.push(bot);
+                }
+                Err(error) => eprintln!("{}: {}", address, error),
+            }
+        }
+    }
+
+    let mut second = bots.pop().expect("There are two bots");
+    let mut first = bots.pop().expect("There are two bots");
+    let result = arena::play(&mut first, &mut second, &config);
+    println!("{} vs {}: {:+.1} ± {:.1} bb/100 over {} hands",
+             first.name(),
+             second.name(),
+             result.bb_per_100,
+             result.confidence,
+             result.hands);
+    for bot in &[&first, &second] {
+        if bot.timeouts() > 0 {
+            println!("{} timed out {} times", bot.name(), bot.timeouts());
+        }
+        if !bot.is_connected() {
+            println!("{} disconnected", bot.name());
+        }
+    }
+}
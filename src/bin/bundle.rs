@@ -0,0 +1,108 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::env;
+use std::fs::File;
+use std::io;
+use std::io::Write;
+
+// Sources of the modules needed by the solver, on dependency order
+const MODULES: [(&str, &str); 4] = [("consts", include_str!("../consts.rs")),
+                                    ("types", include_str!("../types.rs")),
+                                    ("card", include_str!("../card.rs")),
+                                    ("hand", include_str!("../hand.rs"))];
+const LIB: &str = include_str!("../lib.rs");
+const SOLVER: &str = include_str!("solver.rs");
+
+/**
+ * strip removes the license header and the tests of a source file
+ */
+fn strip(source: &str) -> String {
+    let source = match source.find("#[cfg(test)]") {
+        Some(tests) => &source[..tests],
+        None => source,
+    };
+
+    source.lines()
+        .skip_while(|line| line.starts_with("//"))
+        .collect::<Vec<&str>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/**
+ * indent adds a level of indentation to every non empty line
+ */
+fn indent(source: &str) -> String {
+    source.lines()
+        .map(|line| if line.is_empty() { String::new() } else { format!("    {}", line) })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/**
+ * bundle builds a single source file with the solver and the modules it needs, so it can be
+ * pasted on the CodinGame IDE
+ */
+fn bundle() -> String {
+    let header: String = LIB.lines()
+        .take_while(|line| line.starts_with("//"))
+        .map(|line| format!("{}\n", line))
+        .collect();
+
+    let mut output = header;
+    output.push_str("\n// Generated by `cargo run --bin bundle`, do not edit.\n\n");
+    output.push_str("#![allow(dead_code)]\n\n");
+
+    for &(name, source) in MODULES.iter() {
+        output.push_str(&format!("mod {} {{\n{}\n}}\n\n", name, indent(&strip(source))));
+    }
+
+    // The play function without the module declarations and external crates of the library
+    let lib: Vec<&str> = LIB.lines()
+        .filter(|line| {
+            !line.starts_with("pub mod ") && !line.starts_with("mod ") &&
+            !line.starts_with("extern crate ") && !line.starts_with("#[cfg(feature")
+        })
+        .collect();
+    output.push_str(&strip(&lib.join("\n")));
+    output.push_str("\n\n");
+
+    let solver = strip(SOLVER).replace("extern crate poker;\n\n", "").replace("poker::", "");
+    output.push_str(&solver);
+    output.push('\n');
+
+    output
+}
+
+/**
+ * Writes the bundled solver on the file given as argument or on the standard output
+ */
+fn main() {
+    let source = bundle();
+
+    match env::args().nth(1) {
+        Some(path) => File::create(path).unwrap().write_all(source.as_bytes()).unwrap(),
+        None => io::stdout().write_all(source.as_bytes()).unwrap(),
+    }
+}
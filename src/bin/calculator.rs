@@ -0,0 +1,188 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+extern crate poker;
+
+use std::io;
+use std::io::BufRead;
+use std::io::IsTerminal;
+use std::io::Read;
+use std::io::Write;
+use std::process::Command;
+use std::process::Stdio;
+
+use poker::calculator::Calculator;
+use poker::calculator::Response;
+
+const PROMPT: &str = "poker> ";
+
+/**
+ * RawMode keeps the terminal on raw mode while it's alive, so keys can be read one by one
+ */
+struct RawMode {
+    saved: String, // Settings of the terminal to restore
+}
+
+impl RawMode {
+    fn enable() -> Option<RawMode> {
+        let saved = stty(&["-g"])?;
+        stty(&["-icanon", "-echo", "-isig", "min", "1"])?;
+        Some(RawMode { saved: saved.trim().to_string() })
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        stty(&[&self.saved]);
+    }
+}
+
+/**
+ * stty runs the stty command on the terminal of the standard input
+ */
+fn stty(args: &[&str]) -> Option<String> {
+    let output = Command::new("stty").args(args).stdin(Stdio::inherit()).output().ok()?;
+    if output.status.success() {
+        Some(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+        None
+    }
+}
+
+/**
+ * redraw writes again the prompt and the line being edited
+ */
+fn redraw(line: &str) {
+    print!("\r\x1b[K{}{}", PROMPT, line);
+    io::stdout().flush().unwrap();
+}
+
+/**
+ * complete replaces the last word of the line with the candidates of the calculator, or with
+ * their common prefix if there are many of them
+ */
+fn complete(calculator: &Calculator, line: &mut String) {
+    let candidates = calculator.complete(line);
+    let start = line.rfind(char::is_whitespace).map(|i| i + 1).unwrap_or(0);
+
+    match candidates.len() {
+        0 => {}
+        1 => {
+            line.truncate(start);
+            line.push_str(&candidates[0]);
+            line.push(' ');
+        }
+        _ => {
+            let mut prefix = candidates[0].clone();
+            for candidate in &candidates[1..] {
+                while !candidate.starts_with(prefix.as_str()) {
+                    prefix.pop();
+                }
+            }
+            if prefix.len() > line.len() - start {
+                line.truncate(start);
+                line.push_str(&prefix);
+            }
+            print!("\r\n{}\r\n", candidates.join("  "));
+        }
+    }
+}
+
+/**
+ * read_line reads a line key by key with history (up and down arrows) and tab completion.
+ * Returns None on Ctrl-C or on Ctrl-D with an empty line.
+ */
+fn read_line(calculator: &Calculator) -> Option<String> {
+    let history = calculator.history();
+    let mut position = history.len();
+    let mut line = String::new();
+    let mut stdin = io::stdin();
+    redraw(&line);
+
+    loop {
+        let mut key = [0u8; 1];
+        if stdin.read(&mut key).ok()? == 0 {
+            return None;
+        }
+
+        match key[0] {
+            3 => return None,
+            4 if line.is_empty() => return None,
+            b'\r' | b'\n' => {
+                print!("\r\n");
+                return Some(line);
+            }
+            8 | 127 => {
+                line.pop();
+            }
+            b'\t' => complete(calculator, &mut line),
+            27 => {
+                let mut sequence = [0u8; 2];
+                stdin.read_exact(&mut sequence).ok()?;
+                match &sequence {
+                    b"[A" if position > 0 => {
+                        position -= 1;
+                        line = history[position].clone();
+                    }
+                    b"[B" if position < history.len() => {
+                        position += 1;
+                        line = history.get(position).cloned().unwrap_or_default();
+                    }
+                    _ => {}
+                }
+            }
+            key if (32..127).contains(&key) => line.push(key as char),
+            _ => {}
+        }
+        redraw(&line);
+    }
+}
+
+/**
+ * Interactive calculator: type hole cards, board and ranges to get the best hand, the equity and
+ * the outs. When the input is not a terminal lines are read one by one without editing.
+ */
+fn main() {
+    let mut calculator = Calculator::new();
+
+    if io::stdin().is_terminal() {
+        if let Some(raw) = RawMode::enable() {
+            println!("Type :help for the list of commands\r");
+            while let Some(line) = read_line(&calculator) {
+                match calculator.execute(&line) {
+                    Response::Text(text) => print!("{}\r\n", text.replace('\n', "\r\n")),
+                    Response::Quit => break,
+                }
+            }
+            drop(raw);
+            return;
+        }
+    }
+
+    let stdin = io::stdin();
+    for line in stdin.lock().lines() {
+        match calculator.execute(&line.unwrap()) {
+            Response::Text(text) => println!("{}", text),
+            Response::Quit => break,
+        }
+    }
+}
@@ -0,0 +1,59 @@
+extern crate poker;
+
+use std::env;
+
+use poker::cli::fail;
+use poker::cli::number;
+use poker::preflop::EquityTable;
+use poker::pushfold;
+use poker::pushfold::Spot;
+
+const USAGE: &str = "Usage: pushfold [--small-blind BB] [--ante BB] [--boards N] [--seed N] \
+                     STACK";
+
+/**
+ * Computes the heads-up push/fold equilibrium for an effective stack in big blinds and writes the
+ * charts of the small blind and the big blind with the expected value of every hand
+ */
+fn main() {
+    let mut spot = Spot::new(0.0);
+    let mut boards = 2000;
+    let mut seed = 1;
+    let mut stack = None;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--small-blind" => spot.small_blind = number(args.next(), USAGE),
+            "--ante" => spot.ante = number(args.next(), USAGE),
+            "--boards" => boards = number(args.next(), USAGE),
+            "--seed" => seed = number(args.next(), USAGE),
+            "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ if stack.is_none() && !arg.starts_with("--") => {
+                stack = Some(number(Some(arg), USAGE))
+            }
+            _ => fail(USAGE),
+        }
+    }
+    spot.stack = stack.unwrap_or_else(|| fail(USAGE));
+    if spot.stack < 1.0 + spot.ante {
+        fail("The stack must cover the big blind and the ante");
+    }
+
+    let table = EquityTable::sampled(boards, seed);
+    let solution = pushfold::solve(&table, &spot, 1000);
+    let percent = |value: f64| format!("{:.0}", 100.0 * value);
+    let ev = |value: f64| format!("{:+.2}", value);
+
+    println!("Small blind shoves {:.1}% of hands", solution.shove_percentage());
+    println!("{}", pushfold::grid(&solution.shove, percent));
+    println!("Big blind calls {:.1}% of hands", solution.call_percentage());
+    println!("{}", pushfold::grid(&solution.call, percent));
+    println!("Shove EV (fold {:+.2})", spot.small_blind_fold_ev());
+    println!("{}", pushfold::grid(&solution.shove_ev, ev));
+    println!("Call EV (fold {:+.2})", spot.big_blind_fold_ev());
+    print!("{}", pushfold::grid(&solution.call_ev, ev));
+}
@@ -0,0 +1,252 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// Internal imports
+use consts;
+use card;
+use card::Card;
+use card::CardValue;
+use hand::Hand;
+use hand::HandType;
+use outs;
+
+/**
+ * Suitedness tells how the suits are distributed on the board
+ */
+#[derive(Hash, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Suitedness {
+    Monotone, // All the cards have the same suit
+    TwoTone, // Some cards share a suit but not all of them
+    Rainbow, // Every card has a different suit
+}
+
+/**
+ * Texture describes a flop, a turn or a river
+ */
+#[derive(Clone, Debug)]
+pub struct Texture {
+    pub paired: bool, // At least two cards have the same value
+    pub two_paired: bool, // There are two different pairs
+    pub trips: bool, // At least three cards have the same value
+    pub suitedness: Suitedness,
+    pub flush_possible: bool, // A player can have a flush
+    pub flush_draw_possible: bool, // A player can have a flush draw, only before the river
+    pub connectedness: usize, // Maximum number of board values inside a straight
+    pub straights: Vec<CardValue>, // Highest card of every straight that a player can have
+    pub nut_hand: HandType, // Type of the best possible hand
+    pub nut_values: Vec<CardValue>, // Values of the best possible hand
+    pub nut_combos: Vec<[Card; 2]>, // Hole cards that make the best possible hand
+}
+
+/**
+ * texture analyzes a board of 3 to 5 community cards
+ */
+pub fn texture(board: &[&Card]) -> Texture {
+    assert!(board.len() >= 3 && board.len() <= consts::TABLE_SIZE,
+            "A board has from 3 to 5 cards");
+
+    // Count how many cards share value or suit
+    let mut values = [0; 13];
+    let mut suits = [0; 4];
+    for card in board {
+        values[card.rank()] += 1;
+        suits[card.suit.index()] += 1;
+    }
+    let pairs = values.iter().filter(|count| **count >= 2).count();
+    let max_suit = *suits.iter().max().unwrap();
+
+    let suitedness = if max_suit == board.len() {
+        Suitedness::Monotone
+    } else if max_suit == 1 {
+        Suitedness::Rainbow
+    } else {
+        Suitedness::TwoTone
+    };
+
+    let (nut_hand, nut_values, nut_combos) = nuts(board);
+
+    Texture {
+        paired: pairs >= 1,
+        two_paired: pairs >= 2,
+        trips: values.iter().any(|count| *count >= 3),
+        suitedness,
+        flush_possible: max_suit >= 3,
+        flush_draw_possible: board.len() < consts::TABLE_SIZE && max_suit >= 2,
+        connectedness: connectedness(board),
+        straights: straights(board),
+        nut_hand,
+        nut_values,
+        nut_combos,
+    }
+}
+
+/**
+ * nuts finds the best possible hand on the board and all the hole cards that make it
+ */
+pub fn nuts(board: &[&Card]) -> (HandType, Vec<CardValue>, Vec<[Card; 2]>) {
+    let deck: Vec<Card> = card::deck().into_iter().filter(|card| !board.contains(&card)).collect();
+
+    let mut best: Option<(HandType, Vec<CardValue>)> = None;
+    let mut combos = Vec::new();
+    for i in 0..deck.len() {
+        for j in i + 1..deck.len() {
+            let mut cards = vec![&deck[j], &deck[i]];
+            cards.extend_from_slice(board);
+            let hand = Hand::from_cards(&cards);
+            let current = (hand.hand_type(), hand.cards().iter().map(|card| card.value).collect());
+
+            let better = match best {
+                Some(ref best) => current > *best,
+                None => true,
+            };
+            if better {
+                best = Some(current.clone());
+                combos.clear();
+            }
+            if best.as_ref() == Some(&current) {
+                combos.push([deck[j], deck[i]]);
+            }
+        }
+    }
+
+    let (hand_type, values) = best.unwrap();
+    (hand_type, values, combos)
+}
+
+/**
+ * connectedness gets the maximum number of different board values that fit inside a straight
+ */
+fn connectedness(board: &[&Card]) -> usize {
+    let values = outs::value_mask(board) as u32;
+    let ace = card::VALUES.len() - 1;
+    let extended = values << 1 | (values >> ace & 1);
+    (0..card::VALUES.len() + 1 - consts::HAND_SIZE + 1)
+        .map(|low| (extended >> low & 0b11111).count_ones() as usize)
+        .max()
+        .unwrap()
+}
+
+/**
+ * straights gets the highest card of every straight that can be made with two hole cards
+ */
+fn straights(board: &[&Card]) -> Vec<CardValue> {
+    let values = outs::value_mask(board);
+    let mut highs = Vec::new();
+    for first in 0..card::VALUES.len() {
+        for second in first..card::VALUES.len() {
+            let mask = values | 1 << first | 1 << second;
+            for high in straights_in(mask) {
+                if !highs.contains(&high) {
+                    highs.push(high);
+                }
+            }
+        }
+    }
+
+    highs.sort_by(|a, b| b.cmp(a));
+    highs.into_iter().map(|high| card::VALUES[high]).collect()
+}
+
+/**
+ * straights_in gets the rank of the highest card of every straight on a value mask
+ */
+fn straights_in(values: u16) -> Vec<usize> {
+    let mut highs = Vec::new();
+    let mut remaining = values;
+    // Removing the top card of a straight shows the next lower one, if any
+    while let Some(high) = outs::straight_high(remaining) {
+        highs.push(high);
+        remaining &= !(1 << high);
+    }
+    highs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::texture;
+    use super::Suitedness;
+    use super::super::card::Card;
+    use super::super::card::CardValue;
+    use super::super::hand::HandType;
+
+    fn parse(cards: &str) -> Vec<Card> {
+        cards.split_whitespace()
+            .map(|card| Card::new(card.chars().next().unwrap(), card.chars().nth(1).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_monotone_connected_flop() {
+        let board = parse("AS KS QS");
+        let texture = texture(&[&board[0], &board[1], &board[2]]);
+
+        assert_eq!(texture.suitedness, Suitedness::Monotone);
+        assert!(texture.flush_possible);
+        assert!(!texture.paired);
+        assert_eq!(texture.connectedness, 3);
+        assert_eq!(texture.straights, vec![CardValue::ValueA]);
+        assert_eq!(texture.nut_hand, HandType::StraightFlush);
+        assert_eq!(texture.nut_combos.len(), 1);
+        assert!(texture.nut_combos[0].contains(&Card::new('J', 'S')));
+        assert!(texture.nut_combos[0].contains(&Card::new('T', 'S')));
+    }
+
+    #[test]
+    fn test_paired_rainbow_flop() {
+        let board = parse("7C 7D 2H");
+        let texture = texture(&[&board[0], &board[1], &board[2]]);
+
+        assert_eq!(texture.suitedness, Suitedness::Rainbow);
+        assert!(texture.paired);
+        assert!(!texture.trips);
+        assert!(!texture.flush_possible);
+        assert_eq!(texture.nut_hand, HandType::FourOfAKind);
+        assert_eq!(texture.nut_combos.len(), 1);
+    }
+
+    #[test]
+    fn test_dry_turn() {
+        let board = parse("KH 9H 4C 2D");
+        let texture = texture(&[&board[0], &board[1], &board[2], &board[3]]);
+
+        assert_eq!(texture.suitedness, Suitedness::TwoTone);
+        assert!(!texture.flush_possible);
+        assert!(texture.flush_draw_possible);
+        assert!(texture.straights.is_empty());
+        assert_eq!(texture.nut_hand, HandType::ThreeOfAKind);
+        assert_eq!(texture.nut_values[..3], [CardValue::ValueK; 3]);
+        assert_eq!(texture.nut_combos.len(), 3);
+    }
+
+    #[test]
+    fn test_possible_straights() {
+        let board = parse("9C 8D 2H 5S 6C");
+        let texture = texture(&[&board[0], &board[1], &board[2], &board[3], &board[4]]);
+
+        assert_eq!(texture.straights,
+                   vec![CardValue::ValueT,
+                        CardValue::Value9,
+                        CardValue::Value8,
+                        CardValue::Value6]);
+        assert_eq!(texture.connectedness, 4);
+    }
+}
@@ -0,0 +1,221 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// External imports
+use std::fmt;
+
+// Internal imports
+use card::Card;
+use types::Street;
+
+/**
+ * Chips are counted as whole units, the big blind being usually 100 of them
+ */
+pub type Chips = u64;
+
+/**
+ * Action is a decision of a bot. A raise carries the whole bet of the player on the street after
+ * raising, and it's also used for the first bet of a street.
+ */
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Action {
+    Fold,
+    Check,
+    Call,
+    Raise(Chips),
+}
+
+/**
+ * Actions are written as "fold", "check", "call" or "raise 300"
+ */
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Action::Fold => write!(f, "fold"),
+            Action::Check => write!(f, "check"),
+            Action::Call => write!(f, "call"),
+            Action::Raise(to) => write!(f, "raise {}", to),
+        }
+    }
+}
+
+impl Action {
+    /**
+     * parse reads an action written like its Display
+     */
+    pub fn parse(text: &str) -> Option<Action> {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        match words[..] {
+            ["fold"] => Some(Action::Fold),
+            ["check"] => Some(Action::Check),
+            ["call"] => Some(Action::Call),
+            ["raise", to] => to.parse().ok().map(Action::Raise),
+            _ => None,
+        }
+    }
+}
+
+/**
+ * LegalActions tells what the player to act can do
+ */
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct LegalActions {
+    pub check: bool,
+    pub call: Option<Chips>, // Chips needed to call, less than the bet if calling all-in
+    pub raise: Option<(Chips, Chips)>, // Smallest and largest whole bet after raising
+}
+
+impl LegalActions {
+    /**
+     * fold tells if the player can fold, which is only allowed when facing a bet
+     */
+    pub fn fold(&self) -> bool {
+        self.call.is_some()
+    }
+
+    /**
+     * contains checks if an action is legal
+     */
+    pub fn contains(&self, action: &Action) -> bool {
+        match *action {
+            Action::Fold => self.fold(),
+            Action::Check => self.check,
+            Action::Call => self.call.is_some(),
+            Action::Raise(to) => self.raise.is_some_and(|(min, max)| (min..=max).contains(&to)),
+        }
+    }
+
+    /**
+     * coerce turns any action into a legal one: raises are clamped to the legal amounts or
+     * become calls, calls become checks when there is nothing to call and anything else becomes
+     * a check or, facing a bet, a fold
+     */
+    pub fn coerce(&self, action: &Action) -> Action {
+        match *action {
+            _ if self.contains(action) => *action,
+            Action::Raise(to) => match self.raise {
+                Some((min, max)) => Action::Raise(to.max(min).min(max)),
+                None => self.coerce(&Action::Call),
+            },
+            Action::Call if self.check => Action::Check,
+            _ if self.check => Action::Check,
+            _ => Action::Fold,
+        }
+    }
+}
+
+/**
+ * Event is an action taken on a hand
+ */
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Event {
+    pub seat: usize,
+    pub street: Street,
+    pub action: Action,
+}
+
+/**
+ * Observation is what a bot knows when it has to act. Seats are 0 and 1 and the player on the
+ * button posts the small blind.
+ */
+#[derive(Clone, Debug)]
+pub struct Observation {
+    pub seat: usize,
+    pub button: usize,
+    pub hole: [Card; 2],
+    pub board: Vec<Card>,
+    pub street: Street,
+    pub pot: Chips, // Chips on the pot, the bets of the street included
+    pub stacks: [Chips; 2], // Chips behind of every seat
+    pub bets: [Chips; 2], // Bets of every seat on the street
+    pub big_blind: Chips,
+    pub history: Vec<Event>,
+    pub legal: LegalActions,
+}
+
+/**
+ * HandResult is the end of a hand: the board dealt, the hole cards shown at showdown and the
+ * chips won or lost by every seat
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct HandResult {
+    pub button: usize,
+    pub board: Vec<Card>,
+    pub shown: [Option<[Card; 2]>; 2],
+    pub winnings: [i64; 2],
+    pub history: Vec<Event>,
+}
+
+/**
+ * Bot is a player that decides what to do given an observation. Actions that are not legal are
+ * coerced to legal ones.
+ */
+pub trait Bot {
+    /**
+     * name gets the name of the bot, used on reports
+     */
+    fn name(&self) -> String;
+
+    /**
+     * hand_started tells the bot a new hand was dealt, before anybody acts
+     */
+    fn hand_started(&mut self, _observation: &Observation) {}
+
+    /**
+     * act gets the action of the bot
+     */
+    fn act(&mut self, observation: &Observation) -> Action;
+
+    /**
+     * hand_over tells the bot how a hand ended, from its seat
+     */
+    fn hand_over(&mut self, _seat: usize, _result: &HandResult) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Action;
+    use super::LegalActions;
+
+    #[test]
+    fn test_coerce() {
+        let facing_bet = LegalActions {
+            check: false,
+            call: Some(200),
+            raise: Some((500, 1000)),
+        };
+        assert_eq!(facing_bet.coerce(&Action::Raise(2000)), Action::Raise(1000));
+        assert_eq!(facing_bet.coerce(&Action::Raise(300)), Action::Raise(500));
+        assert_eq!(facing_bet.coerce(&Action::Check), Action::Fold);
+        assert_eq!(facing_bet.coerce(&Action::Call), Action::Call);
+
+        let unopened = LegalActions {
+            check: true,
+            call: None,
+            raise: None,
+        };
+        assert_eq!(unopened.coerce(&Action::Raise(300)), Action::Check);
+        assert_eq!(unopened.coerce(&Action::Fold), Action::Check);
+        assert_eq!(Action::parse("raise 300"), Some(Action::Raise(300)));
+        assert_eq!(Action::parse(&Action::Fold.to_string()), Some(Action::Fold));
+    }
+}
@@ -0,0 +1,427 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// External imports
+use std::fmt::Write;
+
+// Internal imports
+use types;
+use consts;
+use card;
+use card::Card;
+use hand::Hand;
+use range::Range;
+use strength;
+use outs;
+use description;
+
+// Commands understood by the calculator
+const COMMANDS: [&str; 7] = [":set", ":clear", ":show", ":history", ":help", ":quit", ":exit"];
+
+// Things that can be changed with :set and :clear
+const TARGETS: [&str; 3] = ["hole", "board", "range"];
+
+// Samples used to estimate the equity when there are too many runouts to enumerate them
+const SAMPLES: usize = 20000;
+
+const HELP: &str = "Type hole cards, optionally followed by the board and a range after 'vs':
+    AhKh
+    AhKh QsJs2c vs QQ+, AK
+Commands:
+    :set hole|board|range <cards or range>
+    :clear board|range
+    :show
+    :history
+    :quit";
+
+/**
+ * Response is the output of a line typed on the calculator
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub enum Response {
+    Text(String),
+    Quit,
+}
+
+/**
+ * Calculator keeps the state of an interactive session: the hole cards of the player, the board
+ * and the range of the opponent
+ */
+pub struct Calculator {
+    hole: Vec<Card>,
+    board: Vec<Card>,
+    range: Range,
+    range_text: String,
+    history: Vec<String>,
+}
+
+impl Default for Calculator {
+    fn default() -> Calculator {
+        Calculator::new()
+    }
+}
+
+impl Calculator {
+    /**
+     * new creates a calculator with no cards and a random opponent range
+     */
+    pub fn new() -> Calculator {
+        Calculator {
+            hole: Vec::new(),
+            board: Vec::new(),
+            range: Range::uniform(),
+            range_text: String::from("random"),
+            history: Vec::new(),
+        }
+    }
+
+    /**
+     * history gets the lines typed so far
+     */
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /**
+     * execute runs a line typed by the user. Errors are reported as text so the session can go on.
+     */
+    pub fn execute(&mut self, line: &str) -> Response {
+        let line = line.trim();
+        if line.is_empty() {
+            return Response::Text(String::new());
+        }
+        self.history.push(line.to_string());
+
+        let result = if line.starts_with(':') {
+            self.command(line)
+        } else {
+            self.situation(line)
+        };
+
+        match result {
+            Ok(response) => response,
+            Err(error) => Response::Text(format!("Error: {}", error)),
+        }
+    }
+
+    /**
+     * command runs one of the commands starting with a colon
+     */
+    fn command(&mut self, line: &str) -> Result<Response, String> {
+        let mut words = line.splitn(3, char::is_whitespace);
+        let command = words.next().unwrap();
+        let target = words.next().unwrap_or("");
+        let argument = words.next().unwrap_or("").trim();
+
+        match command {
+            ":set" => {
+                match target {
+                    "hole" => self.set_hole(parse_cards(argument)?)?,
+                    "board" => self.set_board(parse_cards(argument)?)?,
+                    "range" => self.set_range(argument)?,
+                    _ => return Err(format!("Unknown target '{}'", target)),
+                }
+                Ok(Response::Text(self.report()))
+            }
+            ":clear" => {
+                match target {
+                    "board" => self.board.clear(),
+                    "range" => self.set_range("random")?,
+                    _ => return Err(format!("Unknown target '{}'", target)),
+                }
+                Ok(Response::Text(self.report()))
+            }
+            ":show" => Ok(Response::Text(self.report())),
+            ":history" => {
+                let lines: Vec<String> = self.history
+                    .iter()
+                    .enumerate()
+                    .map(|(i, line)| format!("{:4}  {}", i + 1, line))
+                    .collect();
+                Ok(Response::Text(lines.join("\n")))
+            }
+            ":help" => Ok(Response::Text(String::from(HELP))),
+            ":quit" | ":exit" => Ok(Response::Quit),
+            _ => Err(format!("Unknown command '{}', type :help", command)),
+        }
+    }
+
+    /**
+     * situation reads the hole cards, the board and the range from a single line
+     */
+    fn situation(&mut self, line: &str) -> Result<Response, String> {
+        let (cards, range) = match line.find(" vs ") {
+            Some(vs) => (&line[..vs], Some(line[vs + 4..].trim())),
+            None => (line, None),
+        };
+
+        let mut cards = parse_cards(cards)?;
+        if cards.len() < consts::HOLE_SIZE {
+            return Err(String::from("Two hole cards are needed"));
+        }
+        let board = cards.split_off(consts::HOLE_SIZE);
+
+        self.set_cards(cards, board)?;
+        if let Some(range) = range {
+            self.set_range(range)?;
+        }
+        Ok(Response::Text(self.report()))
+    }
+
+    fn set_hole(&mut self, hole: Vec<Card>) -> Result<(), String> {
+        let board = self.board.clone();
+        self.set_cards(hole, board)
+    }
+
+    fn set_board(&mut self, board: Vec<Card>) -> Result<(), String> {
+        let hole = self.hole.clone();
+        self.set_cards(hole, board)
+    }
+
+    /**
+     * set_cards validates the hole cards and the board together, so neither of them changes when
+     * any of them is wrong
+     */
+    fn set_cards(&mut self, hole: Vec<Card>, board: Vec<Card>) -> Result<(), String> {
+        if hole.len() != consts::HOLE_SIZE {
+            return Err(String::from("Two hole cards are needed"));
+        }
+        if types::Street::from_board_size(board.len()).is_none() {
+            return Err(String::from("The board has 0, 3, 4 or 5 cards"));
+        }
+        check_duplicates(&hole, &board)?;
+        self.hole = hole;
+        self.board = board;
+        Ok(())
+    }
+
+    fn set_range(&mut self, text: &str) -> Result<(), String> {
+        self.range = Range::parse(text)?;
+        if self.range.is_empty() {
+            return Err(String::from("The range is empty"));
+        }
+        self.range_text = text.to_string();
+        Ok(())
+    }
+
+    /**
+     * report describes the current situation: best hand, equity against the range and outs
+     */
+    pub fn report(&self) -> String {
+        let mut report = String::new();
+        if self.hole.is_empty() {
+            return String::from("No hole cards, type :help");
+        }
+
+        writeln!(report, "Hole:   {}", join_cards(&self.hole)).unwrap();
+        writeln!(report, "Board:  {}", join_cards(&self.board)).unwrap();
+        writeln!(report, "Range:  {} ({} combos)", self.range_text, self.range.len()).unwrap();
+
+        let hole: types::Hole = [&self.hole[0], &self.hole[1]];
+        let board: Vec<&Card> = self.board.iter().collect();
+
+        if board.len() >= 3 {
+            let mut cards = hole.to_vec();
+            cards.extend_from_slice(&board);
+            let hand = Hand::from_cards(&cards);
+            writeln!(report,
+                     "Hand:   {} {} ({})",
+                     hand.get_hand_type(),
+                     hand,
+                     description::describe(&hand))
+                .unwrap();
+        }
+
+        // Enumerate the runouts when they are few, otherwise estimate the equity
+        let equity = if board.len() >= 4 {
+            strength::equity(hole, &board, &self.range)
+        } else {
+            strength::sampled_equity(hole, &board, &self.range, SAMPLES, 0)
+        };
+        write!(report, "Equity: {:.1}%", equity * 100.0).unwrap();
+
+        if board.len() >= 3 && board.len() < consts::TABLE_SIZE {
+            let outs = outs::outs(hole, &board, None);
+            let cards: Vec<Card> = outs.iter().map(|out| out.card).collect();
+            write!(report, "\nOuts:   {} {}", outs.len(), join_cards(&cards)).unwrap();
+
+            let draws = outs::draws(hole, &board);
+            if !draws.is_empty() {
+                let names: Vec<String> = draws.iter().map(|draw| format!("{:?}", draw)).collect();
+                write!(report, "\nDraws:  {}", names.join(", ")).unwrap();
+            }
+        }
+
+        report
+    }
+
+    /**
+     * complete gets the candidates to complete the last word of a line: commands, targets of the
+     * commands or the cards that are still on the deck
+     */
+    pub fn complete(&self, line: &str) -> Vec<String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let last = if line.ends_with(char::is_whitespace) {
+            ""
+        } else {
+            words.last().cloned().unwrap_or("")
+        };
+        let position = if last.is_empty() { words.len() } else { words.len() - 1 };
+
+        let candidates: Vec<String> = if position == 0 && last.starts_with(':') {
+            COMMANDS.iter().map(|command| command.to_string()).collect()
+        } else if position == 1 && (words[0] == ":set" || words[0] == ":clear") {
+            TARGETS.iter().map(|target| target.to_string()).collect()
+        } else {
+            // Cards can be written together, so only the last incomplete card is completed
+            let length = last.chars().count();
+            let written: String = last.chars().take(length - length % 2).collect();
+            let used = match parse_cards(&written) {
+                Ok(used) => used,
+                Err(_) => return Vec::new(),
+            };
+            card::deck()
+                .iter()
+                .filter(|card| !used.contains(card))
+                .filter(|card| !self.hole.contains(card) && !self.board.contains(card))
+                .map(|card| format!("{}{}", written, card_name(card)))
+                .collect()
+        };
+
+        candidates.into_iter()
+            .filter(|candidate| candidate.to_lowercase().starts_with(&last.to_lowercase()))
+            .collect()
+    }
+}
+
+/**
+ * parse_cards reads a list of cards, separated by spaces or not
+ */
+pub fn parse_cards(text: &str) -> Result<Vec<Card>, String> {
+    let chars: Vec<char> = text.chars().filter(|c| !c.is_whitespace() && *c != ',').collect();
+    if !chars.len().is_multiple_of(2) {
+        return Err(format!("Invalid cards '{}'", text.trim()));
+    }
+
+    let mut cards: Vec<Card> = Vec::new();
+    for pair in chars.chunks(2) {
+        let text: String = pair.iter().collect();
+        let card = Card::parse(&text).ok_or_else(|| format!("Invalid card '{}'", text))?;
+        if cards.contains(&card) {
+            return Err(format!("Card '{}' is repeated", text));
+        }
+        cards.push(card);
+    }
+    Ok(cards)
+}
+
+/**
+ * check_duplicates fails if a card is both on the hole cards and the board
+ */
+fn check_duplicates(hole: &[Card], board: &[Card]) -> Result<(), String> {
+    match hole.iter().find(|card| board.contains(card)) {
+        Some(card) => Err(format!("Card '{}' is repeated", card_name(card))),
+        None => Ok(()),
+    }
+}
+
+/**
+ * card_name writes a card as its value and a lowercase suit, for example "Ah"
+ */
+fn card_name(card: &Card) -> String {
+    card.to_string()
+}
+
+/**
+ * join_cards writes a list of cards separated by spaces
+ */
+fn join_cards(cards: &[Card]) -> String {
+    if cards.is_empty() {
+        return String::from("-");
+    }
+    cards.iter().map(card_name).collect::<Vec<String>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Calculator;
+    use super::Response;
+
+    fn text(response: Response) -> String {
+        match response {
+            Response::Text(text) => text,
+            Response::Quit => panic!("Unexpected quit"),
+        }
+    }
+
+    #[test]
+    fn test_situation() {
+        let mut calculator = Calculator::new();
+        let report = text(calculator.execute("AhKh QhJh2c vs QQ+"));
+
+        assert!(report.contains("Hole:   Ah Kh"));
+        assert!(report.contains("Board:  Qh Jh 2c"));
+        assert!(report.contains("Range:  QQ+ (18 combos)"));
+        assert!(report.contains("Hand:   HIGH_CARD AKQJ2"));
+        assert!(report.contains("Draws:  FlushDraw, Gutshot, ComboDraw"));
+
+        // The new hole cards are only checked against the new board
+        let report = text(calculator.execute("QhJh 2d3d4d"));
+        assert!(report.contains("Hole:   Qh Jh"));
+        assert!(report.contains("Board:  2d 3d 4d"));
+
+        // Nothing changes when the board is wrong
+        assert!(text(calculator.execute("AhKh 2c3c")).starts_with("Error"));
+        assert!(text(calculator.execute(":set range QQ+")).contains("Hole:   Qh Jh"));
+    }
+
+    #[test]
+    fn test_commands() {
+        let mut calculator = Calculator::new();
+        calculator.execute("AsAd");
+        let report = text(calculator.execute(":set board Ac 7d 2h 9s 3c"));
+        assert!(report.contains("Hand:   THREE_OF_A_KIND AAA97"));
+        assert!(!report.contains("Outs"));
+
+        let report = text(calculator.execute(":set board Ah 7d 2h"));
+        assert!(report.contains("Outs:   "));
+        assert_eq!(text(calculator.execute(":set board As 7d 2h")),
+                   "Error: Card 'As' is repeated");
+        assert!(text(calculator.execute(":nope")).starts_with("Error: Unknown command"));
+
+        assert_eq!(calculator.execute(":quit"), Response::Quit);
+        assert_eq!(calculator.history().len(), 6);
+        assert_eq!(calculator.history()[1], ":set board Ac 7d 2h 9s 3c");
+    }
+
+    #[test]
+    fn test_complete() {
+        let mut calculator = Calculator::new();
+        assert_eq!(calculator.complete(":se"), vec![":set"]);
+        assert_eq!(calculator.complete(":set b"), vec!["board"]);
+
+        calculator.execute("AhKh");
+        assert_eq!(calculator.complete("Ah Kh Q"), vec!["Qc", "Qd", "Qh", "Qs"]);
+        assert_eq!(calculator.complete("AhKhA"), vec!["AhKhAc", "AhKhAd", "AhKhAs"]);
+        assert!(calculator.complete("A€").is_empty());
+    }
+}
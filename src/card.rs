@@ -20,10 +20,13 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+// External imports
+use std::fmt;
+
 /**
  * CardValue represents any of the possible values of a Card
  */
-#[derive(Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[derive(Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
 pub enum CardValue {
     None,
     Value2 = 2,
@@ -44,7 +47,7 @@ pub enum CardValue {
 /**
  * CardValue represents any of the possible suits of a Card
  */
-#[derive(Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
 pub enum CardSuit {
     None,
     Clubs,
@@ -56,7 +59,7 @@ pub enum CardSuit {
 /**
  * A Card has a value (numeric value) and a suit
  */
-#[derive(Hash, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Hash, Eq, PartialEq, PartialOrd, Ord, Clone, Copy, Debug)]
 pub struct Card {
     pub value: CardValue,
     pub suit: CardSuit,
@@ -67,47 +70,208 @@ impl Card {
      * Creates a new instance of a Card given the value and the suit as chars
      */
     pub fn new(value_char: char, suit_char: char) -> Card {
-        let value: CardValue;
-        let suit: CardSuit;
-
         // Parse value of the card
-        match value_char {
-            '2' => value = CardValue::Value2,
-            '3' => value = CardValue::Value3,
-            '4' => value = CardValue::Value4,
-            '5' => value = CardValue::Value5,
-            '6' => value = CardValue::Value6,
-            '7' => value = CardValue::Value7,
-            '8' => value = CardValue::Value8,
-            '9' => value = CardValue::Value9,
-            'T' => value = CardValue::ValueT,
-            'J' => value = CardValue::ValueJ,
-            'Q' => value = CardValue::ValueQ,
-            'K' => value = CardValue::ValueK,
-            'A' => value = CardValue::ValueA,
+        let value = match value_char {
+            '2' => CardValue::Value2,
+            '3' => CardValue::Value3,
+            '4' => CardValue::Value4,
+            '5' => CardValue::Value5,
+            '6' => CardValue::Value6,
+            '7' => CardValue::Value7,
+            '8' => CardValue::Value8,
+            '9' => CardValue::Value9,
+            'T' => CardValue::ValueT,
+            'J' => CardValue::ValueJ,
+            'Q' => CardValue::ValueQ,
+            'K' => CardValue::ValueK,
+            'A' => CardValue::ValueA,
             _ => {
                 println!("Unknown value");
-                value = CardValue::None;
+                CardValue::None
             }
-        }
+        };
 
         // Parse suit of the card
-        match suit_char {
-            'C' => suit = CardSuit::Clubs,
-            'D' => suit = CardSuit::Diamonds,
-            'H' => suit = CardSuit::Hearts,
-            'S' => suit = CardSuit::Spades,
+        let suit = match suit_char {
+            'C' => CardSuit::Clubs,
+            'D' => CardSuit::Diamonds,
+            'H' => CardSuit::Hearts,
+            'S' => CardSuit::Spades,
             _ => {
                 println!("Unknown suit");
-                suit = CardSuit::None;
+                CardSuit::None
             }
+        };
+
+        Card { value, suit }
+    }
+
+    /**
+     * Parses a card written as its value and its suit, for example "Ah" or "TD". Returns None if
+     * the text is not a valid card.
+     */
+    pub fn parse(text: &str) -> Option<Card> {
+        let chars: Vec<char> = text.chars().collect();
+        if chars.len() != 2 {
+            return None;
+        }
+
+        let value = chars[0].to_ascii_uppercase();
+        let suit = chars[1].to_ascii_uppercase();
+        if "23456789TJQKA".contains(value) && "CDHS".contains(suit) {
+            Some(Card::new(value, suit))
+        } else {
+            None
         }
+    }
 
+    /**
+     * Creates a Card from its position on a 52 card deck. Cards are ordered by value and then by
+     * suit, so 0 is the 2 of Clubs and 51 the Ace of Spades.
+     */
+    pub fn from_index(index: usize) -> Card {
         Card {
-            value: value,
-            suit: suit,
+            value: VALUES[index / SUITS.len()],
+            suit: SUITS[index % SUITS.len()],
         }
     }
+
+    /**
+     * Position of the Card on a 52 card deck. It's the inverse of from_index.
+     */
+    pub fn index(&self) -> usize {
+        self.rank() * SUITS.len() + self.suit.index()
+    }
+
+    /**
+     * Rank of the value of the Card, starting at 0 for a 2 and ending at 12 for an Ace
+     */
+    pub fn rank(&self) -> usize {
+        self.value.rank()
+    }
+}
+
+impl CardValue {
+    /**
+     * symbol gets the character used to represent the value, for example 'T' for a Ten
+     */
+    pub fn symbol(&self) -> char {
+        match *self {
+            CardValue::None => '?',
+            CardValue::Value2 => '2',
+            CardValue::Value3 => '3',
+            CardValue::Value4 => '4',
+            CardValue::Value5 => '5',
+            CardValue::Value6 => '6',
+            CardValue::Value7 => '7',
+            CardValue::Value8 => '8',
+            CardValue::Value9 => '9',
+            CardValue::ValueT => 'T',
+            CardValue::ValueJ => 'J',
+            CardValue::ValueQ => 'Q',
+            CardValue::ValueK => 'K',
+            CardValue::ValueA => 'A',
+        }
+    }
+
+    /**
+     * from_symbol gets the value written as a character in any case, for example 'T' for a Ten
+     */
+    pub fn from_symbol(symbol: char) -> Option<CardValue> {
+        let symbol = symbol.to_ascii_uppercase();
+        VALUES.iter().cloned().find(|value| value.symbol() == symbol)
+    }
+
+    /**
+     * Rank of the value, starting at 0 for a 2 and ending at 12 for an Ace
+     */
+    pub fn rank(&self) -> usize {
+        *self as usize - CardValue::Value2 as usize
+    }
+}
+
+impl CardSuit {
+    /**
+     * Position of the suit on the SUITS array, starting at 0 for Clubs
+     */
+    pub fn index(&self) -> usize {
+        *self as usize - CardSuit::Clubs as usize
+    }
+
+    /**
+     * from_symbol gets the suit written as a character in any case, for example 'h' for Hearts
+     */
+    pub fn from_symbol(symbol: char) -> Option<CardSuit> {
+        let symbol = symbol.to_ascii_lowercase();
+        SUITS.iter().cloned().find(|suit| suit.symbol() == symbol)
+    }
+
+    /**
+     * symbol gets the lowercase character used to represent the suit, for example 'h' for Hearts
+     */
+    pub fn symbol(&self) -> char {
+        match *self {
+            CardSuit::None => '?',
+            CardSuit::Clubs => 'c',
+            CardSuit::Diamonds => 'd',
+            CardSuit::Hearts => 'h',
+            CardSuit::Spades => 's',
+        }
+    }
+
+    /**
+     * glyph gets the Unicode symbol of the suit, for example '♥' for Hearts
+     */
+    pub fn glyph(&self) -> char {
+        match *self {
+            CardSuit::None => '?',
+            CardSuit::Clubs => '♣',
+            CardSuit::Diamonds => '♦',
+            CardSuit::Hearts => '♥',
+            CardSuit::Spades => '♠',
+        }
+    }
+}
+
+/**
+ * Cards are written as their value and a lowercase suit, like "As", or with the Unicode symbol of
+ * the suit with the alternate flag, like "A♠"
+ */
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let suit = if f.alternate() { self.suit.glyph() } else { self.suit.symbol() };
+        f.pad(&format!("{}{}", self.value.symbol(), suit))
+    }
+}
+
+/**
+ * All the valid values of a Card in ascending order
+ */
+pub const VALUES: [CardValue; 13] = [CardValue::Value2,
+                                     CardValue::Value3,
+                                     CardValue::Value4,
+                                     CardValue::Value5,
+                                     CardValue::Value6,
+                                     CardValue::Value7,
+                                     CardValue::Value8,
+                                     CardValue::Value9,
+                                     CardValue::ValueT,
+                                     CardValue::ValueJ,
+                                     CardValue::ValueQ,
+                                     CardValue::ValueK,
+                                     CardValue::ValueA];
+
+/**
+ * All the valid suits of a Card
+ */
+pub const SUITS: [CardSuit; 4] =
+    [CardSuit::Clubs, CardSuit::Diamonds, CardSuit::Hearts, CardSuit::Spades];
+
+/**
+ * Builds the 52 cards of a deck ordered by index
+ */
+pub fn deck() -> Vec<Card> {
+    (0..VALUES.len() * SUITS.len()).map(Card::from_index).collect()
 }
 
 #[cfg(test)]
@@ -127,4 +291,29 @@ mod tests {
         assert!(card2.value == CardValue::Value7);
         assert!(card2.suit == CardSuit::Clubs);
     }
+
+    #[test]
+    fn test_card_index() {
+        for index in 0..52 {
+            assert_eq!(Card::from_index(index).index(), index);
+        }
+
+        let card = Card::new('A', 'S');
+        assert_eq!(card.index(), 51);
+        assert_eq!(card.rank(), 12);
+        assert_eq!(Card::from_index(0), Card::new('2', 'C'));
+        assert_eq!(CardValue::from_symbol('t'), Some(CardValue::ValueT));
+        assert_eq!(CardValue::from_symbol('1'), None);
+        assert_eq!(CardSuit::from_symbol('H'), Some(CardSuit::Hearts));
+        assert_eq!(CardSuit::from_symbol('x'), None);
+        assert_eq!(CardValue::ValueA.rank(), 12);
+    }
+
+    #[test]
+    fn test_parse_card() {
+        assert_eq!(Card::parse("Ah"), Some(Card::new('A', 'H')));
+        assert_eq!(Card::parse("tD"), Some(Card::new('T', 'D')));
+        assert_eq!(Card::parse("1H"), None);
+        assert_eq!(Card::parse("AHS"), None);
+    }
 }
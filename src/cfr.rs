@@ -0,0 +1,383 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// External imports
+use std::collections::HashMap;
+
+// Internal imports
+use game::Game;
+
+/**
+ * Strategy is the probability of every action on every information set
+ */
+pub type Strategy = HashMap<String, Vec<f64>>;
+
+/**
+ * Variant of counterfactual regret minimization
+ */
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Variant {
+    Vanilla, // Regret matching and a uniform average of the strategies
+    Plus, // Regrets floored at zero and an average weighted by iteration (CFR+)
+}
+
+/**
+ * Node keeps the accumulated regrets and strategies of an information set
+ */
+#[derive(Clone, Debug)]
+struct Node {
+    regrets: Vec<f64>,
+    updates: Vec<f64>, // Regrets of the current iteration, applied once it's finished
+    strategy_sum: Vec<f64>,
+}
+
+impl Node {
+    fn new(actions: usize) -> Node {
+        Node {
+            regrets: vec![0.0; actions],
+            updates: vec![0.0; actions],
+            strategy_sum: vec![0.0; actions],
+        }
+    }
+
+    /**
+     * strategy gets the current strategy by regret matching
+     */
+    fn strategy(&self) -> Vec<f64> {
+        normalize(self.regrets.iter().map(|regret| regret.max(0.0)).collect())
+    }
+
+    /**
+     * update adds the regrets of the current iteration, flooring them at zero for CFR+
+     */
+    fn update(&mut self, variant: Variant) {
+        for (regret, update) in self.regrets.iter_mut().zip(self.updates.iter_mut()) {
+            *regret += *update;
+            if variant == Variant::Plus {
+                *regret = regret.max(0.0);
+            }
+            *update = 0.0;
+        }
+    }
+
+    /**
+     * average gets the average strategy of all the iterations
+     */
+    fn average(&self) -> Vec<f64> {
+        normalize(self.strategy_sum.clone())
+    }
+}
+
+/**
+ * Solver computes an approximate Nash equilibrium of a game with counterfactual regret
+ * minimization. The players update their regrets alternately on every iteration.
+ */
+pub struct Solver<G: Game> {
+    game: G,
+    variant: Variant,
+    nodes: HashMap<String, Node>,
+    iterations: u64,
+}
+
+impl<G: Game> Solver<G> {
+    /**
+     * Creates a solver of a game
+     */
+    pub fn new(game: G, variant: Variant) -> Solver<G> {
+        Solver {
+            game,
+            variant,
+            nodes: HashMap::new(),
+            iterations: 0,
+        }
+    }
+
+    /**
+     * game gets the solved game
+     */
+    pub fn game(&self) -> &G {
+        &self.game
+    }
+
+    /**
+     * iterations gets the number of iterations done
+     */
+    pub fn iterations(&self) -> u64 {
+        self.iterations
+    }
+
+    /**
+     * train runs a number of iterations
+     */
+    pub fn train(&mut self, iterations: u64) {
+        for _ in 0..iterations {
+            self.iterate();
+        }
+    }
+
+    /**
+     * iterate traverses the game tree once for every player, updating its regrets. The strategy
+     * stays the same during a traversal, as an information set is visited once for every chance
+     * outcome hidden to the player.
+     */
+    pub fn iterate(&mut self) {
+        self.iterations += 1;
+        for player in 0..2 {
+            let root = self.game.root();
+            self.traverse(&root, player, 1.0, 1.0);
+            let variant = self.variant;
+            for node in self.nodes.values_mut() {
+                node.update(variant);
+            }
+        }
+    }
+
+    /**
+     * average_strategy gets the average strategy of an information set, if visited
+     */
+    pub fn average_strategy(&self, information_set: &str) -> Option<Vec<f64>> {
+        self.nodes.get(information_set).map(|node| node.average())
+    }
+
+    /**
+     * strategy gets the average strategy of every information set, which converges to a Nash
+     * equilibrium
+     */
+    pub fn strategy(&self) -> Strategy {
+        self.nodes.iter().map(|(key, node)| (key.clone(), node.average())).collect()
+    }
+
+    /**
+     * traverse gets the expected utility of the traverser on a state, updating the regrets of its
+     * information sets. Reaches are the probabilities of getting to the state by the traverser
+     * and by the opponent and chance.
+     */
+    fn traverse(&mut self,
+                state: &G::State,
+                traverser: usize,
+                reach: f64,
+                other_reach: f64)
+                -> f64 {
+        if self.game.is_terminal(state) {
+            return self.game.payoff(state, traverser);
+        }
+        if self.game.is_chance(state) {
+            return self.game
+                .chance_outcomes(state)
+                .iter()
+                .map(|&(ref outcome, probability)| {
+                    let next = self.game.apply(state, outcome);
+                    probability * self.traverse(&next, traverser, reach, other_reach * probability)
+                })
+                .sum();
+        }
+
+        let actions = self.game.actions(state);
+        let key = self.game.information_set(state);
+        let strategy = self.nodes
+            .entry(key.clone())
+            .or_insert_with(|| Node::new(actions.len()))
+            .strategy();
+
+        if self.game.player(state) != traverser {
+            return actions.iter()
+                .zip(strategy.iter())
+                .map(|(action, &probability)| {
+                    let next = self.game.apply(state, action);
+                    probability * self.traverse(&next, traverser, reach, other_reach * probability)
+                })
+                .sum();
+        }
+
+        let utilities: Vec<f64> = actions.iter()
+            .zip(strategy.iter())
+            .map(|(action, &probability)| {
+                let next = self.game.apply(state, action);
+                self.traverse(&next, traverser, reach * probability, other_reach)
+            })
+            .collect();
+        let utility: f64 = utilities.iter().zip(strategy.iter()).map(|(u, p)| u * p).sum();
+
+        let weight = match self.variant {
+            Variant::Vanilla => reach,
+            Variant::Plus => reach * self.iterations as f64,
+        };
+        let node = self.nodes.get_mut(&key).unwrap();
+        for (i, action_utility) in utilities.iter().enumerate() {
+            node.updates[i] += other_reach * (action_utility - utility);
+            node.strategy_sum[i] += weight * strategy[i];
+        }
+
+        utility
+    }
+}
+
+/**
+ * expected_value gets the expected utility of a player when both players follow a strategy.
+ * Information sets missing on the strategy are played uniformly.
+ */
+pub fn expected_value<G: Game>(game: &G, strategy: &Strategy, player: usize) -> f64 {
+    value(game, strategy, &game.root(), player)
+}
+
+fn value<G: Game>(game: &G, strategy: &Strategy, state: &G::State, player: usize) -> f64 {
+    if game.is_terminal(state) {
+        return game.payoff(state, player);
+    }
+    outcomes(game, strategy, state)
+        .iter()
+        .map(|&(ref action, probability)| {
+            probability * value(game, strategy, &game.apply(state, action), player)
+        })
+        .sum()
+}
+
+/**
+ * best_response_value gets the expected utility of a player who knows the strategy of the
+ * opponent and plays the best response to it
+ */
+pub fn best_response_value<G: Game>(game: &G, strategy: &Strategy, responder: usize) -> f64 {
+    let mut response = BestResponse {
+        game,
+        strategy,
+        responder,
+        states: HashMap::new(),
+        choices: HashMap::new(),
+    };
+    let root = game.root();
+    response.collect(&root, 1.0);
+    response.value(&root)
+}
+
+/**
+ * exploitability gets how much a best response wins on average against the strategy, as the
+ * mean of the best response values of both players. It's zero on a Nash equilibrium.
+ */
+pub fn exploitability<G: Game>(game: &G, strategy: &Strategy) -> f64 {
+    (best_response_value(game, strategy, 0) + best_response_value(game, strategy, 1)) / 2.0
+}
+
+/**
+ * BestResponse finds the best action of the responder on every information set
+ */
+struct BestResponse<'a, G: Game + 'a> {
+    game: &'a G,
+    strategy: &'a Strategy,
+    responder: usize,
+    states: HashMap<String, Vec<(G::State, f64)>>, // States of every information set and the
+                                                    // probability of the opponent and chance
+                                                    // getting to them
+    choices: HashMap<String, usize>,
+}
+
+impl<'a, G: Game> BestResponse<'a, G> {
+    /**
+     * collect finds the states of the information sets of the responder
+     */
+    fn collect(&mut self, state: &G::State, reach: f64) {
+        if self.game.is_terminal(state) {
+            return;
+        }
+        if !self.game.is_chance(state) && self.game.player(state) == self.responder {
+            let key = self.game.information_set(state);
+            self.states.entry(key).or_default().push((state.clone(), reach));
+            for action in self.game.actions(state) {
+                self.collect(&self.game.apply(state, &action), reach);
+            }
+            return;
+        }
+        for (action, probability) in outcomes(self.game, self.strategy, state) {
+            self.collect(&self.game.apply(state, &action), reach * probability);
+        }
+    }
+
+    /**
+     * value gets the utility of the responder on a state
+     */
+    fn value(&mut self, state: &G::State) -> f64 {
+        if self.game.is_terminal(state) {
+            return self.game.payoff(state, self.responder);
+        }
+        if !self.game.is_chance(state) && self.game.player(state) == self.responder {
+            let choice = self.choose(&self.game.information_set(state));
+            let action = &self.game.actions(state)[choice];
+            return self.value(&self.game.apply(state, action));
+        }
+        outcomes(self.game, self.strategy, state)
+            .iter()
+            .map(|&(ref action, probability)| {
+                probability * self.value(&self.game.apply(state, action))
+            })
+            .sum()
+    }
+
+    /**
+     * choose gets the index of the action with the highest value on an information set
+     */
+    fn choose(&mut self, key: &str) -> usize {
+        if let Some(&choice) = self.choices.get(key) {
+            return choice;
+        }
+
+        let states = self.states[key].clone();
+        let actions = self.game.actions(&states[0].0);
+        let mut best = (0, f64::NEG_INFINITY);
+        for (i, action) in actions.iter().enumerate() {
+            let total: f64 = states.iter()
+                .map(|&(ref state, reach)| reach * self.value(&self.game.apply(state, action)))
+                .sum();
+            if total > best.1 {
+                best = (i, total);
+            }
+        }
+
+        self.choices.insert(key.to_string(), best.0);
+        best.0
+    }
+}
+
+/**
+ * outcomes gets the actions of a chance node or of a player following the strategy, with their
+ * probabilities
+ */
+fn outcomes<G: Game>(game: &G, strategy: &Strategy, state: &G::State) -> Vec<(G::Action, f64)> {
+    if game.is_chance(state) {
+        return game.chance_outcomes(state);
+    }
+
+    let actions = game.actions(state);
+    let uniform = vec![1.0 / actions.len() as f64; actions.len()];
+    let probabilities = strategy.get(&game.information_set(state)).unwrap_or(&uniform);
+    actions.into_iter().zip(probabilities.iter().cloned()).collect()
+}
+
+/**
+ * normalize scales some weights to probabilities, or gets a uniform distribution if they are zero
+ */
+pub(crate) fn normalize(weights: Vec<f64>) -> Vec<f64> {
+    let total: f64 = weights.iter().sum();
+    if total > 0.0 {
+        weights.iter().map(|weight| weight / total).collect()
+    } else {
+        vec![1.0 / weights.len() as f64; weights.len()]
+    }
+}
@@ -0,0 +1,267 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// Internal imports
+use card;
+use card::Card;
+use card::CardValue;
+use preflop::HandClass;
+use range::Range;
+
+/**
+ * CardSet is a set of live cards, sorted by index, to pick combinations from. Combinations are
+ * ranked by the positions of their cards on the set, in colexicographic order.
+ */
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct CardSet {
+    cards: Vec<Card>,
+}
+
+impl CardSet {
+    /**
+     * new creates a set with some cards, without the dead ones and the repeated ones
+     */
+    pub fn new(cards: &[Card], dead: &[Card]) -> CardSet {
+        let mut cards: Vec<Card> =
+            cards.iter().filter(|card| !dead.contains(card)).cloned().collect();
+        cards.sort_by_key(|card| card.index());
+        cards.dedup();
+        CardSet { cards }
+    }
+
+    /**
+     * deck creates a set with the cards of a deck that are not dead
+     */
+    pub fn deck(dead: &[Card]) -> CardSet {
+        CardSet::new(&card::deck(), dead)
+    }
+
+    /**
+     * cards gets the live cards, sorted by index
+     */
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    /**
+     * len gets the number of live cards
+     */
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    /**
+     * is_empty checks if there are no live cards
+     */
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    /**
+     * count gets the number of combinations of k cards of the set
+     */
+    pub fn count(&self, k: usize) -> u64 {
+        binomial(self.cards.len() as u64, k as u64)
+    }
+
+    /**
+     * combinations iterates over every combination of k cards of the set in colexicographic
+     * order, so the rank of every combination is its position on the iteration
+     */
+    pub fn combinations(&self, k: usize) -> Combinations<'_> {
+        Combinations {
+            cards: &self.cards,
+            positions: (0..k).collect(),
+            done: k > self.cards.len(),
+        }
+    }
+
+    /**
+     * rank gets the colexicographic rank of a combination of cards of the set, in any order. Gets
+     * None if any card is not on the set or is repeated.
+     */
+    pub fn rank(&self, combination: &[Card]) -> Option<u64> {
+        let mut positions = Vec::with_capacity(combination.len());
+        for card in combination {
+            positions.push(self.cards.iter().position(|live| live == card)?);
+        }
+        positions.sort();
+        let size = positions.len();
+        positions.dedup();
+        if positions.len() < size {
+            return None;
+        }
+        Some(colex_rank(&positions))
+    }
+
+    /**
+     * unrank gets the combination of k cards of the set with a colexicographic rank
+     */
+    pub fn unrank(&self, rank: u64, k: usize) -> Vec<Card> {
+        assert!(rank < self.count(k), "The rank is out of range");
+        colex_unrank(rank, k).iter().map(|position| self.cards[*position]).collect()
+    }
+}
+
+/**
+ * Combinations iterates over the combinations of k cards of a set in colexicographic order
+ */
+pub struct Combinations<'a> {
+    cards: &'a [Card],
+    positions: Vec<usize>, // Positions of the cards of the next combination, ascending
+    done: bool,
+}
+
+impl<'a> Iterator for Combinations<'a> {
+    type Item = Vec<Card>;
+
+    fn next(&mut self) -> Option<Vec<Card>> {
+        if self.done {
+            return None;
+        }
+        let combination = self.positions.iter().map(|position| self.cards[*position]).collect();
+
+        // Move up the first card that can, sending the ones below it to the bottom
+        let k = self.positions.len();
+        let movable = (0..k).find(|i| {
+            let limit = if i + 1 < k { self.positions[i + 1] } else { self.cards.len() };
+            self.positions[*i] + 1 < limit
+        });
+        match movable {
+            Some(i) => {
+                self.positions[i] += 1;
+                for (j, position) in self.positions[..i].iter_mut().enumerate() {
+                    *position = j;
+                }
+            }
+            None => self.done = true,
+        }
+        Some(combination)
+    }
+}
+
+/**
+ * binomial computes the number of combinations of k elements out of n
+ */
+pub fn binomial(n: u64, k: u64) -> u64 {
+    if k > n {
+        return 0;
+    }
+
+    let k = if k > n - k { n - k } else { k };
+    let mut result = 1;
+    for i in 0..k {
+        result = result * (n - i) / (i + 1);
+    }
+    result
+}
+
+/**
+ * colex_rank ranks a combination, given as its ascending positions, on colexicographic order:
+ * the sum of the binomial of every position and the number of positions up to it
+ */
+pub fn colex_rank(positions: &[usize]) -> u64 {
+    positions.iter()
+        .enumerate()
+        .map(|(i, position)| binomial(*position as u64, i as u64 + 1))
+        .sum()
+}
+
+/**
+ * colex_unrank gets the ascending positions of the combination of k elements with a
+ * colexicographic rank
+ */
+pub fn colex_unrank(mut rank: u64, k: usize) -> Vec<usize> {
+    let mut positions = vec![0; k];
+    for i in (0..k).rev() {
+        let count = i as u64 + 1;
+        let mut position = i as u64;
+        while binomial(position + 1, count) <= rank {
+            position += 1;
+        }
+        rank -= binomial(position, count);
+        positions[i] = position as usize;
+    }
+    positions
+}
+
+/**
+ * remaining counts the live cards of a value. Repeated dead cards only count once.
+ */
+pub fn remaining(value: CardValue, dead: &[Card]) -> usize {
+    card::SUITS
+        .iter()
+        .filter(|suit| !dead.iter().any(|card| card.value == value && card.suit == **suit))
+        .count()
+}
+
+/**
+ * class_combos counts the combos of a starting hand class that don't use any dead card
+ */
+pub fn class_combos(class: HandClass, dead: &[Card]) -> usize {
+    if class.is_pair() {
+        return binomial(remaining(class.high, dead) as u64, 2) as usize;
+    }
+
+    let live = |value: CardValue, suit| {
+        !dead.iter().any(|card| card.value == value && card.suit == suit)
+    };
+    let suited = card::SUITS
+        .iter()
+        .filter(|suit| live(class.high, **suit) && live(class.low, **suit))
+        .count();
+    if class.suited {
+        suited
+    } else {
+        remaining(class.high, dead) * remaining(class.low, dead) - suited
+    }
+}
+
+/**
+ * hand_combos counts the combos of a hand or range written on the usual notation, like "AK" or
+ * "QQ+, AJs+", that don't use any dead card
+ */
+pub fn hand_combos(hand: &str, dead: &[Card]) -> Result<usize, String> {
+    let dead: Vec<&Card> = dead.iter().collect();
+    let range = Range::parse(hand)?;
+    Ok(range.combos().iter().filter(|combo| !combo.conflicts(&dead)).count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::binomial;
+    use super::colex_rank;
+    use super::colex_unrank;
+
+    #[test]
+    fn test_colex() {
+        assert_eq!(binomial(52, 5), 2598960);
+        assert_eq!(binomial(3, 5), 0);
+        assert_eq!(colex_rank(&[0, 1, 2]), 0);
+        assert_eq!(colex_rank(&[0, 1, 3]), 1);
+        assert_eq!(colex_rank(&[1, 2, 3]), 3);
+        assert_eq!(colex_rank(&[0, 1, 4]), 4);
+        for rank in 0..binomial(10, 4) {
+            assert_eq!(colex_rank(&colex_unrank(rank, 4)), rank);
+        }
+    }
+}
@@ -0,0 +1,238 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// External imports
+use std::cmp::Ordering;
+
+// Internal imports
+use consts;
+use card::CardValue;
+use hand::Hand;
+use hand::HandType;
+
+/**
+ * describe gets a full description of a hand, including the kickers, for example "Pair of Tens,
+ * King-Queen-Jack kickers" or "Full House, Kings full of Fours"
+ */
+pub fn describe(hand: &Hand) -> String {
+    let values: Vec<CardValue> = hand.cards().iter().map(|card| card.value).collect();
+
+    match hand.hand_type() {
+        HandType::StraightFlush => format!("Straight Flush, {} high", name(values[0])),
+        HandType::FourOfAKind => {
+            format!("Four of a Kind, {}{}", plural(values[0]), kickers(&values[4..]))
+        }
+        HandType::FullHouse => {
+            format!("Full House, {} full of {}", plural(values[0]), plural(values[3]))
+        }
+        HandType::Flush => format!("Flush, {}", join(&values)),
+        HandType::Straight => format!("Straight, {} high", name(values[0])),
+        HandType::ThreeOfAKind => {
+            format!("Three of a Kind, {}{}", plural(values[0]), kickers(&values[3..]))
+        }
+        HandType::TwoPair => {
+            format!("Two Pair, {} and {}{}",
+                    plural(values[0]),
+                    plural(values[2]),
+                    kickers(&values[4..]))
+        }
+        HandType::Pair => format!("Pair of {}{}", plural(values[0]), kickers(&values[2..])),
+        HandType::HighCard => format!("High Card, {}{}", name(values[0]), kickers(&values[1..])),
+    }
+}
+
+/**
+ * explain tells which player wins a showdown and why, for example "Player 1 wins on second
+ * kicker: Q vs J"
+ */
+pub fn explain(hand1: &Hand, hand2: &Hand) -> String {
+    let (winner, loser, player) = match hand1.cmp(hand2) {
+        Ordering::Greater => (hand1, hand2, 1),
+        Ordering::Less => (hand2, hand1, 2),
+        Ordering::Equal => return format!("Draw, both players have {}", describe(hand1)),
+    };
+
+    if winner.hand_type() != loser.hand_type() {
+        return format!("Player {} wins with {} over {}",
+                       player,
+                       type_name(winner.hand_type()),
+                       type_name(loser.hand_type()));
+    }
+
+    // Hands of the same type are decided by the first different card
+    let position = (0..consts::HAND_SIZE)
+        .find(|i| winner.cards()[*i].value != loser.cards()[*i].value)
+        .unwrap();
+    format!("Player {} wins on {}: {} vs {}",
+            player,
+            position_name(winner.hand_type(), position),
+            winner.cards()[position].value.symbol(),
+            loser.cards()[position].value.symbol())
+}
+
+/**
+ * type_name gets the name of a type of hand as it's written on descriptions
+ */
+pub fn type_name(hand_type: HandType) -> &'static str {
+    match hand_type {
+        HandType::StraightFlush => "Straight Flush",
+        HandType::FourOfAKind => "Four of a Kind",
+        HandType::FullHouse => "Full House",
+        HandType::Flush => "Flush",
+        HandType::Straight => "Straight",
+        HandType::ThreeOfAKind => "Three of a Kind",
+        HandType::TwoPair => "Two Pair",
+        HandType::Pair => "Pair",
+        HandType::HighCard => "High Card",
+    }
+}
+
+/**
+ * name gets the name of a card value, for example "Queen"
+ */
+pub fn name(value: CardValue) -> &'static str {
+    match value {
+        CardValue::None => "Unknown",
+        CardValue::Value2 => "Two",
+        CardValue::Value3 => "Three",
+        CardValue::Value4 => "Four",
+        CardValue::Value5 => "Five",
+        CardValue::Value6 => "Six",
+        CardValue::Value7 => "Seven",
+        CardValue::Value8 => "Eight",
+        CardValue::Value9 => "Nine",
+        CardValue::ValueT => "Ten",
+        CardValue::ValueJ => "Jack",
+        CardValue::ValueQ => "Queen",
+        CardValue::ValueK => "King",
+        CardValue::ValueA => "Ace",
+    }
+}
+
+/**
+ * plural gets the name of several cards of the same value, for example "Sixes"
+ */
+pub fn plural(value: CardValue) -> String {
+    match value {
+        CardValue::Value6 => String::from("Sixes"),
+        _ => format!("{}s", name(value)),
+    }
+}
+
+/**
+ * join gets the names of the values separated by hyphens
+ */
+fn join(values: &[CardValue]) -> String {
+    values.iter().map(|value| name(*value)).collect::<Vec<&str>>().join("-")
+}
+
+/**
+ * kickers describes the kickers of a hand, if any
+ */
+fn kickers(values: &[CardValue]) -> String {
+    match values.len() {
+        0 => String::new(),
+        1 => format!(", {} kicker", name(values[0])),
+        _ => format!(", {} kickers", join(values)),
+    }
+}
+
+/**
+ * position_name gets what a position of the ordered cards means for a type of hand
+ */
+fn position_name(hand_type: HandType, position: usize) -> &'static str {
+    const KICKERS: [&str; 4] = ["first kicker", "second kicker", "third kicker", "fourth kicker"];
+    const CARDS: [&str; 5] = ["highest card", "second card", "third card", "fourth card",
+                              "fifth card"];
+
+    match hand_type {
+        HandType::StraightFlush | HandType::Straight => "high card",
+        HandType::FourOfAKind => if position < 4 { "four of a kind" } else { "kicker" },
+        HandType::FullHouse => if position < 3 { "three of a kind" } else { "pair" },
+        HandType::Flush => CARDS[position],
+        HandType::ThreeOfAKind => {
+            if position < 3 { "three of a kind" } else { KICKERS[position - 3] }
+        }
+        HandType::TwoPair => {
+            match position {
+                0 | 1 => "top pair",
+                2 | 3 => "second pair",
+                _ => "kicker",
+            }
+        }
+        HandType::Pair => if position < 2 { "pair" } else { KICKERS[position - 2] },
+        HandType::HighCard => if position == 0 { "high card" } else { KICKERS[position - 1] },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::describe;
+    use super::explain;
+    use super::super::card::Card;
+    use super::super::hand::Hand;
+
+    fn parse(cards: &str) -> Vec<Card> {
+        cards.split_whitespace()
+            .map(|card| Card::new(card.chars().next().unwrap(), card.chars().nth(1).unwrap()))
+            .collect()
+    }
+
+    fn description(cards: &str) -> String {
+        let cards = parse(cards);
+        let refs: Vec<&Card> = cards.iter().collect();
+        describe(&Hand::from_cards(&refs))
+    }
+
+    fn explanation(hole1: &str, hole2: &str, table: &str) -> String {
+        let (hole1, hole2, table) = (parse(hole1), parse(hole2), parse(table));
+        let mut cards1: Vec<&Card> = hole1.iter().collect();
+        let mut cards2: Vec<&Card> = hole2.iter().collect();
+        cards1.extend(table.iter());
+        cards2.extend(table.iter());
+        explain(&Hand::from_cards(&cards1), &Hand::from_cards(&cards2))
+    }
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(description("TC JC 2H 7H TH QS KC"), "Pair of Tens, King-Queen-Jack kickers");
+        assert_eq!(description("KC KD KH 4S 4C 2D 3D"), "Full House, Kings full of Fours");
+        assert_eq!(description("6C 6D 5H 5S QC 2D 3D"), "Two Pair, Sixes and Fives, Queen kicker");
+        assert_eq!(description("2H 3C 4D 5S AH KH QC"), "Straight, Five high");
+        assert_eq!(description("9H 9S 9C 9D 2C 3C KS"), "Four of a Kind, Nines, King kicker");
+        assert_eq!(description("2H 7H 9H TH AH KH 3C"), "Flush, Ace-King-Ten-Nine-Seven");
+        assert_eq!(description("8D 7C KS 9D 5C 3S 2D"),
+                   "High Card, King, Nine-Eight-Seven-Five kickers");
+    }
+
+    #[test]
+    fn test_explain() {
+        assert_eq!(explanation("AS QD", "AC JD", "AH 7C 4D 3S KS"),
+                   "Player 1 wins on second kicker: Q vs J");
+        assert_eq!(explanation("TC JC", "AD 4S", "2H 7H TH QS KC"),
+                   "Player 1 wins with Pair over High Card");
+        assert_eq!(explanation("KC 3C", "KD 8C", "KH 9D 9C 4S 2H"),
+                   "Player 2 wins on kicker: 8 vs 4");
+        assert_eq!(explanation("2D 3S", "2C 3H", "AH KC QH JS TC"),
+                   "Draw, both players have Straight, Ace high");
+    }
+}
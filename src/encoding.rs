@@ -0,0 +1,468 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// External imports
+use std::io;
+use std::io::Write;
+
+// Internal imports
+use consts;
+use types::Street;
+use card::Card;
+
+/**
+ * First bytes of a stream of deals
+ */
+pub const MAGIC: [u8; 4] = *b"PKDL";
+
+/**
+ * Version of the stream format
+ */
+pub const VERSION: u8 = 1;
+
+/**
+ * Size of the header: magic, version, number of players and two reserved bytes
+ */
+pub const HEADER_SIZE: usize = 8;
+
+/**
+ * Size of the trailer: number of records (u64) and CRC-32 of the records (u32), little endian
+ */
+pub const TRAILER_SIZE: usize = 12;
+
+/**
+ * Bits used by every card. Codes go from 1 to 52, the index of the card plus one, and 0 is an
+ * empty slot of the board.
+ */
+pub const CARD_BITS: usize = 6;
+
+/**
+ * Record is a deal: the hole cards of every player and the board, from 0 to 5 cards
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    pub holes: Vec<[Card; 2]>,
+    pub board: Vec<Card>,
+}
+
+/**
+ * encode_card gets the 6 bit code of a card
+ */
+pub fn encode_card(card: &Card) -> u8 {
+    card.index() as u8 + 1
+}
+
+/**
+ * decode_card gets the card of a code, or None for an empty slot or an invalid code
+ */
+pub fn decode_card(code: u8) -> Option<Card> {
+    match code {
+        1..=52 => Some(Card::from_index(code as usize - 1)),
+        _ => None,
+    }
+}
+
+/**
+ * record_size gets the bytes of a packed record with a number of players
+ */
+pub fn record_size(players: usize) -> usize {
+    ((players * consts::HOLE_SIZE + consts::TABLE_SIZE) * CARD_BITS).div_ceil(8)
+}
+
+/**
+ * pack writes a record on a buffer of record_size bytes: the hole cards of the players on order
+ * followed by the five slots of the board
+ */
+pub fn pack(record: &Record, buffer: &mut [u8]) {
+    assert!(record.board.len() <= consts::TABLE_SIZE, "A board has at most 5 cards");
+    for byte in buffer.iter_mut() {
+        *byte = 0;
+    }
+
+    let cards = record.holes.iter().flat_map(|hole| hole.iter()).chain(record.board.iter());
+    for (slot, card) in cards.enumerate() {
+        put_code(buffer, slot, encode_card(card));
+    }
+}
+
+/**
+ * unpack reads a packed record of a number of players
+ */
+pub fn unpack(bytes: &[u8], players: usize) -> Result<Record, String> {
+    RecordRef {
+        bytes,
+        players,
+    }
+    .to_record()
+}
+
+/**
+ * put_code writes the code of a card on a slot. The bits of the slot must be zero.
+ */
+fn put_code(buffer: &mut [u8], slot: usize, code: u8) {
+    let position = slot * CARD_BITS;
+    let (byte, offset) = (position / 8, position % 8);
+    let window = (code as u16) << (16 - CARD_BITS - offset);
+    buffer[byte] |= (window >> 8) as u8;
+    if offset + CARD_BITS > 8 {
+        buffer[byte + 1] |= window as u8;
+    }
+}
+
+/**
+ * get_code reads the code of the card on a slot
+ */
+fn get_code(bytes: &[u8], slot: usize) -> u8 {
+    let position = slot * CARD_BITS;
+    let (byte, offset) = (position / 8, position % 8);
+    let high = bytes[byte] as u16;
+    let low = if offset + CARD_BITS > 8 { bytes[byte + 1] as u16 } else { 0 };
+    ((high << 8 | low) >> (16 - CARD_BITS - offset)) as u8 & 0x3f
+}
+
+/**
+ * RecordRef is a packed record inside a larger buffer, decoded on demand
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct RecordRef<'a> {
+    bytes: &'a [u8],
+    players: usize,
+}
+
+impl<'a> RecordRef<'a> {
+    /**
+     * bytes gets the packed bytes of the record
+     */
+    pub fn bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /**
+     * hole gets the hole cards of a player
+     */
+    pub fn hole(&self, player: usize) -> Option<[Card; 2]> {
+        if player >= self.players {
+            return None;
+        }
+        let first = decode_card(get_code(self.bytes, player * consts::HOLE_SIZE))?;
+        let second = decode_card(get_code(self.bytes, player * consts::HOLE_SIZE + 1))?;
+        Some([first, second])
+    }
+
+    /**
+     * board gets the community cards, up to the first empty slot
+     */
+    pub fn board(&self) -> Vec<Card> {
+        let first = self.players * consts::HOLE_SIZE;
+        (first..first + consts::TABLE_SIZE)
+            .map(|slot| decode_card(get_code(self.bytes, slot)))
+            .take_while(|card| card.is_some())
+            .map(|card| card.unwrap())
+            .collect()
+    }
+
+    /**
+     * to_record decodes the whole record, checking that every code is valid
+     */
+    pub fn to_record(&self) -> Result<Record, String> {
+        if self.bytes.len() < record_size(self.players) {
+            return Err(format!("a record of {} players needs {} bytes",
+                               self.players,
+                               record_size(self.players)));
+        }
+
+        let holes = (0..self.players)
+            .map(|player| {
+                self.hole(player).ok_or_else(|| format!("invalid hole of player {}", player))
+            })
+            .collect::<Result<Vec<[Card; 2]>, String>>()?;
+
+        let board = self.board();
+        let first = self.players * consts::HOLE_SIZE;
+        let rest = first + board.len()..first + consts::TABLE_SIZE;
+        if Street::from_board_size(board.len()).is_none() ||
+           rest.map(|slot| get_code(self.bytes, slot)).any(|code| code != 0) {
+            return Err(String::from("invalid board"));
+        }
+
+        Ok(Record { holes, board })
+    }
+}
+
+/**
+ * Encoder writes a stream of records: a header, the packed records and a trailer with their number
+ * and checksum
+ */
+pub struct Encoder<W: Write> {
+    writer: W,
+    players: usize,
+    count: u64,
+    checksum: Crc32,
+    buffer: Vec<u8>,
+}
+
+impl<W: Write> Encoder<W> {
+    /**
+     * Creates an encoder of records with a number of players, writing the header
+     */
+    pub fn new(mut writer: W, players: usize) -> io::Result<Encoder<W>> {
+        if players == 0 || players > u8::MAX as usize {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "invalid number of players"));
+        }
+
+        let mut header = [0; HEADER_SIZE];
+        header[..4].copy_from_slice(&MAGIC);
+        header[4] = VERSION;
+        header[5] = players as u8;
+        writer.write_all(&header)?;
+
+        Ok(Encoder {
+            writer,
+            players,
+            count: 0,
+            checksum: Crc32::new(),
+            buffer: vec![0; record_size(players)],
+        })
+    }
+
+    /**
+     * write appends a record
+     */
+    pub fn write(&mut self, record: &Record) -> io::Result<()> {
+        if record.holes.len() != self.players || record.board.len() > consts::TABLE_SIZE {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "invalid record"));
+        }
+
+        pack(record, &mut self.buffer);
+        self.checksum.update(&self.buffer);
+        self.count += 1;
+        self.writer.write_all(&self.buffer)
+    }
+
+    /**
+     * finish writes the trailer and returns the writer
+     */
+    pub fn finish(mut self) -> io::Result<W> {
+        let mut trailer = [0; TRAILER_SIZE];
+        trailer[..8].copy_from_slice(&self.count.to_le_bytes());
+        trailer[8..].copy_from_slice(&self.checksum.value().to_le_bytes());
+        self.writer.write_all(&trailer)?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/**
+ * Decoder reads a stream of records from memory without copying it, so large files can be mapped
+ * or read once and iterated
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct Decoder<'a> {
+    records: &'a [u8],
+    players: usize,
+    count: usize,
+    checksum: u32,
+}
+
+impl<'a> Decoder<'a> {
+    /**
+     * Creates a decoder of a whole stream, checking its header and its size
+     */
+    pub fn new(bytes: &'a [u8]) -> Result<Decoder<'a>, String> {
+        if bytes.len() < HEADER_SIZE + TRAILER_SIZE || bytes[..4] != MAGIC {
+            return Err(String::from("not a stream of deals"));
+        }
+        if bytes[4] != VERSION {
+            return Err(format!("unsupported version {}", bytes[4]));
+        }
+        let players = bytes[5] as usize;
+        if players == 0 {
+            return Err(String::from("invalid number of players"));
+        }
+
+        let trailer = &bytes[bytes.len() - TRAILER_SIZE..];
+        let mut count = [0; 8];
+        count.copy_from_slice(&trailer[..8]);
+        let mut checksum = [0; 4];
+        checksum.copy_from_slice(&trailer[8..]);
+
+        let records = &bytes[HEADER_SIZE..bytes.len() - TRAILER_SIZE];
+        let count = u64::from_le_bytes(count) as usize;
+        if count.checked_mul(record_size(players)) != Some(records.len()) {
+            return Err(format!("expected {} records of {} bytes, found {} bytes",
+                               count,
+                               record_size(players),
+                               records.len()));
+        }
+
+        Ok(Decoder {
+            records,
+            players,
+            count,
+            checksum: u32::from_le_bytes(checksum),
+        })
+    }
+
+    /**
+     * players gets the number of players of every record
+     */
+    pub fn players(&self) -> usize {
+        self.players
+    }
+
+    /**
+     * len gets the number of records
+     */
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /**
+     * verify tells if the checksum of the records matches the one of the trailer
+     */
+    pub fn verify(&self) -> bool {
+        let mut checksum = Crc32::new();
+        checksum.update(self.records);
+        checksum.value() == self.checksum
+    }
+
+    /**
+     * get gets a record by its position
+     */
+    pub fn get(&self, index: usize) -> Option<RecordRef<'a>> {
+        let size = record_size(self.players);
+        let start = index.checked_mul(size)?;
+        let end = start.checked_add(size)?;
+        self.records.get(start..end).map(|bytes| {
+            RecordRef {
+                bytes,
+                players: self.players,
+            }
+        })
+    }
+
+    /**
+     * records iterates the records on order
+     */
+    pub fn records(&self) -> Records<'a> {
+        Records {
+            chunks: self.records.chunks(record_size(self.players)),
+            players: self.players,
+        }
+    }
+}
+
+/**
+ * Records iterates the records of a decoder
+ */
+pub struct Records<'a> {
+    chunks: ::std::slice::Chunks<'a, u8>,
+    players: usize,
+}
+
+impl<'a> Iterator for Records<'a> {
+    type Item = RecordRef<'a>;
+
+    fn next(&mut self) -> Option<RecordRef<'a>> {
+        self.chunks.next().map(|bytes| {
+            RecordRef {
+                bytes,
+                players: self.players,
+            }
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+}
+
+/**
+ * Crc32 computes the CRC-32 (IEEE) of a sequence of bytes
+ */
+struct Crc32 {
+    table: [u32; 256],
+    value: u32,
+}
+
+impl Crc32 {
+    fn new() -> Crc32 {
+        let mut table = [0; 256];
+        for (i, entry) in table.iter_mut().enumerate() {
+            let mut crc = i as u32;
+            for _ in 0..8 {
+                crc = if crc & 1 == 1 { 0xedb8_8320 ^ (crc >> 1) } else { crc >> 1 };
+            }
+            *entry = crc;
+        }
+        Crc32 {
+            table,
+            value: 0xffff_ffff,
+        }
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            let index = ((self.value ^ byte as u32) & 0xff) as usize;
+            self.value = self.table[index] ^ (self.value >> 8);
+        }
+    }
+
+    fn value(&self) -> u32 {
+        !self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::decode_card;
+    use super::encode_card;
+    use super::record_size;
+    use super::Crc32;
+    use super::super::card;
+
+    #[test]
+    fn test_card_codes() {
+        for card in card::deck() {
+            let code = encode_card(&card);
+            assert!((1..64).contains(&code));
+            assert_eq!(decode_card(code), Some(card));
+        }
+        assert_eq!(decode_card(0), None);
+        assert_eq!(decode_card(53), None);
+    }
+
+    #[test]
+    fn test_sizes_and_checksum() {
+        assert_eq!(record_size(1), 6);
+        assert_eq!(record_size(2), 7);
+        assert_eq!(record_size(9), 18);
+
+        let mut crc = Crc32::new();
+        crc.update(b"123456789");
+        assert_eq!(crc.value(), 0xcbf4_3926);
+    }
+}
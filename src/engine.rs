@@ -0,0 +1,425 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// External imports
+use std::cmp::Ordering;
+
+// Internal imports
+use bot::Action;
+use bot::Chips;
+use bot::Event;
+use bot::HandResult;
+use bot::LegalActions;
+use bot::Observation;
+use card::Card;
+use hand::Hand;
+use types::Street;
+
+/**
+ * TableConfig holds the blinds and the stack every player starts the hands with
+ */
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct TableConfig {
+    pub small_blind: Chips,
+    pub big_blind: Chips,
+    pub stack: Chips,
+}
+
+impl TableConfig {
+    /**
+     * new creates a table where the small blind is half the big blind
+     */
+    pub fn new(big_blind: Chips, stack: Chips) -> TableConfig {
+        TableConfig {
+            small_blind: big_blind / 2,
+            big_blind,
+            stack,
+        }
+    }
+}
+
+/**
+ * HandState runs a hand of heads-up No Limit Texas Hold'em. The player on the button posts the
+ * small blind and acts first before the flop and last after it.
+ */
+#[derive(Clone, Debug)]
+pub struct HandState {
+    config: TableConfig,
+    button: usize,
+    holes: [[Card; 2]; 2],
+    runout: [Card; 5],
+    street: Street,
+    stacks: [Chips; 2],
+    bets: [Chips; 2],
+    pot: Chips, // Chips of the previous streets
+    acted: [bool; 2],
+    min_raise: Chips, // Smallest increment of a raise
+    to_act: Option<usize>,
+    folded: Option<usize>,
+    history: Vec<Event>,
+    result: Option<HandResult>,
+}
+
+impl HandState {
+    /**
+     * new starts a hand posting the blinds. The first 9 cards of the deck are the hole cards of
+     * the seat 0, the ones of the seat 1 and the board.
+     */
+    pub fn new(config: TableConfig, button: usize, deck: &[Card]) -> HandState {
+        assert!(deck.len() >= 9, "A hand needs 9 cards");
+        let mut state = HandState {
+            config,
+            button,
+            holes: [[deck[0], deck[1]], [deck[2], deck[3]]],
+            runout: [deck[4], deck[5], deck[6], deck[7], deck[8]],
+            street: Street::Preflop,
+            stacks: [config.stack; 2],
+            bets: [0; 2],
+            pot: 0,
+            acted: [false; 2],
+            min_raise: config.big_blind,
+            to_act: Some(button),
+            folded: None,
+            history: Vec::new(),
+            result: None,
+        };
+        state.put(button, config.small_blind);
+        state.put(1 - button, config.big_blind);
+        if state.is_round_complete() {
+            state.end_round();
+        }
+        state
+    }
+
+    /**
+     * to_act gets the seat that has to act, or None if the hand is over
+     */
+    pub fn to_act(&self) -> Option<usize> {
+        self.to_act
+    }
+
+    /**
+     * street gets the current street
+     */
+    pub fn street(&self) -> Street {
+        self.street
+    }
+
+    /**
+     * hole gets the hole cards of a seat
+     */
+    pub fn hole(&self, seat: usize) -> [Card; 2] {
+        self.holes[seat]
+    }
+
+    /**
+     * board gets the community cards visible on the current street
+     */
+    pub fn board(&self) -> &[Card] {
+        &self.runout[..self.street.board_size()]
+    }
+
+    /**
+     * pot gets the chips on the pot, the bets of the current street included
+     */
+    pub fn pot(&self) -> Chips {
+        self.pot + self.bets[0] + self.bets[1]
+    }
+
+    /**
+     * legal_actions gets what the player to act can do
+     */
+    pub fn legal_actions(&self) -> LegalActions {
+        let seat = match self.to_act {
+            Some(seat) => seat,
+            None => return LegalActions::default(),
+        };
+        let highest = self.bets[0].max(self.bets[1]);
+        let to_call = highest - self.bets[seat];
+        let all_in = self.bets[seat] + self.stacks[seat];
+
+        LegalActions {
+            check: to_call == 0,
+            call: if to_call > 0 { Some(to_call.min(self.stacks[seat])) } else { None },
+            raise: if self.stacks[seat] > to_call && self.stacks[1 - seat] > 0 {
+                Some(((highest + self.min_raise).min(all_in), all_in))
+            } else {
+                None
+            },
+        }
+    }
+
+    /**
+     * observation gets what a seat knows about the hand
+     */
+    pub fn observation(&self, seat: usize) -> Observation {
+        Observation {
+            seat,
+            button: self.button,
+            hole: self.holes[seat],
+            board: self.board().to_vec(),
+            street: self.street,
+            pot: self.pot(),
+            stacks: self.stacks,
+            bets: self.bets,
+            big_blind: self.config.big_blind,
+            history: self.history.clone(),
+            legal: if self.to_act == Some(seat) {
+                self.legal_actions()
+            } else {
+                LegalActions::default()
+            },
+        }
+    }
+
+    /**
+     * apply takes an action of the player to act. Illegal actions are rejected.
+     */
+    pub fn apply(&mut self, action: Action) -> Result<(), String> {
+        let seat = self.to_act.ok_or_else(|| "The hand is over".to_string())?;
+        if !self.legal_actions().contains(&action) {
+            return Err(format!("Illegal action '{}'", action));
+        }
+
+        self.history.push(Event {
+            seat,
+            street: self.street,
+            action,
+        });
+        let highest = self.bets[0].max(self.bets[1]);
+        match action {
+            Action::Fold => {
+                self.folded = Some(seat);
+                self.finish();
+                return Ok(());
+            }
+            Action::Check => (),
+            Action::Call => {
+                let amount = highest - self.bets[seat];
+                self.put(seat, amount);
+            }
+            Action::Raise(to) => {
+                // A short all-in does not change the size of the next raises
+                self.min_raise = self.min_raise.max(to - highest);
+                let amount = to - self.bets[seat];
+                self.put(seat, amount);
+                self.acted[1 - seat] = false;
+            }
+        }
+        self.acted[seat] = true;
+
+        if self.is_round_complete() {
+            self.end_round();
+        } else {
+            self.to_act = Some(1 - seat);
+        }
+        Ok(())
+    }
+
+    /**
+     * result gets how the hand ended, once it's over
+     */
+    pub fn result(&self) -> Option<&HandResult> {
+        self.result.as_ref()
+    }
+
+    /**
+     * put moves chips from the stack of a seat to its bet, going all-in if it has not enough
+     */
+    fn put(&mut self, seat: usize, amount: Chips) {
+        let amount = amount.min(self.stacks[seat]);
+        self.stacks[seat] -= amount;
+        self.bets[seat] += amount;
+    }
+
+    /**
+     * is_round_complete checks if every player with chips has acted and matched the highest bet
+     */
+    fn is_round_complete(&self) -> bool {
+        let highest = self.bets[0].max(self.bets[1]);
+        let with_chips: Vec<usize> = (0..2).filter(|seat| self.stacks[*seat] > 0).collect();
+        match with_chips.len() {
+            0 => true,
+            1 => self.bets[with_chips[0]] == highest,
+            _ => with_chips.iter().all(|seat| self.acted[*seat] && self.bets[*seat] == highest),
+        }
+    }
+
+    /**
+     * end_round moves the bets to the pot, returning the part of a bet nobody called, and deals
+     * the next street or goes to showdown
+     */
+    fn end_round(&mut self) {
+        let called = self.bets[0].min(self.bets[1]);
+        for seat in 0..2 {
+            self.stacks[seat] += self.bets[seat] - called;
+            self.pot += called;
+        }
+        self.bets = [0; 2];
+
+        let with_chips = self.stacks.iter().filter(|stack| **stack > 0).count();
+        if self.street == Street::River || with_chips < 2 {
+            self.street = Street::River;
+            self.finish();
+            return;
+        }
+
+        self.street = match self.street {
+            Street::Preflop => Street::Flop,
+            Street::Flop => Street::Turn,
+            _ => Street::River,
+        };
+        self.acted = [false; 2];
+        self.min_raise = self.config.big_blind;
+        self.to_act = Some(1 - self.button);
+    }
+
+    /**
+     * finish gives the pot to the winner, splitting it on a tie with the odd chip for the player
+     * out of position
+     */
+    fn finish(&mut self) {
+        self.pot += self.bets[0] + self.bets[1];
+        self.bets = [0; 2];
+        self.to_act = None;
+
+        let mut shares = [0; 2];
+        let mut shown = [None; 2];
+        match self.folded {
+            Some(folder) => shares[1 - folder] = self.pot,
+            None => {
+                let hands: Vec<Hand> = (0..2)
+                    .map(|seat| {
+                        let mut cards: Vec<&Card> = self.holes[seat].iter().collect();
+                        cards.extend(self.runout.iter());
+                        Hand::from_cards(&cards)
+                    })
+                    .collect();
+                match hands[0].cmp(&hands[1]) {
+                    Ordering::Greater => shares[0] = self.pot,
+                    Ordering::Less => shares[1] = self.pot,
+                    Ordering::Equal => {
+                        shares = [self.pot / 2; 2];
+                        shares[1 - self.button] += self.pot % 2;
+                    }
+                }
+                shown = [Some(self.holes[0]), Some(self.holes[1])];
+            }
+        }
+
+        let mut winnings = [0; 2];
+        for seat in 0..2 {
+            let stack = self.stacks[seat] + shares[seat];
+            winnings[seat] = stack as i64 - self.config.stack as i64;
+        }
+        self.result = Some(HandResult {
+            button: self.button,
+            board: self.board().to_vec(),
+            shown,
+            winnings,
+            history: self.history.clone(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HandState;
+    use super::TableConfig;
+    use super::super::bot::Action;
+    use super::super::calculator;
+    use super::super::types::Street;
+
+    fn state(deck: &str, stack: u64) -> HandState {
+        let deck = calculator::parse_cards(deck).unwrap();
+        HandState::new(TableConfig::new(100, stack), 0, &deck)
+    }
+
+    #[test]
+    fn test_betting_round() {
+        let mut hand = state("As Ad Kc Kd 2c 7d 9h Jc 3s", 10000);
+        assert_eq!(hand.to_act(), Some(0));
+        assert_eq!(hand.pot(), 150);
+        let legal = hand.legal_actions();
+        assert_eq!(legal.call, Some(50));
+        assert_eq!(legal.raise, Some((200, 10000)));
+        assert!(hand.apply(Action::Check).is_err());
+
+        // The big blind has the option after a call
+        hand.apply(Action::Call).unwrap();
+        assert_eq!(hand.to_act(), Some(1));
+        assert!(hand.legal_actions().check);
+        hand.apply(Action::Raise(300)).unwrap();
+        assert_eq!(hand.legal_actions().raise, Some((500, 10000)));
+        hand.apply(Action::Call).unwrap();
+
+        // The big blind acts first after the flop
+        assert_eq!(hand.street(), Street::Flop);
+        assert_eq!(hand.board().len(), 3);
+        assert_eq!(hand.to_act(), Some(1));
+        assert_eq!(hand.pot(), 600);
+        hand.apply(Action::Raise(400)).unwrap();
+        hand.apply(Action::Fold).unwrap();
+        assert_eq!(hand.to_act(), None);
+
+        let result = hand.result().unwrap();
+        assert_eq!(result.winnings, [-300, 300]);
+        assert_eq!(result.shown, [None, None]);
+        assert_eq!(result.board.len(), 3);
+    }
+
+    #[test]
+    fn test_all_in() {
+        let mut hand = state("As Ad Kc Kd 2c 7d 9h Jc 3s", 1000);
+        hand.apply(Action::Raise(1000)).unwrap();
+        assert_eq!(hand.legal_actions().raise, None);
+        hand.apply(Action::Call).unwrap();
+
+        // The board is dealt and the Aces win
+        let result = hand.result().unwrap();
+        assert_eq!(result.board.len(), 5);
+        assert_eq!(result.winnings, [1000, -1000]);
+        assert!(result.shown[1].is_some());
+    }
+
+    #[test]
+    fn test_split_pot() {
+        let mut hand = state("2s 3d 2c 3h Ac Kd Qh Jc Ts", 1000);
+        hand.apply(Action::Call).unwrap();
+        for _ in 0..7 {
+            hand.apply(Action::Check).unwrap();
+        }
+        assert_eq!(hand.result().unwrap().winnings, [0, 0]);
+    }
+
+    #[test]
+    fn test_short_stack() {
+        // Both players are all-in with the blinds, so nobody acts
+        let deck = calculator::parse_cards("As Ad Kc Kd 2c 7d 9h Jc 3s").unwrap();
+        let mut config = TableConfig::new(100, 1000);
+        config.small_blind = 1000;
+        config.big_blind = 2000;
+        let hand = HandState::new(config, 1, &deck);
+        assert_eq!(hand.to_act(), None);
+        assert_eq!(hand.result().unwrap().winnings, [1000, -1000]);
+    }
+}
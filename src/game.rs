@@ -0,0 +1,79 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// External imports
+use std::fmt::Debug;
+
+/**
+ * Game is a two player zero sum extensive form game, like Kuhn or Leduc poker. Players are 0 and
+ * 1, and chance nodes have a probability for every outcome.
+ */
+pub trait Game {
+    type State: Clone + Debug;
+    type Action: Clone + Debug + PartialEq;
+
+    /**
+     * root gets the state before the first action
+     */
+    fn root(&self) -> Self::State;
+
+    /**
+     * is_terminal tells if the game is over
+     */
+    fn is_terminal(&self, state: &Self::State) -> bool;
+
+    /**
+     * payoff gets the utility of a player on a terminal state
+     */
+    fn payoff(&self, state: &Self::State, player: usize) -> f64;
+
+    /**
+     * is_chance tells if the next action is chosen by chance, like dealing cards
+     */
+    fn is_chance(&self, state: &Self::State) -> bool;
+
+    /**
+     * chance_outcomes gets every possible outcome of a chance node with its probability
+     */
+    fn chance_outcomes(&self, state: &Self::State) -> Vec<(Self::Action, f64)>;
+
+    /**
+     * player gets the player who acts on a decision node
+     */
+    fn player(&self, state: &Self::State) -> usize;
+
+    /**
+     * actions gets the legal actions of a decision node. States of the same information set must
+     * have the same actions on the same order.
+     */
+    fn actions(&self, state: &Self::State) -> Vec<Self::Action>;
+
+    /**
+     * apply gets the state after an action
+     */
+    fn apply(&self, state: &Self::State, action: &Self::Action) -> Self::State;
+
+    /**
+     * information_set gets a key identifying what the acting player knows on a decision node
+     */
+    fn information_set(&self, state: &Self::State) -> String;
+}
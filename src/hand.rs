@@ -28,15 +28,15 @@ use std::fmt;
 // Internal imports
 use types;
 use consts;
+use card;
 use card::CardValue;
-use card::CardSuit;
 use card::Card;
 
 /**
  * HandType can be any of the possible defined hands. Ordered by value.
  */
-#[derive(PartialEq)]
-enum HandType {
+#[derive(Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum HandType {
     HighCard,
     Pair,
     TwoPair,
@@ -48,6 +48,25 @@ enum HandType {
     StraightFlush,
 }
 
+impl HandType {
+    /**
+     * name gets the string representing the type of a hand
+     */
+    pub fn name(&self) -> &'static str {
+        match *self {
+            HandType::StraightFlush => "STRAIGHT_FLUSH",
+            HandType::FourOfAKind => "FOUR_OF_A_KIND",
+            HandType::FullHouse => "FULL_HOUSE",
+            HandType::Flush => "FLUSH",
+            HandType::Straight => "STRAIGHT",
+            HandType::ThreeOfAKind => "THREE_OF_A_KIND",
+            HandType::TwoPair => "TWO_PAIR",
+            HandType::Pair => "PAIR",
+            HandType::HighCard => "HIGH_CARD",
+        }
+    }
+}
+
 /**
  * Hand represents a combination of 5 cards with together build a HandType.
  */
@@ -62,20 +81,20 @@ pub struct Hand<'a> {
 impl<'a> Eq for Hand<'a> {}
 impl<'a> PartialEq for Hand<'a> {
     fn eq(&self, other: &Hand) -> bool {
-        for i in 0..consts::HAND_SIZE {
-            if self.cards[i] != other.cards[i] {
-                return false;
-            }
-        }
-        true
+        self.cmp(other) == Ordering::Equal
     }
 }
 
 /**
- * Implement Ord and PartialOrd for the Hand so it's possible to check which hand has more value
+ * Implement Ord and PartialOrd for the Hand so it's possible to check which hand has more value.
+ * The type of the hand is compared first and the values of the cards break the ties.
  */
 impl<'a> Ord for Hand<'a> {
     fn cmp(&self, other: &Hand) -> Ordering {
+        if self.hand_type != other.hand_type {
+            return self.hand_type.cmp(&other.hand_type);
+        }
+
         for i in 0..consts::HAND_SIZE {
             let ordering: Ordering = self.cards[i].value.cmp(&other.cards[i].value);
             if ordering == Ordering::Equal {
@@ -94,35 +113,21 @@ impl<'a> PartialOrd for Hand<'a> {
 }
 
 /**
- * Implement Display for Hand so it's possible to obtain the alias of the card values, for example,
- * 'T' instead of '10' or 'Q' instead of '12'
+ * Hands are written as the values of their cards, like "TTKQJ", or as the cards with the Unicode
+ * symbols of their suits with the alternate flag, like "T♠ T♦ K♠ Q♥ J♣"
  */
 impl<'a> fmt::Display for Hand<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for i in 0..consts::HAND_SIZE {
-            match self.cards[i].value {
-                CardValue::ValueT => {
-                    write!(f, "T").unwrap();
-                }
-                CardValue::ValueJ => {
-                    write!(f, "J").unwrap();
-                }
-                CardValue::ValueQ => {
-                    write!(f, "Q").unwrap();
-                }
-                CardValue::ValueK => {
-                    write!(f, "K").unwrap();
-                }
-                CardValue::ValueA => {
-                    write!(f, "A").unwrap();
-                }
-                _ => {
-                    write!(f, "{}", self.cards[i].value.clone() as u8).unwrap();
-                }
-            }
+        if f.alternate() {
+            let cards: Vec<String> = self.cards.iter().map(|card| format!("{:#}", card)).collect();
+            return write!(f, "{}", cards.join(" "));
         }
 
-        write!(f, "")
+        for card in self.cards.iter() {
+            write!(f, "{}", card.value.symbol())?;
+        }
+
+        Ok(())
     }
 }
 
@@ -132,50 +137,272 @@ impl<'a> Hand<'a> {
      * hand for the given cards.
      */
     pub fn new(hole: types::Hole<'a>, table: types::Table<'a>) -> Hand<'a> {
-        if let Some(hand) = get_pair(hole, table) {
-            hand
-        } else {
-            check_high_card(hole, table)
-        }
+        let mut cards: Vec<&Card> = hole.to_vec();
+        cards.extend_from_slice(&table);
+        Hand::from_cards(&cards)
+    }
+
+    /**
+     * from_cards finds the best hand for any combination of 5 to 7 cards, so it can be used with
+     * the partial boards of the flop and the turn.
+     */
+    pub fn from_cards(cards: &[&'a Card]) -> Hand<'a> {
+        assert!(cards.len() >= consts::HAND_SIZE, "A hand needs at least 5 cards");
+
+        get_straight_flush(cards)
+            .or_else(|| get_four_of_a_kind(cards))
+            .or_else(|| get_full_house(cards))
+            .or_else(|| get_flush(cards))
+            .or_else(|| get_straight(cards))
+            .or_else(|| get_three_of_a_kind(cards))
+            .or_else(|| get_two_pair(cards))
+            .or_else(|| get_pair(cards))
+            .unwrap_or_else(|| check_high_card(cards))
     }
 
     /**
      * get_hand_type gets a string represening the type of a hand
      */
     pub fn get_hand_type(self: &Hand<'a>) -> &'static str {
-        match self.hand_type {
-            HandType::StraightFlush => "STRAIGHT_FLUSH",
-            HandType::FourOfAKind => "FOUR_OF_A_KIND",
-            HandType::FullHouse => "FULL_HOUSE",
-            HandType::Flush => "FLUSH",
-            HandType::Straight => "STRAIGHT",
-            HandType::ThreeOfAKind => "THREE_OF_A_KIND",
-            HandType::TwoPair => "TWO_PAIR",
-            HandType::Pair => "PAIR",
-            HandType::HighCard => "HIGH_CARD",
+        self.hand_type.name()
+    }
+
+    /**
+     * hand_type gets the type of the hand
+     */
+    pub fn hand_type(&self) -> HandType {
+        self.hand_type
+    }
+
+    /**
+     * cards gets the 5 cards of the hand on the order used to compare hands
+     */
+    pub fn cards(&self) -> &[&'a Card; consts::HAND_SIZE] {
+        &self.cards
+    }
+}
+
+/**
+ * hand_type finds the type of the best hand that can be built with the cards. Unlike Hand::new it
+ * works with any number of cards, so it can be used on partial boards. With less than 5 cards it's
+ * not possible to have a Straight or a Flush.
+ */
+pub fn hand_type(cards: &[&Card]) -> HandType {
+    let mut sizes: Vec<usize> = group_by_value(cards).iter().map(|group| group.len()).collect();
+    sizes.sort_by(|a, b| b.cmp(a));
+    let first = sizes.first().cloned().unwrap_or(0);
+    let second = sizes.get(1).cloned().unwrap_or(0);
+
+    if get_straight_flush(cards).is_some() {
+        HandType::StraightFlush
+    } else if first >= 4 {
+        HandType::FourOfAKind
+    } else if first == 3 && second >= 2 {
+        HandType::FullHouse
+    } else if get_flush_cards(cards).is_some() {
+        HandType::Flush
+    } else if cards.len() >= consts::HAND_SIZE && find_straight(cards).is_some() {
+        HandType::Straight
+    } else if first == 3 {
+        HandType::ThreeOfAKind
+    } else if second == 2 {
+        HandType::TwoPair
+    } else if first == 2 {
+        HandType::Pair
+    } else {
+        HandType::HighCard
+    }
+}
+
+/**
+ * build_hand creates a Hand from the first 5 cards of a vector
+ */
+fn build_hand<'a>(hand: &[&'a Card], hand_type: HandType) -> Hand<'a> {
+    Hand {
+        cards: [hand[0], hand[1], hand[2], hand[3], hand[4]],
+        hand_type,
+    }
+}
+
+/**
+ * group_by_value splits the cards in groups of the same value. Groups are sorted by value in
+ * descending order.
+ */
+fn group_by_value<'a>(cards: &[&'a Card]) -> Vec<Vec<&'a Card>> {
+    let mut sorted = cards.to_vec();
+    sorted.sort_by(|a, b| b.cmp(a));
+
+    let mut groups: Vec<Vec<&Card>> = Vec::new();
+    for card in sorted {
+        let same_value = match groups.last() {
+            Some(group) => group[0].value == card.value,
+            None => false,
+        };
+        if same_value {
+            groups.last_mut().unwrap().push(card);
+        } else {
+            groups.push(vec![card]);
         }
     }
+    groups
 }
 
 /**
- * get_pair finds the best pair on a combination of 5 cards from a Table and 2 from a Hole. It does
- * not check if there is a better hand. If a Pair is not found None is returned.
+ * push_kickers fills the hand with the highest cards whose value is not already on the hand
  */
-fn get_pair<'a>(hole: types::Hole<'a>, table: types::Table<'a>) -> Option<Hand<'a>> {
-    // Compose a vector with the hole cards and the table cards
-    let mut cards: Vec<&Card> = Vec::new();
-    for card in hole.iter() {
-        cards.push(card);
+fn push_kickers<'a>(hand: &mut Vec<&'a Card>, cards: &[&'a Card]) {
+    let mut kickers: Vec<&Card> = cards.iter()
+        .filter(|card| hand.iter().all(|used| used.value != card.value))
+        .cloned()
+        .collect();
+    kickers.sort_by(|a, b| b.cmp(a));
+
+    let missing = consts::HAND_SIZE - hand.len();
+    hand.extend(kickers.into_iter().take(missing));
+}
+
+/**
+ * find_straight finds the highest 5 consecutive values on the cards. An Ace can be used as the
+ * lowest card, in that case it's placed at the end of the straight.
+ */
+fn find_straight<'a>(cards: &[&'a Card]) -> Option<Vec<&'a Card>> {
+    let groups = group_by_value(cards);
+    let mut highs: Vec<&Card> = groups.iter().map(|group| group[0]).collect();
+    if highs.is_empty() {
+        return None;
     }
-    for card in table.iter() {
-        cards.push(card);
+    if highs[0].value == CardValue::ValueA {
+        let ace = highs[0];
+        highs.push(ace);
     }
+
+    let mut straight: Vec<&Card> = Vec::new();
+    for card in highs {
+        let consecutive = match straight.last() {
+            Some(last) => {
+                last.value as u8 == card.value as u8 + 1 ||
+                (last.value == CardValue::Value2 && card.value == CardValue::ValueA)
+            }
+            None => true,
+        };
+        if !consecutive {
+            straight.clear();
+        }
+        straight.push(card);
+        if straight.len() == consts::HAND_SIZE {
+            return Some(straight);
+        }
+    }
+    None
+}
+
+/**
+ * get_flush_cards gets all the cards of the suit with at least 5 cards, if any
+ */
+fn get_flush_cards<'a>(cards: &[&'a Card]) -> Option<Vec<&'a Card>> {
+    for suit in &card::SUITS {
+        let suited: Vec<&Card> = cards.iter().filter(|card| card.suit == *suit).cloned().collect();
+        if suited.len() >= consts::HAND_SIZE {
+            return Some(suited);
+        }
+    }
+    None
+}
+
+/**
+ * get_straight_flush finds the best straight flush on a combination of cards. If a Straight Flush
+ * is not found None is returned.
+ */
+fn get_straight_flush<'a>(cards: &[&'a Card]) -> Option<Hand<'a>> {
+    let suited = get_flush_cards(cards)?;
+    let straight = find_straight(&suited)?;
+    Some(build_hand(&straight, HandType::StraightFlush))
+}
+
+/**
+ * get_four_of_a_kind finds the best four of a kind on a combination of cards. It does not check
+ * if there is a better hand. If a Four of a Kind is not found None is returned.
+ */
+fn get_four_of_a_kind<'a>(cards: &[&'a Card]) -> Option<Hand<'a>> {
+    let groups = group_by_value(cards);
+    let mut hand = groups.into_iter().find(|group| group.len() == 4)?;
+    push_kickers(&mut hand, cards);
+    Some(build_hand(&hand, HandType::FourOfAKind))
+}
+
+/**
+ * get_full_house finds the best full house on a combination of cards, the set of 3 goes before
+ * the pair. It does not check if there is a better hand. If a Full House is not found None is
+ * returned.
+ */
+fn get_full_house<'a>(cards: &[&'a Card]) -> Option<Hand<'a>> {
+    let groups = group_by_value(cards);
+    let three = groups.iter().position(|group| group.len() >= 3)?;
+    let two = (0..groups.len()).find(|i| *i != three && groups[*i].len() >= 2)?;
+
+    let mut hand: Vec<&Card> = groups[three][..3].to_vec();
+    hand.extend_from_slice(&groups[two][..2]);
+    Some(build_hand(&hand, HandType::FullHouse))
+}
+
+/**
+ * get_flush finds the best flush on a combination of cards. It does not check if there is a better
+ * hand. If a Flush is not found None is returned.
+ */
+fn get_flush<'a>(cards: &[&'a Card]) -> Option<Hand<'a>> {
+    let mut suited = get_flush_cards(cards)?;
+    suited.sort_by(|a, b| b.cmp(a));
+    Some(build_hand(&suited, HandType::Flush))
+}
+
+/**
+ * get_straight finds the best straight on a combination of cards. It does not check if there is a
+ * better hand. If a Straight is not found None is returned.
+ */
+fn get_straight<'a>(cards: &[&'a Card]) -> Option<Hand<'a>> {
+    let straight = find_straight(cards)?;
+    Some(build_hand(&straight, HandType::Straight))
+}
+
+/**
+ * get_three_of_a_kind finds the best three of a kind on a combination of cards. It does not check
+ * if there is a better hand. If a Three of a Kind is not found None is returned.
+ */
+fn get_three_of_a_kind<'a>(cards: &[&'a Card]) -> Option<Hand<'a>> {
+    let groups = group_by_value(cards);
+    let mut hand = groups.into_iter().find(|group| group.len() == 3)?;
+    push_kickers(&mut hand, cards);
+    Some(build_hand(&hand, HandType::ThreeOfAKind))
+}
+
+/**
+ * get_two_pair finds the best two pairs on a combination of cards. It does not check if there is
+ * a better hand. If Two Pair are not found None is returned.
+ */
+fn get_two_pair<'a>(cards: &[&'a Card]) -> Option<Hand<'a>> {
+    let pairs: Vec<Vec<&Card>> =
+        group_by_value(cards).into_iter().filter(|group| group.len() == 2).take(2).collect();
+    if pairs.len() < 2 {
+        return None;
+    }
+
+    let mut hand: Vec<&Card> = pairs.concat();
+    push_kickers(&mut hand, cards);
+    Some(build_hand(&hand, HandType::TwoPair))
+}
+
+/**
+ * get_pair finds the best pair on a combination of cards. It does not check if there is a better
+ * hand. If a Pair is not found None is returned.
+ */
+fn get_pair<'a>(cards: &[&'a Card]) -> Option<Hand<'a>> {
+    let mut cards: Vec<&Card> = cards.to_vec();
     cards.sort(); // Order the cards so it's possible to know where the most valuable cards are
 
     // Count the occurrences of each value
-    let ref mut occurrences: HashMap<&CardValue, Vec<&Card>> = HashMap::new();
+    let occurrences: &mut HashMap<&CardValue, Vec<&Card>> = &mut HashMap::new();
     for card in &cards {
-        let count = occurrences.entry(&card.value).or_insert(Vec::new());
+        let count = occurrences.entry(&card.value).or_default();
         count.push(card);
     }
 
@@ -214,33 +441,19 @@ fn get_pair<'a>(hole: types::Hole<'a>, table: types::Table<'a>) -> Option<Hand<'
         hand.push(kickers.pop().unwrap());
     }
 
-    Some(Hand {
-        cards: [hand[0], hand[1], hand[2], hand[3], hand[4]],
-        hand_type: HandType::Pair,
-    })
+    Some(build_hand(&hand, HandType::Pair))
 }
 
 /**
- * get_pair finds the best pair on a combination of 5 cards from a Table and 2 from a Hole. It does
- * not check if there is a better hand. If a Pair is not found None is returned.
+ * check_high_card gets the 5 highest cards of a combination of cards. It's used when there is no
+ * better hand.
  */
-fn check_high_card<'a>(hole: types::Hole<'a>, table: types::Table<'a>) -> Hand<'a> {
-    // Compose a vector with the hole cards and the table cards
-    let mut cards: Vec<&Card> = Vec::new();
-    for card in hole.iter() {
-        cards.push(card);
-    }
-    for card in table.iter() {
-        cards.push(card);
-    }
-
+fn check_high_card<'a>(cards: &[&'a Card]) -> Hand<'a> {
+    let mut cards: Vec<&Card> = cards.to_vec();
     cards.sort();
     cards.reverse();
 
-    Hand {
-        cards: [cards[0], cards[1], cards[2], cards[3], cards[4]],
-        hand_type: HandType::HighCard,
-    }
+    build_hand(&cards, HandType::HighCard)
 }
 
 #[cfg(test)]
@@ -249,6 +462,9 @@ mod tests {
     use super::HandType;
     use super::get_pair;
     use super::check_high_card;
+    use super::get_full_house;
+    use super::hand_type;
+    use super::get_straight;
     use super::super::card::Card;
     use super::super::card::CardValue;
     use super::super::card::CardSuit;
@@ -285,7 +501,8 @@ mod tests {
                          suit: CardSuit::Spades,
                      }];
 
-        let hand: Hand = check_high_card(hole, table);
+        let cards: Vec<&Card> = hole.iter().chain(table.iter()).cloned().collect();
+        let hand: Hand = check_high_card(&cards);
 
         assert!(hand.hand_type == HandType::HighCard);
         assert!(hand.cards[0].value == CardValue::ValueA);
@@ -327,7 +544,8 @@ mod tests {
                          suit: CardSuit::Spades,
                      }];
 
-        let hand: Hand = get_pair(hole, table).unwrap();
+        let cards: Vec<&Card> = hole.iter().chain(table.iter()).cloned().collect();
+        let hand: Hand = get_pair(&cards).unwrap();
 
         assert!(hand.hand_type == HandType::Pair);
         assert!(hand.cards[0].value == CardValue::ValueQ);
@@ -369,6 +587,95 @@ mod tests {
                          suit: CardSuit::Spades,
                      }];
 
-        assert_eq!(get_pair(hole, table).is_none(), true);
+        let cards: Vec<&Card> = hole.iter().chain(table.iter()).cloned().collect();
+        assert!(get_pair(&cards).is_none());
+    }
+
+    fn parse(cards: &str) -> Vec<Card> {
+        cards.split_whitespace()
+            .map(|card| Card::new(card.chars().next().unwrap(), card.chars().nth(1).unwrap()))
+            .collect()
+    }
+
+    fn best(cards: &str) -> (HandType, String) {
+        let cards = parse(cards);
+        let refs: Vec<&Card> = cards.iter().collect();
+        let hand = Hand::from_cards(&refs);
+        (hand.hand_type, hand.to_string())
+    }
+
+    #[test]
+    fn test_hand_types() {
+        assert_eq!(best("9H TH 2C JH QH KH 3S"), (HandType::StraightFlush, "KQJT9".to_string()));
+        assert_eq!(best("9H 9S 9C 9D 2C 3C KS"), (HandType::FourOfAKind, "9999K".to_string()));
+        assert_eq!(best("2H 2S 3C 3D 3H 2C KS"), (HandType::FullHouse, "33322".to_string()));
+        assert_eq!(best("2H 7H 9H TH AH KH 3C"), (HandType::Flush, "AKT97".to_string()));
+        assert_eq!(best("2H 3C 4D 5S AH KH QC"), (HandType::Straight, "5432A".to_string()));
+        assert_eq!(best("7H 7C 7D 2S AH KH QC"), (HandType::ThreeOfAKind, "777AK".to_string()));
+        assert_eq!(best("7H 7C 2D 2S 5H 5D QC"), (HandType::TwoPair, "7755Q".to_string()));
+        assert_eq!(best("TC JC 2H 7H TH QS KC"), (HandType::Pair, "TTKQJ".to_string()));
+        assert_eq!(best("8D 7C KS 9D 5C 3S 2D"), (HandType::HighCard, "K9875".to_string()));
+    }
+
+    #[test]
+    fn test_partial_board() {
+        assert_eq!(best("AS KS QS JS TS"), (HandType::StraightFlush, "AKQJT".to_string()));
+        assert_eq!(best("AS AD 4C 4H 9S 9C"), (HandType::TwoPair, "AA994".to_string()));
+    }
+
+    #[test]
+    fn test_full_house_from_two_sets() {
+        let cards = parse("4S 4D 4C KH KD KC 2S");
+        let refs: Vec<&Card> = cards.iter().collect();
+
+        let hand = get_full_house(&refs).unwrap();
+        assert_eq!(hand.to_string(), "KKK44");
+    }
+
+    #[test]
+    fn test_straight_missing() {
+        let cards = parse("4S 5D 6C 7H 9D KC 2S");
+        let refs: Vec<&Card> = cards.iter().collect();
+
+        assert!(get_straight(&refs).is_none());
+    }
+
+    #[test]
+    fn test_compare_hands() {
+        let flush = parse("2H 7H 9H TH 3H");
+        let straight = parse("AS KD QC JH TS");
+        let wheel = parse("5S 4D 3C 2H AS");
+        let six_high = parse("6S 5D 4C 3H 2S");
+
+        let refs1: Vec<&Card> = flush.iter().collect();
+        let refs2: Vec<&Card> = straight.iter().collect();
+        assert!(Hand::from_cards(&refs1) > Hand::from_cards(&refs2));
+
+        let refs1: Vec<&Card> = six_high.iter().collect();
+        let refs2: Vec<&Card> = wheel.iter().collect();
+        assert!(Hand::from_cards(&refs1) > Hand::from_cards(&refs2));
+    }
+
+    #[test]
+    fn test_partial_hand_type() {
+        let boards = ["AS", "AS AD", "AS AD 4C 4H", "AS AD AC 4H", "AS AD AC AH", "9H 8H 7H 6H"];
+        let types: Vec<HandType> = boards.iter()
+            .map(|cards| {
+                let cards = parse(cards);
+                let refs: Vec<&Card> = cards.iter().collect();
+                hand_type(&refs)
+            })
+            .collect();
+        assert_eq!(types,
+                   vec![HandType::HighCard,
+                        HandType::Pair,
+                        HandType::TwoPair,
+                        HandType::ThreeOfAKind,
+                        HandType::FourOfAKind,
+                        HandType::HighCard]);
+
+        let cards = parse("9H TH 2C JH QH KH 3S");
+        let refs: Vec<&Card> = cards.iter().collect();
+        assert_eq!(hand_type(&refs), Hand::from_cards(&refs).hand_type());
     }
 }
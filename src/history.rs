@@ -0,0 +1,204 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// External imports
+use std::fmt;
+
+// Internal imports
+use types::Street;
+use card::Card;
+
+/**
+ * Amount of money or chips in hundredths, so cents are exact. A stack of 1500 tournament chips is
+ * 150000 and $0.02 is 2.
+ */
+pub type Amount = i64;
+
+/**
+ * HandHistory is a hand of Texas Hold'em as it was recorded by a poker site
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct HandHistory {
+    pub site: String,
+    pub id: u64,
+    pub tournament: Option<Tournament>, // None for cash games
+    pub currency: Option<String>, // Currency of the stakes, if any
+    pub limit: String, // Betting structure, like "No Limit"
+    pub small_blind: Amount,
+    pub big_blind: Amount,
+    pub date: String,
+    pub table: String,
+    pub max_seats: u32,
+    pub button: u32, // Seat number of the button
+    pub seats: Vec<Seat>,
+    pub hero: Option<String>, // Player whose hole cards were dealt to the recorder
+    pub hero_cards: Option<[Card; 2]>,
+    pub actions: Vec<Action>, // Every action on the order it happened, blinds included
+    pub board: Vec<Card>,
+    pub total_pot: Amount,
+    pub rake: Amount,
+}
+
+/**
+ * Tournament identifies the tournament of a hand
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tournament {
+    pub id: u64,
+    pub buy_in: String, // As written by the site, like "$0.98+$0.12 USD"
+    pub level: String,
+}
+
+/**
+ * Seat is a player sitting at the table when the hand starts
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Seat {
+    pub number: u32,
+    pub player: String,
+    pub stack: Amount,
+    pub sitting_out: bool,
+}
+
+/**
+ * Action is anything done by a player during the hand
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Action {
+    pub street: Street,
+    pub player: String,
+    pub kind: ActionKind,
+    pub all_in: bool,
+}
+
+/**
+ * ActionKind is any of the possible actions. Amounts of calls and bets are the chips put on the
+ * pot by the action. The amount of a raise is the increment over the previous bet, and its total
+ * is the whole bet of the player on the street.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub enum ActionKind {
+    Ante(Amount),
+    SmallBlind(Amount),
+    BigBlind(Amount),
+    SmallAndBigBlind(Amount), // Dead small blind and big blind posted together
+    Fold,
+    Check,
+    Call(Amount),
+    Bet(Amount),
+    Raise { amount: Amount, to: Amount },
+    UncalledBet(Amount), // Part of a bet that nobody called, returned to the player
+    Show([Card; 2]),
+    Muck,
+    Collect(Amount),
+}
+
+impl HandHistory {
+    /**
+     * seat gets the seat of a player
+     */
+    pub fn seat(&self, player: &str) -> Option<&Seat> {
+        self.seats.iter().find(|seat| seat.player == player)
+    }
+
+    /**
+     * actions_on gets the actions of a street
+     */
+    pub fn actions_on(&self, street: Street) -> Vec<&Action> {
+        self.actions.iter().filter(|action| action.street == street).collect()
+    }
+}
+
+/**
+ * ParseError is an error found while reading a hand history, with the line where it happened
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/**
+ * parse_amount reads an amount of money or chips like "$1.05", "€0.50" or "1,500"
+ */
+pub fn parse_amount(text: &str) -> Option<Amount> {
+    let digits: String = text.trim()
+        .trim_start_matches(['$', '€', '£'])
+        .chars()
+        .filter(|c| *c != ',')
+        .collect();
+
+    let (units, hundredths) = match digits.find('.') {
+        Some(dot) => (&digits[..dot], &digits[dot + 1..]),
+        None => (&digits[..], ""),
+    };
+    if units.is_empty() || hundredths.len() > 2 ||
+       !units.chars().chain(hundredths.chars()).all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let units: Amount = units.parse().ok()?;
+    let hundredths: Amount = format!("{:0<2}", hundredths).parse().ok()?;
+    units.checked_mul(100)?.checked_add(hundredths)
+}
+
+/**
+ * format_amount writes an amount without trailing zeros, like "1.5" or "1500"
+ */
+pub fn format_amount(amount: Amount) -> String {
+    let sign = if amount < 0 { "-" } else { "" };
+    let amount = amount.abs();
+    match amount % 100 {
+        0 => format!("{}{}", sign, amount / 100),
+        hundredths if hundredths % 10 == 0 => {
+            format!("{}{}.{}", sign, amount / 100, hundredths / 10)
+        }
+        hundredths => format!("{}{}.{:02}", sign, amount / 100, hundredths),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_amount;
+    use super::format_amount;
+
+    #[test]
+    fn test_amounts() {
+        assert_eq!(parse_amount("$0.02"), Some(2));
+        assert_eq!(parse_amount("$2"), Some(200));
+        assert_eq!(parse_amount("€1.5"), Some(150));
+        assert_eq!(parse_amount("1,500"), Some(150000));
+        assert_eq!(parse_amount("$0.001"), None);
+        assert_eq!(parse_amount("abc"), None);
+
+        assert_eq!(format_amount(150000), "1500");
+        assert_eq!(format_amount(150), "1.5");
+        assert_eq!(format_amount(2), "0.02");
+        assert_eq!(format_amount(-105), "-1.05");
+    }
+}
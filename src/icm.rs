@@ -0,0 +1,280 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// Internal imports
+use preflop::EquityTable;
+use preflop::HandClass;
+use rng::Rng;
+
+/**
+ * Largest field that exact computes. The work doubles with every player.
+ */
+pub const MAX_EXACT_PLAYERS: usize = 20;
+
+/**
+ * Largest field that equities computes exactly, sampling the larger ones
+ */
+pub const EXACT_PLAYERS: usize = 16;
+
+/**
+ * Number of finishing orders that equities samples for fields too large to be computed exactly
+ */
+pub const SAMPLES: usize = 100000;
+
+/**
+ * exact computes the prize equity of every player with the Independent Chip Model
+ * (Malmuth-Harville): the chances of finishing first are proportional to the stacks, and the
+ * chances of finishing on any other place are the ones of finishing first among the players that
+ * are left. Payouts are the prizes from the first place down, and players without chips get
+ * nothing.
+ */
+pub fn exact(stacks: &[f64], payouts: &[f64]) -> Vec<f64> {
+    let players = stacks.len();
+    assert!(players <= MAX_EXACT_PLAYERS, "Too many players for an exact computation");
+    let places = payouts.len().min(players);
+
+    // Probability of every set of players taking the first places, and the chips they have
+    let mut probabilities = vec![0.0; 1 << players];
+    let mut chips = vec![0.0; 1 << players];
+    probabilities[0] = 1.0;
+    let total: f64 = stacks.iter().sum();
+
+    let mut equities = vec![0.0; players];
+    for set in 0..1usize << players {
+        if set != 0 {
+            let lowest = set.trailing_zeros() as usize;
+            chips[set] = chips[set & (set - 1)] + stacks[lowest];
+        }
+        let probability = probabilities[set];
+        let place = set.count_ones() as usize;
+        let remaining = total - chips[set];
+        if probability == 0.0 || place >= places || remaining <= 0.0 {
+            continue;
+        }
+
+        for player in (0..players).filter(|player| set & 1 << player == 0) {
+            let next = probability * stacks[player] / remaining;
+            equities[player] += next * payouts[place];
+            probabilities[set | 1 << player] += next;
+        }
+    }
+
+    equities
+}
+
+/**
+ * sampled estimates the prize equities of the Independent Chip Model drawing random finishing
+ * orders, which works with fields of any size. The same seed gives the same estimation.
+ */
+pub fn sampled(stacks: &[f64], payouts: &[f64], samples: usize, seed: u64) -> Vec<f64> {
+    let places = payouts.len().min(stacks.len());
+    let mut rng = Rng::new(seed);
+    let mut equities = vec![0.0; stacks.len()];
+
+    for _ in 0..samples {
+        let mut remaining: Vec<usize> = (0..stacks.len()).filter(|i| stacks[*i] > 0.0).collect();
+        let mut chips: f64 = remaining.iter().map(|i| stacks[*i]).sum();
+
+        for payout in payouts.iter().take(places) {
+            if remaining.is_empty() {
+                break;
+            }
+            let mut target = rng.next_f64() * chips;
+            let mut position = remaining.len() - 1;
+            for (i, player) in remaining.iter().enumerate() {
+                if target < stacks[*player] {
+                    position = i;
+                    break;
+                }
+                target -= stacks[*player];
+            }
+
+            let player = remaining.swap_remove(position);
+            equities[player] += payout;
+            chips -= stacks[player];
+        }
+    }
+
+    equities.iter().map(|equity| equity / samples as f64).collect()
+}
+
+/**
+ * equities computes the prize equities exactly for small fields and samples them for large ones
+ */
+pub fn equities(stacks: &[f64], payouts: &[f64]) -> Vec<f64> {
+    if stacks.len() <= EXACT_PLAYERS {
+        exact(stacks, payouts)
+    } else {
+        sampled(stacks, payouts, SAMPLES, 0)
+    }
+}
+
+/**
+ * Confrontation is a hand where everybody folds to a player who can shove all-in or fold, and a
+ * single opponent is left to call or fold
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Confrontation {
+    pub stacks: Vec<f64>, // Stacks of every player before posting the blinds and the antes
+    pub posted: Vec<f64>, // Blinds and antes posted by every player
+    pub pusher: usize,
+    pub caller: usize,
+}
+
+/**
+ * Decision compares the prize equity of folding with the one of playing a hand
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Decision {
+    pub fold: f64, // Prize equity after folding
+    pub play: f64, // Prize equity after shoving or calling
+    pub probability: f64, // Probability of the opponent calling the shove, or 1 when calling
+    pub equity: f64, // Chances of winning the showdown
+}
+
+impl Decision {
+    /**
+     * is_profitable checks if playing the hand is better than folding it
+     */
+    pub fn is_profitable(&self) -> bool {
+        self.play > self.fold
+    }
+}
+
+impl Confrontation {
+    /**
+     * pot gets the chips on the pot before the shove
+     */
+    fn pot(&self) -> f64 {
+        self.posted.iter().sum()
+    }
+
+    /**
+     * after_fold gets the stacks once the player folds and the other one takes the pot
+     */
+    fn after_fold(&self, folder: usize) -> Vec<f64> {
+        let winner = if folder == self.pusher { self.caller } else { self.pusher };
+        let mut stacks: Vec<f64> = self.stacks
+            .iter()
+            .zip(&self.posted)
+            .map(|(stack, posted)| stack - posted)
+            .collect();
+        stacks[winner] += self.pot();
+        stacks
+    }
+
+    /**
+     * after_showdown gets the stacks once both players are all-in and one of them wins
+     */
+    fn after_showdown(&self, winner: usize) -> Vec<f64> {
+        let loser = if winner == self.pusher { self.caller } else { self.pusher };
+        let all_in = self.stacks[self.pusher].min(self.stacks[self.caller]);
+        let mut stacks = self.after_fold(loser);
+        stacks[winner] += all_in - self.posted[loser];
+        stacks[loser] -= all_in - self.posted[loser];
+        stacks
+    }
+
+    /**
+     * push_decision compares folding with shoving a hand, given the frequency the caller calls
+     * with every class (indexed like HandClass::index). The showdown uses the equity of
+     * the hand against the calling range, counting ties as half a win.
+     */
+    pub fn push_decision(&self,
+                         payouts: &[f64],
+                         table: &EquityTable,
+                         hand: HandClass,
+                         calling: &[f64])
+                         -> Decision {
+        let (probability, equity) = matchup(table, hand, calling);
+        let equity_of = |stacks: Vec<f64>| equities(&stacks, payouts)[self.pusher];
+
+        let called = equity * equity_of(self.after_showdown(self.pusher)) +
+                     (1.0 - equity) * equity_of(self.after_showdown(self.caller));
+        Decision {
+            fold: equity_of(self.after_fold(self.pusher)),
+            play: (1.0 - probability) * equity_of(self.after_fold(self.caller)) +
+                  probability * called,
+            probability,
+            equity,
+        }
+    }
+
+    /**
+     * call_decision compares folding with calling a shove with a hand, given the frequency the
+     * pusher shoves every class
+     */
+    pub fn call_decision(&self,
+                         payouts: &[f64],
+                         table: &EquityTable,
+                         hand: HandClass,
+                         shoving: &[f64])
+                         -> Decision {
+        let (_, equity) = matchup(table, hand, shoving);
+        let equity_of = |stacks: Vec<f64>| equities(&stacks, payouts)[self.caller];
+
+        Decision {
+            fold: equity_of(self.after_fold(self.caller)),
+            play: equity * equity_of(self.after_showdown(self.caller)) +
+                  (1.0 - equity) * equity_of(self.after_showdown(self.pusher)),
+            probability: 1.0,
+            equity,
+        }
+    }
+}
+
+/**
+ * matchup gets the probability of the opponent playing the range given the hand (card removal
+ * included) and the equity of the hand against the range
+ */
+fn matchup(table: &EquityTable, hand: HandClass, range: &[f64]) -> (f64, f64) {
+    let mut weights = 0.0;
+    let mut played = 0.0;
+    let mut wins = 0.0;
+    for villain in HandClass::all() {
+        let weight = table.weight(hand, villain);
+        let frequency = range[villain.index()];
+        weights += weight;
+        played += weight * frequency;
+        wins += weight * frequency * table.equity(hand, villain);
+    }
+
+    let equity = if played > 0.0 { wins / played } else { 0.5 };
+    (played / weights, equity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::exact;
+
+    #[test]
+    fn test_exact() {
+        let equities = exact(&[5000.0, 3000.0, 2000.0], &[50.0, 30.0, 20.0]);
+
+        // The first player finishes first half of the times, second when any other player wins
+        // and then beats the remaining one, and third otherwise
+        let second = 0.3 * 5.0 / 7.0 + 0.2 * 5.0 / 8.0;
+        let expected = 0.5 * 50.0 + second * 30.0 + (0.5 - second) * 20.0;
+        assert!((equities[0] - expected).abs() < 1e-9);
+        assert!((equities.iter().sum::<f64>() - 100.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,607 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// External imports
+use std::cmp::Ordering;
+use std::sync::OnceLock;
+
+// Internal imports
+use types;
+use consts;
+use card;
+use card::Card;
+use combinatorics::binomial;
+
+/**
+ * Shape holds how many cards of a single suit have been dealt on every round
+ */
+type Shape = Vec<usize>;
+
+/**
+ * Configuration is a canonical assignment of shapes to the suits, sorted in descending order. All
+ * the hands that share a configuration are indexed together starting at offset.
+ */
+struct Configuration {
+    shapes: Vec<Shape>,
+    offset: u64,
+}
+
+/**
+ * HandIndexer maps hands to a dense index where every hand that is equivalent under a permutation
+ * of the suits gets the same index. Cards are dealt in rounds (for example the hole cards and the
+ * flop) and cards from different rounds are never considered interchangeable.
+ */
+pub struct HandIndexer {
+    cards_per_round: Vec<usize>,
+    configurations: Vec<Vec<Configuration>>, // Sorted configurations of every round
+    sizes: Vec<u64>, // Number of isomorphic hands on every round
+}
+
+impl HandIndexer {
+    /**
+     * new creates an indexer for the given number of cards dealt on every round
+     */
+    pub fn new(cards_per_round: &[usize]) -> HandIndexer {
+        let mut configurations = Vec::new();
+        let mut sizes = Vec::new();
+
+        for round in 0..cards_per_round.len() {
+            let mut found = Vec::new();
+            enumerate_configurations(&cards_per_round[..round + 1],
+                                     &mut Vec::new(),
+                                     &mut found);
+            found.sort();
+
+            // Every configuration gets a slice of the index as big as the number of hands in it
+            let mut size = 0;
+            let mut round_configurations = Vec::new();
+            for shapes in found {
+                let count = configuration_size(&shapes);
+                round_configurations.push(Configuration {
+                    shapes,
+                    offset: size,
+                });
+                size += count;
+            }
+
+            configurations.push(round_configurations);
+            sizes.push(size);
+        }
+
+        HandIndexer {
+            cards_per_round: cards_per_round.to_vec(),
+            configurations,
+            sizes,
+        }
+    }
+
+    /**
+     * for_street creates an indexer for the hole cards plus the board of the given street. The
+     * board is a single round, so the order in which its cards were dealt does not matter.
+     */
+    pub fn for_street(street: types::Street) -> HandIndexer {
+        match street {
+            types::Street::Preflop => HandIndexer::new(&[consts::HOLE_SIZE]),
+            _ => HandIndexer::new(&[consts::HOLE_SIZE, street.board_size()]),
+        }
+    }
+
+    /**
+     * rounds gets the number of rounds handled by the indexer
+     */
+    pub fn rounds(&self) -> usize {
+        self.cards_per_round.len()
+    }
+
+    /**
+     * size gets the number of different isomorphic hands up to the given round
+     */
+    pub fn size(&self, round: usize) -> u64 {
+        self.sizes[round]
+    }
+
+    /**
+     * index gets the index of a hand. The cards must be sorted by round and the hand must end on
+     * a round boundary, the round is deduced from the number of cards.
+     */
+    pub fn index(&self, cards: &[&Card]) -> u64 {
+        let round = self.round_of(cards.len());
+
+        // Sets of ranks dealt on every round for every suit
+        let mut ranks = vec![vec![0u16; round + 1]; card::SUITS.len()];
+        let mut start = 0;
+        for (i, count) in self.cards_per_round[..round + 1].iter().enumerate() {
+            for card in &cards[start..start + count] {
+                let bit = 1 << card.rank();
+                assert!(ranks[card.suit.index()].iter().all(|set| set & bit == 0),
+                        "Duplicated card");
+                ranks[card.suit.index()][i] |= bit;
+            }
+            start += count;
+        }
+
+        // Index every suit on its own
+        let mut suits: Vec<(Shape, u64)> = ranks.iter()
+            .map(|sets| {
+                let shape = sets.iter().map(|set| set.count_ones() as usize).collect();
+                (shape, suit_index(sets))
+            })
+            .collect();
+        suits.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+        let shapes: Vec<Shape> = suits.iter().map(|suit| suit.0.clone()).collect();
+        let configurations = &self.configurations[round];
+        let configuration = configurations.binary_search_by(|c| c.shapes.cmp(&shapes))
+            .expect("Invalid hand");
+        let configuration = &configurations[configuration];
+
+        // Suits sharing a shape can be swapped so they are indexed as a multiset
+        let mut index = 0;
+        let mut multiplier = 1;
+        for group in groups(&configuration.shapes) {
+            let values: Vec<u64> = suits[group.clone()].iter().map(|suit| suit.1).collect();
+            index += multiplier * multiset_index(&values);
+            multiplier *= multiset_size(shape_size(&configuration.shapes[group.start]),
+                                        group.len() as u64);
+        }
+
+        configuration.offset + index
+    }
+
+    /**
+     * unindex gets the canonical hand of the given round and index. Cards are sorted by round and
+     * by descending order inside every round.
+     */
+    pub fn unindex(&self, round: usize, index: u64) -> Vec<Card> {
+        assert!(index < self.sizes[round], "Index out of range");
+
+        // The configuration is the last one starting before the index
+        let configurations = &self.configurations[round];
+        let position = match configurations.binary_search_by(|c| c.offset.cmp(&index)) {
+            Ok(position) => position,
+            Err(position) => position - 1,
+        };
+        let configuration = &configurations[position];
+
+        // Recover the index of every suit, in the same order that they were indexed
+        let mut suit_indexes = Vec::new();
+        let mut remaining = index - configuration.offset;
+        for group in groups(&configuration.shapes) {
+            let size = multiset_size(shape_size(&configuration.shapes[group.start]),
+                                     group.len() as u64);
+            suit_indexes.extend(multiset_unindex(remaining % size, group.len()));
+            remaining /= size;
+        }
+
+        // Deal the cards of every suit on its round
+        let mut rounds: Vec<Vec<Card>> = vec![Vec::new(); round + 1];
+        for (suit, shape) in configuration.shapes.iter().enumerate() {
+            let sets = suit_unindex(shape, suit_indexes[suit]);
+            for (i, set) in sets.iter().enumerate() {
+                for rank in 0..card::VALUES.len() {
+                    if set & (1 << rank) != 0 {
+                        rounds[i].push(Card {
+                            value: card::VALUES[rank],
+                            suit: card::SUITS[suit],
+                        });
+                    }
+                }
+            }
+        }
+
+        let mut cards = Vec::new();
+        for mut round_cards in rounds {
+            round_cards.sort_by(|a, b| b.cmp(a));
+            cards.extend(round_cards);
+        }
+        cards
+    }
+
+    /**
+     * canonicalize gets the representative of all the hands isomorphic to the given one
+     */
+    pub fn canonicalize(&self, cards: &[&Card]) -> Vec<Card> {
+        let round = self.round_of(cards.len());
+        self.unindex(round, self.index(cards))
+    }
+
+    /**
+     * round_of gets the round that ends after the given number of cards
+     */
+    fn round_of(&self, count: usize) -> usize {
+        let mut total = 0;
+        for (round, cards) in self.cards_per_round.iter().enumerate() {
+            total += cards;
+            if total == count {
+                return round;
+            }
+        }
+        panic!("{} cards do not end on a round", count);
+    }
+}
+
+/**
+ * shared gets an indexer of the street that is built on the first use and kept for the whole
+ * program, so the free functions below don't rebuild its configurations on every call
+ */
+fn shared(street: types::Street) -> &'static HandIndexer {
+    static PREFLOP: OnceLock<HandIndexer> = OnceLock::new();
+    static FLOP: OnceLock<HandIndexer> = OnceLock::new();
+    static TURN: OnceLock<HandIndexer> = OnceLock::new();
+    static RIVER: OnceLock<HandIndexer> = OnceLock::new();
+
+    let indexer = match street {
+        types::Street::Preflop => &PREFLOP,
+        types::Street::Flop => &FLOP,
+        types::Street::Turn => &TURN,
+        types::Street::River => &RIVER,
+    };
+    indexer.get_or_init(|| HandIndexer::for_street(street))
+}
+
+/**
+ * index gets the isomorphic index of a player's hole cards and the board (0, 3, 4 or 5 cards)
+ */
+pub fn index(hole: types::Hole, board: &[&Card]) -> u64 {
+    let street = types::Street::from_board_size(board.len()).expect("Invalid board size");
+    let mut cards: Vec<&Card> = hole.to_vec();
+    cards.extend_from_slice(board);
+    shared(street).index(&cards)
+}
+
+/**
+ * unindex gets the canonical hole cards and board of the given street and index
+ */
+pub fn unindex(street: types::Street, index: u64) -> (Vec<Card>, Vec<Card>) {
+    let indexer = shared(street);
+    let mut cards = indexer.unindex(indexer.rounds() - 1, index);
+    let board = cards.split_off(2);
+    (cards, board)
+}
+
+/**
+ * colex_index ranks a set of ranks (as a bitmask) on colexicographic order
+ */
+fn colex_index(set: u16) -> u64 {
+    let mut index = 0;
+    let mut position = 1;
+    for rank in 0..16 {
+        if set & (1 << rank) != 0 {
+            index += binomial(rank, position);
+            position += 1;
+        }
+    }
+    index
+}
+
+/**
+ * colex_unindex gets the set of k ranks which has the given colexicographic index
+ */
+fn colex_unindex(mut index: u64, k: usize) -> u16 {
+    let mut set = 0;
+    for position in (1..k as u64 + 1).rev() {
+        let mut rank = position - 1;
+        while binomial(rank + 1, position) <= index {
+            rank += 1;
+        }
+        index -= binomial(rank, position);
+        set |= 1 << rank;
+    }
+    set
+}
+
+/**
+ * suit_index indexes the rank sets dealt on every round for a single suit. Ranks of every round
+ * are compacted leaving out the ones already used on previous rounds.
+ */
+fn suit_index(sets: &[u16]) -> u64 {
+    let mut index = 0;
+    let mut multiplier = 1;
+    let mut used: u16 = 0;
+    for set in sets {
+        index += multiplier * colex_index(compact(*set, used));
+        multiplier *= binomial((card::VALUES.len() as u32 - used.count_ones()) as u64,
+                               set.count_ones() as u64);
+        used |= *set;
+    }
+    index
+}
+
+/**
+ * suit_unindex gets the rank sets of a single suit from its shape and index
+ */
+fn suit_unindex(shape: &[usize], mut index: u64) -> Vec<u16> {
+    let mut sets = Vec::new();
+    let mut used: u16 = 0;
+    for count in shape {
+        let size = binomial((card::VALUES.len() as u32 - used.count_ones()) as u64,
+                            *count as u64);
+        let set = expand(colex_unindex(index % size, *count), used);
+        index /= size;
+        used |= set;
+        sets.push(set);
+    }
+    sets
+}
+
+/**
+ * compact removes from set the positions used so the remaining ranks are consecutive
+ */
+fn compact(set: u16, used: u16) -> u16 {
+    let mut result = 0;
+    let mut position = 0;
+    for rank in 0..card::VALUES.len() {
+        if used & (1 << rank) == 0 {
+            if set & (1 << rank) != 0 {
+                result |= 1 << position;
+            }
+            position += 1;
+        }
+    }
+    result
+}
+
+/**
+ * expand is the inverse of compact
+ */
+fn expand(set: u16, used: u16) -> u16 {
+    let mut result = 0;
+    let mut position = 0;
+    for rank in 0..card::VALUES.len() {
+        if used & (1 << rank) == 0 {
+            if set & (1 << position) != 0 {
+                result |= 1 << rank;
+            }
+            position += 1;
+        }
+    }
+    result
+}
+
+/**
+ * shape_size gets the number of different ways to deal a suit with the given shape
+ */
+fn shape_size(shape: &[usize]) -> u64 {
+    let mut size = 1;
+    let mut remaining = card::VALUES.len() as u64;
+    for count in shape {
+        size *= binomial(remaining, *count as u64);
+        remaining -= *count as u64;
+    }
+    size
+}
+
+/**
+ * multiset_size gets the number of multisets of k elements taken from n values
+ */
+fn multiset_size(n: u64, k: u64) -> u64 {
+    binomial(n + k - 1, k)
+}
+
+/**
+ * multiset_index ranks a multiset of values. Sorting the values and adding its position to every
+ * one of them gives a set that can be ranked on colexicographic order.
+ */
+fn multiset_index(values: &[u64]) -> u64 {
+    let mut sorted = values.to_vec();
+    sorted.sort();
+    sorted.iter()
+        .enumerate()
+        .map(|(i, value)| binomial(value + i as u64, i as u64 + 1))
+        .sum()
+}
+
+/**
+ * multiset_unindex gets the sorted multiset of k values with the given index
+ */
+fn multiset_unindex(mut index: u64, k: usize) -> Vec<u64> {
+    let mut values = vec![0; k];
+    for i in (0..k).rev() {
+        let position = i as u64 + 1;
+        let mut value = i as u64;
+        while binomial(value + 1, position) <= index {
+            value += 1;
+        }
+        index -= binomial(value, position);
+        values[i] = value - i as u64;
+    }
+    values
+}
+
+/**
+ * groups splits a sorted list of shapes into ranges of equal shapes
+ */
+fn groups(shapes: &[Shape]) -> Vec<::std::ops::Range<usize>> {
+    let mut groups = Vec::new();
+    let mut start = 0;
+    for i in 1..shapes.len() + 1 {
+        if i == shapes.len() || shapes[i] != shapes[start] {
+            groups.push(start..i);
+            start = i;
+        }
+    }
+    groups
+}
+
+/**
+ * configuration_size gets the number of isomorphic hands with the given configuration
+ */
+fn configuration_size(shapes: &[Shape]) -> u64 {
+    groups(shapes)
+        .iter()
+        .map(|group| multiset_size(shape_size(&shapes[group.start]), group.len() as u64))
+        .product()
+}
+
+/**
+ * enumerate_configurations finds every configuration for the given cards per round. Shapes are
+ * assigned to the suits on descending order so every configuration is found only once.
+ */
+fn enumerate_configurations(cards_per_round: &[usize],
+                            shapes: &mut Vec<Shape>,
+                            found: &mut Vec<Vec<Shape>>) {
+    // Cards of every round still not assigned to a suit
+    let remaining: Vec<usize> = cards_per_round.iter()
+        .enumerate()
+        .map(|(round, cards)| cards - shapes.iter().map(|shape| shape[round]).sum::<usize>())
+        .collect();
+
+    if shapes.len() == card::SUITS.len() {
+        if remaining.iter().all(|cards| *cards == 0) {
+            found.push(shapes.clone());
+        }
+        return;
+    }
+
+    let mut candidates = Vec::new();
+    enumerate_shapes(&remaining, &mut Vec::new(), &mut candidates);
+    for shape in candidates {
+        let sorted = match shapes.last() {
+            Some(previous) => shape.cmp(previous) != Ordering::Greater,
+            None => true,
+        };
+        if sorted {
+            shapes.push(shape);
+            enumerate_configurations(cards_per_round, shapes, found);
+            shapes.pop();
+        }
+    }
+}
+
+/**
+ * enumerate_shapes finds every shape of a suit with at most the given cards per round
+ */
+fn enumerate_shapes(remaining: &[usize], shape: &mut Shape, found: &mut Vec<Shape>) {
+    if shape.len() == remaining.len() {
+        if shape.iter().sum::<usize>() <= card::VALUES.len() {
+            found.push(shape.clone());
+        }
+        return;
+    }
+
+    for count in 0..remaining[shape.len()] + 1 {
+        shape.push(count);
+        enumerate_shapes(remaining, shape, found);
+        shape.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use super::HandIndexer;
+    use super::super::types::Street;
+    use super::super::card;
+    use super::super::card::Card;
+
+    #[test]
+    fn test_street_sizes() {
+        assert_eq!(HandIndexer::for_street(Street::Preflop).size(0), 169);
+        assert_eq!(HandIndexer::for_street(Street::Flop).size(1), 1286792);
+        assert_eq!(HandIndexer::for_street(Street::Turn).size(1), 13960050);
+        assert_eq!(HandIndexer::for_street(Street::River).size(1), 123156254);
+    }
+
+    #[test]
+    fn test_round_sizes() {
+        // Every board card dealt on its own round
+        let indexer = HandIndexer::new(&[2, 3, 1, 1]);
+        assert_eq!(indexer.size(0), 169);
+        assert_eq!(indexer.size(1), 1286792);
+        assert_eq!(indexer.size(2), 55190538);
+        assert_eq!(indexer.size(3), 2428287420);
+    }
+
+    #[test]
+    fn test_preflop_classes() {
+        let indexer = HandIndexer::for_street(Street::Preflop);
+        let deck = card::deck();
+
+        let mut indexes = HashSet::new();
+        for i in 0..deck.len() {
+            for j in i + 1..deck.len() {
+                let index = indexer.index(&[&deck[i], &deck[j]]);
+                assert!(index < 169);
+                indexes.insert(index);
+            }
+        }
+        assert_eq!(indexes.len(), 169);
+    }
+
+    #[test]
+    fn test_isomorphic_hands() {
+        let indexer = HandIndexer::for_street(Street::Flop);
+        let hand1 = [Card::new('A', 'S'), Card::new('K', 'S'), Card::new('2', 'S'),
+                     Card::new('7', 'H'), Card::new('9', 'D')];
+        let hand2 = [Card::new('A', 'C'), Card::new('K', 'C'), Card::new('2', 'C'),
+                     Card::new('7', 'D'), Card::new('9', 'H')];
+        let hand3 = [Card::new('A', 'C'), Card::new('K', 'C'), Card::new('2', 'D'),
+                     Card::new('7', 'C'), Card::new('9', 'H')];
+
+        let index = |hand: &[Card; 5]| {
+            let cards: Vec<&Card> = hand.iter().collect();
+            indexer.index(&cards)
+        };
+        assert_eq!(index(&hand1), index(&hand2));
+        assert!(index(&hand1) != index(&hand3));
+
+        // The order of the cards inside a round does not matter
+        let swapped = [&hand1[1], &hand1[0], &hand1[4], &hand1[2], &hand1[3]];
+        assert_eq!(indexer.index(&swapped), index(&hand1));
+    }
+
+    #[test]
+    fn test_unindex_round_trip() {
+        let indexer = HandIndexer::new(&[2, 3, 1, 1]);
+        for round in 0..indexer.rounds() {
+            let size = indexer.size(round);
+            let step = size / 2000 + 1;
+            let mut index = 0;
+            while index < size {
+                let cards = indexer.unindex(round, index);
+                let refs: Vec<&Card> = cards.iter().collect();
+                assert_eq!(indexer.index(&refs), index);
+                index += step;
+            }
+            let cards = indexer.unindex(round, size - 1);
+            let refs: Vec<&Card> = cards.iter().collect();
+            assert_eq!(indexer.index(&refs), size - 1);
+        }
+    }
+
+    #[test]
+    fn test_canonical_situation() {
+        let hole = [Card::new('Q', 'H'), Card::new('J', 'H')];
+        let board = [Card::new('T', 'H'), Card::new('4', 'C'), Card::new('4', 'S')];
+
+        let index = super::index([&hole[0], &hole[1]], &[&board[0], &board[1], &board[2]]);
+        let (canonical_hole, canonical_board) = super::unindex(Street::Flop, index);
+        assert_eq!(canonical_hole.len(), 2);
+        assert_eq!(canonical_board.len(), 3);
+        assert!(canonical_hole[0].suit == canonical_hole[1].suit);
+        assert!(canonical_board.iter().filter(|card| card.suit == canonical_hole[0].suit).count() ==
+                1);
+        assert_eq!(super::index([&canonical_hole[0], &canonical_hole[1]],
+                                &[&canonical_board[0], &canonical_board[1], &canonical_board[2]]),
+                   index);
+    }
+}
@@ -0,0 +1,157 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// Internal imports
+use card::CardValue;
+use game::Game;
+
+/**
+ * Kuhn poker: a deck of a Jack, a Queen and a King, one card for every player, an ante of 1 and a
+ * single bet of 1. The value of the game for the first player is -1/18.
+ */
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Kuhn;
+
+/**
+ * KuhnAction is the deal of the cards or a decision of a player
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KuhnAction {
+    Deal([CardValue; 2]),
+    Pass, // Check or fold
+    Bet, // Bet or call
+}
+
+/**
+ * KuhnState is the cards of the players, once dealt, and the decisions taken
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct KuhnState {
+    pub cards: Option<[CardValue; 2]>,
+    pub history: Vec<KuhnAction>,
+}
+
+const CARDS: [CardValue; 3] = [CardValue::ValueJ, CardValue::ValueQ, CardValue::ValueK];
+
+impl Game for Kuhn {
+    type State = KuhnState;
+    type Action = KuhnAction;
+
+    fn root(&self) -> KuhnState {
+        KuhnState {
+            cards: None,
+            history: Vec::new(),
+        }
+    }
+
+    fn is_terminal(&self, state: &KuhnState) -> bool {
+        use self::KuhnAction::*;
+        matches!(state.history[..], [Pass, Pass] | [Bet, _] | [Pass, Bet, _])
+    }
+
+    fn payoff(&self, state: &KuhnState, player: usize) -> f64 {
+        use self::KuhnAction::*;
+        let cards = state.cards.unwrap();
+        let showdown = if cards[0] > cards[1] { 1.0 } else { -1.0 };
+
+        // Utility of the first player
+        let utility = match state.history[..] {
+            [Pass, Pass] => showdown,
+            [Bet, Pass] => 1.0,
+            [Pass, Bet, Pass] => -1.0,
+            _ => 2.0 * showdown,
+        };
+        if player == 0 { utility } else { -utility }
+    }
+
+    fn is_chance(&self, state: &KuhnState) -> bool {
+        state.cards.is_none()
+    }
+
+    fn chance_outcomes(&self, _: &KuhnState) -> Vec<(KuhnAction, f64)> {
+        let mut outcomes = Vec::new();
+        for first in CARDS.iter() {
+            for second in CARDS.iter().filter(|second| *second != first) {
+                outcomes.push((KuhnAction::Deal([*first, *second]), 1.0 / 6.0));
+            }
+        }
+        outcomes
+    }
+
+    fn player(&self, state: &KuhnState) -> usize {
+        state.history.len() % 2
+    }
+
+    fn actions(&self, _: &KuhnState) -> Vec<KuhnAction> {
+        vec![KuhnAction::Pass, KuhnAction::Bet]
+    }
+
+    fn apply(&self, state: &KuhnState, action: &KuhnAction) -> KuhnState {
+        let mut next = state.clone();
+        match *action {
+            KuhnAction::Deal(cards) => next.cards = Some(cards),
+            action => next.history.push(action),
+        }
+        next
+    }
+
+    /**
+     * Information sets are the card of the player and the decisions, like "Kpb"
+     */
+    fn information_set(&self, state: &KuhnState) -> String {
+        let card = state.cards.unwrap()[self.player(state)];
+        let history: String = state.history
+            .iter()
+            .map(|action| if *action == KuhnAction::Pass { 'p' } else { 'b' })
+            .collect();
+        format!("{}{}", card.symbol(), history)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Kuhn;
+    use super::KuhnAction;
+    use super::super::card::CardValue;
+    use super::super::game::Game;
+
+    #[test]
+    fn test_rules() {
+        let game = Kuhn;
+        let root = game.root();
+        assert!(game.is_chance(&root));
+        assert_eq!(game.chance_outcomes(&root).len(), 6);
+
+        let deal = KuhnAction::Deal([CardValue::ValueQ, CardValue::ValueK]);
+        let state = game.apply(&root, &deal);
+        let state = game.apply(&state, &KuhnAction::Pass);
+        let state = game.apply(&state, &KuhnAction::Bet);
+        assert_eq!(game.player(&state), 0);
+        assert_eq!(game.information_set(&state), "Qpb");
+        assert!(!game.is_terminal(&state));
+
+        let state = game.apply(&state, &KuhnAction::Bet);
+        assert!(game.is_terminal(&state));
+        assert_eq!(game.payoff(&state, 0), -2.0);
+        assert_eq!(game.payoff(&state, 1), 2.0);
+    }
+}
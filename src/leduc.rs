@@ -0,0 +1,263 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// Internal imports
+use card::Card;
+use game::Game;
+
+/**
+ * Leduc poker: a deck of two Jacks, two Queens and two Kings, one private card for every player
+ * and a public card after the first round. Players ante 1, bets are 2 on the first round and 4 on
+ * the second one and there are at most two bets on every round. A pair with the public card wins,
+ * otherwise the highest card. The value of the game for the first player is about -0.0856.
+ */
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Leduc;
+
+/**
+ * LeducAction is a deal or a decision of a player
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LeducAction {
+    DealHoles([Card; 2]),
+    DealBoard(Card),
+    Fold,
+    Call, // Check or call
+    Raise, // Bet or raise
+}
+
+/**
+ * LeducState is the cards dealt, the decisions of every round and the chips on the pot
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct LeducState {
+    pub holes: Option<[Card; 2]>,
+    pub board: Option<Card>,
+    pub rounds: [Vec<LeducAction>; 2],
+    pub round: usize,
+    pub contributions: [u32; 2],
+    pub folded: Option<usize>, // Player who folded
+}
+
+const ANTE: u32 = 1;
+const BETS: [u32; 2] = [2, 4];
+const MAX_RAISES: usize = 2;
+
+/**
+ * deck gets the six cards of the game
+ */
+pub fn deck() -> Vec<Card> {
+    "JS JH QS QH KS KH"
+        .split_whitespace()
+        .map(|card| Card::parse(card).unwrap())
+        .collect()
+}
+
+impl LeducState {
+    /**
+     * is_round_over tells if the actions of the current round are finished
+     */
+    fn is_round_over(&self) -> bool {
+        let actions = &self.rounds[self.round];
+        actions.len() >= 2 && actions[actions.len() - 1] == LeducAction::Call
+    }
+
+    /**
+     * raises counts the bets and raises of the current round
+     */
+    fn raises(&self) -> usize {
+        self.rounds[self.round].iter().filter(|action| **action == LeducAction::Raise).count()
+    }
+}
+
+impl Game for Leduc {
+    type State = LeducState;
+    type Action = LeducAction;
+
+    fn root(&self) -> LeducState {
+        LeducState {
+            holes: None,
+            board: None,
+            rounds: [Vec::new(), Vec::new()],
+            round: 0,
+            contributions: [ANTE; 2],
+            folded: None,
+        }
+    }
+
+    fn is_terminal(&self, state: &LeducState) -> bool {
+        state.folded.is_some() || (state.round == 1 && state.is_round_over())
+    }
+
+    fn payoff(&self, state: &LeducState, player: usize) -> f64 {
+        let opponent = 1 - player;
+        let winner = match state.folded {
+            Some(folded) => Some(1 - folded),
+            None => {
+                let holes = state.holes.unwrap();
+                let board = state.board.unwrap();
+                // A pair beats any card, otherwise the highest card wins
+                let strength = |card: &Card| (card.value == board.value, card.value);
+                match strength(&holes[player]).cmp(&strength(&holes[opponent])) {
+                    ::std::cmp::Ordering::Greater => Some(player),
+                    ::std::cmp::Ordering::Less => Some(opponent),
+                    ::std::cmp::Ordering::Equal => None,
+                }
+            }
+        };
+
+        match winner {
+            Some(winner) if winner == player => state.contributions[opponent] as f64,
+            Some(_) => -(state.contributions[player] as f64),
+            None => 0.0,
+        }
+    }
+
+    fn is_chance(&self, state: &LeducState) -> bool {
+        state.holes.is_none() || (state.round == 1 && state.board.is_none())
+    }
+
+    fn chance_outcomes(&self, state: &LeducState) -> Vec<(LeducAction, f64)> {
+        let deck = deck();
+        match state.holes {
+            None => {
+                let mut outcomes = Vec::new();
+                for first in deck.iter() {
+                    for second in deck.iter().filter(|second| *second != first) {
+                        outcomes.push((LeducAction::DealHoles([*first, *second]), 1.0 / 30.0));
+                    }
+                }
+                outcomes
+            }
+            Some(holes) => {
+                deck.iter()
+                    .filter(|card| !holes.contains(card))
+                    .map(|card| (LeducAction::DealBoard(*card), 1.0 / 4.0))
+                    .collect()
+            }
+        }
+    }
+
+    fn player(&self, state: &LeducState) -> usize {
+        state.rounds[state.round].len() % 2
+    }
+
+    fn actions(&self, state: &LeducState) -> Vec<LeducAction> {
+        let facing_bet = state.contributions[0] != state.contributions[1];
+        let mut actions = Vec::new();
+        if facing_bet {
+            actions.push(LeducAction::Fold);
+        }
+        actions.push(LeducAction::Call);
+        if state.raises() < MAX_RAISES {
+            actions.push(LeducAction::Raise);
+        }
+        actions
+    }
+
+    fn apply(&self, state: &LeducState, action: &LeducAction) -> LeducState {
+        let mut next = state.clone();
+        let player = self.player(state);
+        let highest = *state.contributions.iter().max().unwrap();
+
+        match *action {
+            LeducAction::DealHoles(holes) => next.holes = Some(holes),
+            LeducAction::DealBoard(card) => next.board = Some(card),
+            LeducAction::Fold => next.folded = Some(player),
+            LeducAction::Call => next.contributions[player] = highest,
+            LeducAction::Raise => next.contributions[player] = highest + BETS[state.round],
+        }
+
+        match *action {
+            LeducAction::DealHoles(_) |
+            LeducAction::DealBoard(_) => (),
+            _ => {
+                next.rounds[state.round].push(*action);
+                if next.round == 0 && next.is_round_over() {
+                    next.round = 1;
+                }
+            }
+        }
+        next
+    }
+
+    /**
+     * Information sets are the value of the private card, the value of the public card and the
+     * decisions of every round, like "KQ:rc/r". Suits do not matter in Leduc poker.
+     */
+    fn information_set(&self, state: &LeducState) -> String {
+        let hole = state.holes.unwrap()[self.player(state)];
+        let board = state.board.map(|card| card.value.symbol()).unwrap_or('-');
+        let history = |actions: &Vec<LeducAction>| -> String {
+            actions.iter()
+                .map(|action| match *action {
+                    LeducAction::Fold => 'f',
+                    LeducAction::Call => 'c',
+                    _ => 'r',
+                })
+                .collect()
+        };
+        format!("{}{}:{}/{}",
+                hole.value.symbol(),
+                board,
+                history(&state.rounds[0]),
+                history(&state.rounds[1]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Leduc;
+    use super::LeducAction;
+    use super::super::card::Card;
+    use super::super::game::Game;
+
+    #[test]
+    fn test_rules() {
+        let game = Leduc;
+        let root = game.root();
+        assert_eq!(game.chance_outcomes(&root).len(), 30);
+
+        let holes = [Card::new('K', 'S'), Card::new('Q', 'H')];
+        let mut state = game.apply(&root, &LeducAction::DealHoles(holes));
+        for action in [LeducAction::Raise, LeducAction::Raise].iter() {
+            state = game.apply(&state, action);
+        }
+        assert_eq!(state.contributions, [3, 5]);
+        assert_eq!(game.actions(&state), vec![LeducAction::Fold, LeducAction::Call]);
+
+        state = game.apply(&state, &LeducAction::Call);
+        assert!(game.is_chance(&state));
+        assert_eq!(game.chance_outcomes(&state).len(), 4);
+        state = game.apply(&state, &LeducAction::DealBoard(Card::new('Q', 'S')));
+        assert_eq!(game.information_set(&state), "KQ:rrc/");
+        assert_eq!(game.actions(&state), vec![LeducAction::Call, LeducAction::Raise]);
+
+        // The pair of Queens wins
+        state = game.apply(&state, &LeducAction::Raise);
+        state = game.apply(&state, &LeducAction::Call);
+        assert!(game.is_terminal(&state));
+        assert_eq!(state.contributions, [9, 9]);
+        assert_eq!(game.payoff(&state, 0), -9.0);
+        assert_eq!(game.payoff(&state, 1), 9.0);
+    }
+}
@@ -20,12 +20,53 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "serde")]
+extern crate serde_json;
+
 use std::string::String;
 
-mod consts; // Constants
-mod types;  // Types definition
-mod card;   // Card struct with its tests
-mod hand;   // Hand struct with its tests
+pub mod consts;      // Constants
+pub mod types;       // Types definition
+pub mod card;        // Card struct with its tests
+pub mod hand;        // Hand struct with its tests
+pub mod isomorphism; // Suit isomorphic indexing of hands
+pub mod rng;         // Seeded pseudorandom number generator
+pub mod range;       // Weighted ranges of hole cards
+pub mod strength;    // Hand strength and potential metrics
+pub mod outs;        // Outs counter and draw classifier
+pub mod board;       // Board texture analysis
+pub mod description; // Human readable descriptions of hands
+pub mod batch;       // Batch evaluation of deals
+pub mod calculator;  // Interactive poker calculator
+pub mod history;     // Hand histories recorded by poker sites
+pub mod pokerstars;  // PokerStars hand history parser
+pub mod replay;      // Replayer that verifies recorded hands
+pub mod stats;       // Player statistics from hand histories
+pub mod encoding;    // Compact binary encoding of deals
+pub mod render;      // Unicode and ANSI coloured rendering of cards
+pub mod game;        // Extensive form games
+pub mod cfr;         // Counterfactual regret minimization
+pub mod kuhn;        // Kuhn poker
+pub mod leduc;       // Leduc poker
+pub mod preflop;     // Starting hand classes and their equities
+pub mod pushfold;    // Heads-up push/fold equilibrium
+pub mod icm;         // Independent Chip Model
+pub mod river;       // Single street river solver
+pub mod abstraction; // Card abstraction by hand strength buckets
+pub mod bot;         // Bot interface
+pub mod engine;      // Heads-up No Limit Hold'em dealer
+pub mod arena;       // Self-play arena for bots
+pub mod protocol;    // Line based protocol to play with remote bots
+pub mod baseline;    // Baseline bots to benchmark against
+pub mod opponent;    // Opponent modelling by Bayesian range narrowing
+pub mod combinatorics; // Combinations of cards without the dead ones
+pub mod cli;         // Helpers of the command line binaries
+#[cfg(feature = "serde")]
+pub mod ohh;         // Open Hand History JSON format
+#[cfg(feature = "serde")]
+pub mod serialization; // Serde support for cards and hands
 
 /**
  * play Initializes a game with 2 players with 2 cards each one and 5 community cards. It will
@@ -45,25 +86,25 @@ pub fn play(player1: String, player2: String, table: String) -> String {
     let table_str_cards: Vec<&str> = table.split_whitespace().collect();
 
     // Create instances of cards cards
-    let player1_card1 = card::Card::new(player1_str_cards[0].chars().nth(0).unwrap(),
+    let player1_card1 = card::Card::new(player1_str_cards[0].chars().next().unwrap(),
                                         player1_str_cards[0].chars().nth(1).unwrap());
-    let player1_card2 = card::Card::new(player1_str_cards[1].chars().nth(0).unwrap(),
+    let player1_card2 = card::Card::new(player1_str_cards[1].chars().next().unwrap(),
                                         player1_str_cards[1].chars().nth(1).unwrap());
 
-    let player2_card1 = card::Card::new(player2_str_cards[0].chars().nth(0).unwrap(),
+    let player2_card1 = card::Card::new(player2_str_cards[0].chars().next().unwrap(),
                                         player2_str_cards[0].chars().nth(1).unwrap());
-    let player2_card2 = card::Card::new(player2_str_cards[1].chars().nth(0).unwrap(),
+    let player2_card2 = card::Card::new(player2_str_cards[1].chars().next().unwrap(),
                                         player2_str_cards[1].chars().nth(1).unwrap());
 
-    let table_card1 = card::Card::new(table_str_cards[0].chars().nth(0).unwrap(),
+    let table_card1 = card::Card::new(table_str_cards[0].chars().next().unwrap(),
                                       table_str_cards[0].chars().nth(1).unwrap());
-    let table_card2 = card::Card::new(table_str_cards[1].chars().nth(0).unwrap(),
+    let table_card2 = card::Card::new(table_str_cards[1].chars().next().unwrap(),
                                       table_str_cards[1].chars().nth(1).unwrap());
-    let table_card3 = card::Card::new(table_str_cards[2].chars().nth(0).unwrap(),
+    let table_card3 = card::Card::new(table_str_cards[2].chars().next().unwrap(),
                                       table_str_cards[2].chars().nth(1).unwrap());
-    let table_card4 = card::Card::new(table_str_cards[3].chars().nth(0).unwrap(),
+    let table_card4 = card::Card::new(table_str_cards[3].chars().next().unwrap(),
                                       table_str_cards[3].chars().nth(1).unwrap());
-    let table_card5 = card::Card::new(table_str_cards[4].chars().nth(0).unwrap(),
+    let table_card5 = card::Card::new(table_str_cards[4].chars().next().unwrap(),
                                       table_str_cards[4].chars().nth(1).unwrap());
 
     // Create two collections of cards for the two players and another one for the table
@@ -81,12 +122,12 @@ pub fn play(player1: String, player2: String, table: String) -> String {
     if hand1 > hand2 {
         result.push_str("1 ");
         result.push_str(hand1.get_hand_type());
-        result.push_str(" ");
+        result.push(' ');
         result.push_str(&hand1.to_string());
-    } else if hand1 > hand2 {
+    } else if hand2 > hand1 {
         result.push_str("2 ");
         result.push_str(hand2.get_hand_type());
-        result.push_str(" ");
+        result.push(' ');
         result.push_str(&hand2.to_string());
     } else {
         result.push_str("DRAW");
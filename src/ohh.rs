@@ -0,0 +1,799 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// External imports
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json;
+
+// Internal imports
+use types::Street;
+use card::Card;
+use history;
+use history::ActionKind;
+use history::Amount;
+use history::HandHistory;
+use history::Seat;
+
+/**
+ * Version of the Open Hand History specification written by this module
+ */
+pub const SPEC_VERSION: &str = "1.4.6";
+
+/**
+ * OpenHandHistory is a hand on the Open Hand History format, a JSON standard to exchange hands
+ * between poker tools. Amounts are decimal numbers, like 0.02 for two cents or 1500 for 1500
+ * tournament chips.
+ */
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct OpenHandHistory {
+    pub spec_version: String,
+    pub site_name: String,
+    #[serde(default)]
+    pub network_name: String,
+    #[serde(default)]
+    pub internal_version: String,
+    pub tournament: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tournament_info: Option<TournamentInfo>,
+    pub game_number: String,
+    pub start_date_utc: String,
+    pub table_name: String,
+    #[serde(default)]
+    pub table_handle: String,
+    pub game_type: String, // "Holdem" for Texas Hold'em
+    pub bet_limit: BetLimit,
+    pub table_size: u32,
+    #[serde(default)]
+    pub currency: String,
+    pub dealer_seat: u32,
+    pub small_blind_amount: f64,
+    pub big_blind_amount: f64,
+    #[serde(default)]
+    pub ante_amount: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hero_player_id: Option<u32>,
+    #[serde(default)]
+    pub flags: Vec<String>,
+    pub players: Vec<Player>,
+    pub rounds: Vec<Round>,
+    pub pots: Vec<Pot>,
+}
+
+/**
+ * TournamentInfo describes the tournament of a hand
+ */
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TournamentInfo {
+    pub tournament_number: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub start_date_utc: String,
+    #[serde(default)]
+    pub currency: String,
+    #[serde(default)]
+    pub buyin_amount: f64,
+    #[serde(default)]
+    pub fee_amount: f64,
+    #[serde(default)]
+    pub bounty_fee_amount: f64,
+    #[serde(default)]
+    pub initial_stack: f64,
+    #[serde(rename = "type", default)]
+    pub tournament_type: String, // "STT" or "MTT"
+}
+
+/**
+ * BetLimit is the betting structure of the game
+ */
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BetLimit {
+    pub bet_type: String, // "NL", "PL" or "FL"
+    #[serde(default)]
+    pub bet_cap: f64,
+}
+
+/**
+ * Player is a player sitting at the table when the hand starts
+ */
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Player {
+    pub id: u32,
+    pub seat: u32,
+    pub name: String,
+    #[serde(default)]
+    pub display: String,
+    pub starting_stack: f64,
+    #[serde(default)]
+    pub player_bounty: f64,
+    #[serde(default)]
+    pub is_sitting_out: bool,
+}
+
+/**
+ * Round is a betting round, or the showdown, with the community cards dealt on it
+ */
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Round {
+    pub id: u32,
+    pub street: RoundStreet,
+    #[serde(default)]
+    pub cards: Vec<Card>,
+    pub actions: Vec<Action>,
+}
+
+/**
+ * RoundStreet names the rounds of a hand
+ */
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum RoundStreet {
+    Preflop,
+    Flop,
+    Turn,
+    River,
+    Showdown,
+}
+
+/**
+ * Action is anything done by a player during a round. The amount is the number of chips put on
+ * the pot by the action.
+ */
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Action {
+    pub action_number: u32,
+    pub player_id: u32,
+    pub action: ActionType,
+    #[serde(default)]
+    pub amount: f64,
+    #[serde(default)]
+    pub is_allin: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cards: Vec<Card>,
+}
+
+/**
+ * ActionType is any of the actions of the specification
+ */
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ActionType {
+    #[serde(rename = "Dealt Cards")]
+    DealtCards,
+    #[serde(rename = "Mucks Cards")]
+    MucksCards,
+    #[serde(rename = "Shows Cards")]
+    ShowsCards,
+    #[serde(rename = "Post Ante")]
+    PostAnte,
+    #[serde(rename = "Post SB")]
+    PostSmallBlind,
+    #[serde(rename = "Post BB")]
+    PostBigBlind,
+    Straddle,
+    #[serde(rename = "Post Dead")]
+    PostDead,
+    #[serde(rename = "Post Extra Blind")]
+    PostExtraBlind,
+    Fold,
+    Check,
+    Bet,
+    Raise,
+    Call,
+    #[serde(rename = "Added Chips")]
+    AddedChips,
+    #[serde(rename = "Sits Down")]
+    SitsDown,
+    #[serde(rename = "Stands Up")]
+    StandsUp,
+    #[serde(rename = "Added To Pot")]
+    AddedToPot,
+}
+
+/**
+ * Pot is a pot and the players who won it
+ */
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Pot {
+    pub number: u32,
+    pub amount: f64,
+    #[serde(default)]
+    pub rake: f64,
+    #[serde(default)]
+    pub jackpot: f64,
+    pub player_wins: Vec<PlayerWin>,
+}
+
+/**
+ * PlayerWin is the part of a pot won by a player
+ */
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PlayerWin {
+    pub player_id: u32,
+    pub win_amount: f64,
+    #[serde(default)]
+    pub contributed_rake: f64,
+}
+
+/**
+ * Every hand of a file is wrapped on an object with a single "ohh" field
+ */
+#[derive(Serialize, Deserialize)]
+struct Wrapper {
+    ohh: OpenHandHistory,
+}
+
+/**
+ * read reads a single hand
+ */
+pub fn read(json: &str) -> Result<OpenHandHistory, String> {
+    let mut hands = read_all(json)?;
+    match hands.len() {
+        1 => Ok(hands.remove(0)),
+        count => Err(format!("expected a single hand, found {}", count)),
+    }
+}
+
+/**
+ * read_all reads every hand of a file, where hands are consecutive JSON objects
+ */
+pub fn read_all(json: &str) -> Result<Vec<OpenHandHistory>, String> {
+    serde_json::Deserializer::from_str(json)
+        .into_iter::<Wrapper>()
+        .map(|wrapper| wrapper.map(|wrapper| wrapper.ohh).map_err(|error| error.to_string()))
+        .collect()
+}
+
+/**
+ * write writes a hand as an indented JSON object
+ */
+pub fn write(hand: &OpenHandHistory) -> String {
+    let wrapper = Wrapper { ohh: hand.clone() };
+    serde_json::to_string_pretty(&wrapper).unwrap()
+}
+
+/**
+ * write_all writes a file of hands, separated by blank lines
+ */
+pub fn write_all(hands: &[OpenHandHistory]) -> String {
+    hands.iter().map(|hand| format!("{}\n\n", write(hand))).collect()
+}
+
+impl OpenHandHistory {
+    /**
+     * from_history converts a hand recorded by a site, or played by the crate, to the Open Hand
+     * History format. Player ids are the seat numbers.
+     */
+    pub fn from_history(hand: &HandHistory) -> OpenHandHistory {
+        let player_id = |player: &str| hand.seat(player).map(|seat| seat.number).unwrap_or(0);
+
+        let mut rounds = vec![Round {
+                                  id: 0,
+                                  street: RoundStreet::Preflop,
+                                  cards: Vec::new(),
+                                  actions: Vec::new(),
+                              }];
+        let mut showdown = Vec::new();
+        let mut number = 0;
+
+        if let (Some(hero), Some(cards)) = (hand.hero.as_ref(), hand.hero_cards) {
+            number += 1;
+            rounds[0].actions.push(action(number, player_id(hero), ActionType::DealtCards, 0, false,
+                                          cards.to_vec()));
+        }
+
+        let streets = [(Street::Flop, RoundStreet::Flop, 0..3),
+                       (Street::Turn, RoundStreet::Turn, 3..4),
+                       (Street::River, RoundStreet::River, 4..5)];
+        for &(_, street, ref cards) in streets.iter() {
+            if hand.board.len() >= cards.end {
+                rounds.push(Round {
+                    id: rounds.len() as u32,
+                    street,
+                    cards: hand.board[cards.clone()].to_vec(),
+                    actions: Vec::new(),
+                });
+            }
+        }
+
+        // Chips put by every player on the current street, to know the chips added by the raises
+        let mut street = Street::Preflop;
+        let mut committed: Vec<(u32, Amount)> = Vec::new();
+
+        for recorded in &hand.actions {
+            let id = player_id(&recorded.player);
+            if recorded.street != street {
+                street = recorded.street;
+                committed.clear();
+            }
+
+            let (action_type, amount, cards) = match recorded.kind {
+                ActionKind::Ante(amount) => (ActionType::PostAnte, amount, Vec::new()),
+                ActionKind::SmallBlind(amount) => (ActionType::PostSmallBlind, amount, Vec::new()),
+                ActionKind::BigBlind(amount) => (ActionType::PostBigBlind, amount, Vec::new()),
+                ActionKind::SmallAndBigBlind(amount) => (ActionType::PostDead, amount, Vec::new()),
+                ActionKind::Fold => (ActionType::Fold, 0, Vec::new()),
+                ActionKind::Check => (ActionType::Check, 0, Vec::new()),
+                ActionKind::Call(amount) => (ActionType::Call, amount, Vec::new()),
+                ActionKind::Bet(amount) => (ActionType::Bet, amount, Vec::new()),
+                ActionKind::Raise { to, .. } => {
+                    (ActionType::Raise, to - committed_by(&committed, id), Vec::new())
+                }
+                ActionKind::Show(cards) => (ActionType::ShowsCards, 0, cards.to_vec()),
+                ActionKind::Muck => (ActionType::MucksCards, 0, Vec::new()),
+                // Returned bets and collected pots are written on the pots
+                ActionKind::UncalledBet(_) |
+                ActionKind::Collect(_) => continue,
+            };
+
+            if action_type != ActionType::PostAnte {
+                commit(&mut committed, id, amount);
+            }
+
+            number += 1;
+            let action = action(number, id, action_type, amount, recorded.all_in, cards);
+            match action_type {
+                ActionType::ShowsCards |
+                ActionType::MucksCards => showdown.push(action),
+                _ => {
+                    let street = match recorded.street {
+                        Street::Preflop => RoundStreet::Preflop,
+                        Street::Flop => RoundStreet::Flop,
+                        Street::Turn => RoundStreet::Turn,
+                        Street::River => RoundStreet::River,
+                    };
+                    match rounds.iter_mut().find(|round| round.street == street) {
+                        Some(round) => round.actions.push(action),
+                        None => rounds[0].actions.push(action),
+                    }
+                }
+            }
+        }
+
+        if !showdown.is_empty() {
+            rounds.push(Round {
+                id: rounds.len() as u32,
+                street: RoundStreet::Showdown,
+                cards: Vec::new(),
+                actions: showdown,
+            });
+        }
+
+        // Every collected amount is part of a single pot
+        let mut player_wins: Vec<PlayerWin> = Vec::new();
+        for recorded in &hand.actions {
+            if let ActionKind::Collect(amount) = recorded.kind {
+                let id = player_id(&recorded.player);
+                match player_wins.iter_mut().find(|win| win.player_id == id) {
+                    Some(win) => win.win_amount += decimal(amount),
+                    None => {
+                        player_wins.push(PlayerWin {
+                            player_id: id,
+                            win_amount: decimal(amount),
+                            contributed_rake: 0.0,
+                        })
+                    }
+                }
+            }
+        }
+
+        let ante = hand.actions
+            .iter()
+            .filter_map(|action| match action.kind {
+                ActionKind::Ante(amount) => Some(amount),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0);
+
+        OpenHandHistory {
+            spec_version: SPEC_VERSION.to_string(),
+            site_name: hand.site.clone(),
+            network_name: hand.site.clone(),
+            internal_version: env!("CARGO_PKG_VERSION").to_string(),
+            tournament: hand.tournament.is_some(),
+            tournament_info: hand.tournament.as_ref().map(|tournament| {
+                let mut words = tournament.buy_in.split_whitespace();
+                let fees: Vec<Amount> = words.next()
+                    .unwrap_or("")
+                    .split('+')
+                    .filter_map(history::parse_amount)
+                    .collect();
+                TournamentInfo {
+                    tournament_number: tournament.id.to_string(),
+                    name: String::new(),
+                    start_date_utc: String::new(),
+                    currency: words.next().unwrap_or("").to_string(),
+                    buyin_amount: decimal(fees.first().cloned().unwrap_or(0)),
+                    fee_amount: decimal(fees.get(1).cloned().unwrap_or(0)),
+                    bounty_fee_amount: 0.0,
+                    initial_stack: 0.0,
+                    tournament_type: String::new(),
+                }
+            }),
+            game_number: hand.id.to_string(),
+            start_date_utc: utc_date(&hand.date),
+            table_name: hand.table.clone(),
+            table_handle: String::new(),
+            game_type: String::from("Holdem"),
+            bet_limit: BetLimit {
+                bet_type: match hand.limit.as_str() {
+                        "No Limit" => "NL",
+                        "Pot Limit" => "PL",
+                        "Limit" | "Fixed Limit" => "FL",
+                        other => other,
+                    }
+                    .to_string(),
+                bet_cap: 0.0,
+            },
+            table_size: hand.max_seats,
+            currency: hand.currency.clone().unwrap_or_default(),
+            dealer_seat: hand.button,
+            small_blind_amount: decimal(hand.small_blind),
+            big_blind_amount: decimal(hand.big_blind),
+            ante_amount: decimal(ante),
+            hero_player_id: hand.hero.as_ref().map(|hero| player_id(hero)),
+            flags: Vec::new(),
+            players: hand.seats
+                .iter()
+                .map(|seat| {
+                    Player {
+                        id: seat.number,
+                        seat: seat.number,
+                        name: seat.player.clone(),
+                        display: seat.player.clone(),
+                        starting_stack: decimal(seat.stack),
+                        player_bounty: 0.0,
+                        is_sitting_out: seat.sitting_out,
+                    }
+                })
+                .collect(),
+            rounds,
+            pots: vec![Pot {
+                           number: 0,
+                           amount: decimal(hand.total_pot),
+                           rake: decimal(hand.rake),
+                           jackpot: 0.0,
+                           player_wins,
+                       }],
+        }
+    }
+
+    /**
+     * to_history converts the hand to the representation of the crate. Returned uncalled bets are
+     * not part of the format, so they are not restored.
+     */
+    pub fn to_history(&self) -> Result<HandHistory, String> {
+        let player = |id: u32| -> Result<String, String> {
+            self.players
+                .iter()
+                .find(|player| player.id == id)
+                .map(|player| player.name.clone())
+                .ok_or_else(|| format!("unknown player id {}", id))
+        };
+
+        let mut board = Vec::new();
+        for round in &self.rounds {
+            board.extend_from_slice(&round.cards);
+        }
+        let last_street = Street::from_board_size(board.len())
+            .ok_or_else(|| format!("invalid board of {} cards", board.len()))?;
+
+        let mut hero_cards = None;
+        let mut actions = Vec::new();
+        for round in &self.rounds {
+            let street = match round.street {
+                RoundStreet::Preflop => Street::Preflop,
+                RoundStreet::Flop => Street::Flop,
+                RoundStreet::Turn => Street::Turn,
+                RoundStreet::River => Street::River,
+                RoundStreet::Showdown => last_street,
+            };
+
+            // Chips put by every player on the street, to know the totals of the raises
+            let mut committed: Vec<(u32, Amount)> = Vec::new();
+            for action in &round.actions {
+                let amount = amount(action.amount);
+                let kind = match action.action {
+                    ActionType::DealtCards => {
+                        let hero = Some(action.player_id) == self.hero_player_id;
+                        if hero && action.cards.len() == 2 {
+                            hero_cards = Some([action.cards[0], action.cards[1]]);
+                        }
+                        continue;
+                    }
+                    ActionType::PostAnte => ActionKind::Ante(amount),
+                    ActionType::PostSmallBlind => ActionKind::SmallBlind(amount),
+                    ActionType::PostBigBlind |
+                    ActionType::Straddle |
+                    ActionType::PostExtraBlind => ActionKind::BigBlind(amount),
+                    ActionType::PostDead => ActionKind::SmallAndBigBlind(amount),
+                    ActionType::Fold => ActionKind::Fold,
+                    ActionType::Check => ActionKind::Check,
+                    ActionType::Bet => ActionKind::Bet(amount),
+                    ActionType::Call => ActionKind::Call(amount),
+                    ActionType::Raise => {
+                        let to = committed_by(&committed, action.player_id) + amount;
+                        let previous = committed.iter().map(|&(_, chips)| chips).max().unwrap_or(0);
+                        ActionKind::Raise {
+                            amount: to - previous,
+                            to,
+                        }
+                    }
+                    ActionType::ShowsCards if action.cards.len() == 2 => {
+                        ActionKind::Show([action.cards[0], action.cards[1]])
+                    }
+                    ActionType::ShowsCards => {
+                        return Err(format!("action {} shows {} cards",
+                                           action.action_number,
+                                           action.cards.len()))
+                    }
+                    ActionType::MucksCards => ActionKind::Muck,
+                    ActionType::AddedChips |
+                    ActionType::SitsDown |
+                    ActionType::StandsUp |
+                    ActionType::AddedToPot => continue,
+                };
+
+                if action.action != ActionType::PostAnte {
+                    commit(&mut committed, action.player_id, amount);
+                }
+
+                actions.push(history::Action {
+                    street,
+                    player: player(action.player_id)?,
+                    kind,
+                    all_in: action.is_allin,
+                });
+            }
+        }
+
+        for pot in &self.pots {
+            for win in &pot.player_wins {
+                actions.push(history::Action {
+                    street: last_street,
+                    player: player(win.player_id)?,
+                    kind: ActionKind::Collect(amount(win.win_amount)),
+                    all_in: false,
+                });
+            }
+        }
+
+        let mut seats = Vec::new();
+        for player in &self.players {
+            seats.push(Seat {
+                number: player.seat,
+                player: player.name.clone(),
+                stack: amount(player.starting_stack),
+                sitting_out: player.is_sitting_out,
+            });
+        }
+
+        let tournament = match self.tournament_info {
+            Some(ref info) => {
+                Some(history::Tournament {
+                    id: info.tournament_number
+                        .parse()
+                        .map_err(|_| {
+                            format!("invalid tournament number '{}'", info.tournament_number)
+                        })?,
+                    buy_in: format!("{}+{} {}",
+                                    history::format_amount(amount(info.buyin_amount)),
+                                    history::format_amount(amount(info.fee_amount)),
+                                    info.currency)
+                        .trim()
+                        .to_string(),
+                    level: String::new(),
+                })
+            }
+            None => None,
+        };
+
+        Ok(HandHistory {
+            site: self.site_name.clone(),
+            id: self.game_number
+                .parse()
+                .map_err(|_| format!("invalid game number '{}'", self.game_number))?,
+            tournament,
+            currency: if self.currency.is_empty() { None } else { Some(self.currency.clone()) },
+            limit: match self.bet_limit.bet_type.as_str() {
+                    "NL" => "No Limit",
+                    "PL" => "Pot Limit",
+                    "FL" => "Limit",
+                    other => other,
+                }
+                .to_string(),
+            small_blind: amount(self.small_blind_amount),
+            big_blind: amount(self.big_blind_amount),
+            date: self.start_date_utc.clone(),
+            table: self.table_name.clone(),
+            max_seats: self.table_size,
+            button: self.dealer_seat,
+            seats,
+            hero: match self.hero_player_id {
+                Some(id) => Some(player(id)?),
+                None => None,
+            },
+            hero_cards,
+            actions,
+            board,
+            total_pot: self.pots.iter().map(|pot| amount(pot.amount)).sum(),
+            rake: self.pots.iter().map(|pot| amount(pot.rake)).sum(),
+        })
+    }
+}
+
+/**
+ * action builds an action of a round
+ */
+fn action(number: u32,
+          player_id: u32,
+          action: ActionType,
+          amount: Amount,
+          is_allin: bool,
+          cards: Vec<Card>)
+          -> Action {
+    Action {
+        action_number: number,
+        player_id,
+        action,
+        amount: decimal(amount),
+        is_allin,
+        cards,
+    }
+}
+
+/**
+ * committed_by gets the chips put by a player on the street
+ */
+fn committed_by(committed: &[(u32, Amount)], player_id: u32) -> Amount {
+    committed.iter()
+        .find(|&&(id, _)| id == player_id)
+        .map(|&(_, chips)| chips)
+        .unwrap_or(0)
+}
+
+/**
+ * commit adds chips put by a player on the street
+ */
+fn commit(committed: &mut Vec<(u32, Amount)>, player_id: u32, amount: Amount) {
+    match committed.iter_mut().find(|&&mut (id, _)| id == player_id) {
+        Some(&mut (_, ref mut chips)) => *chips += amount,
+        None => committed.push((player_id, amount)),
+    }
+}
+
+/**
+ * decimal converts an amount in hundredths to a decimal number
+ */
+fn decimal(amount: Amount) -> f64 {
+    amount as f64 / 100.0
+}
+
+/**
+ * amount converts a decimal number to an amount in hundredths
+ */
+fn amount(decimal: f64) -> Amount {
+    (decimal * 100.0).round() as Amount
+}
+
+/**
+ * utc_date converts a date like "2020/03/01 14:00:00 ET" to the ISO 8601 format in UTC, like
+ * "2020-03-01T19:00:00Z". Dates with an unknown time zone are written without it.
+ */
+fn utc_date(date: &str) -> String {
+    let numbers: Vec<i64> = date.split(|c: char| !c.is_ascii_digit())
+        .filter(|number| !number.is_empty())
+        .take(6)
+        .filter_map(|number| number.parse().ok())
+        .collect();
+    if numbers.len() != 6 {
+        return date.to_string();
+    }
+
+    let local = days_from_civil(numbers[0], numbers[1], numbers[2]) * 86400 + numbers[3] * 3600 +
+                numbers[4] * 60 + numbers[5];
+    let zone = date.split_whitespace().last().unwrap_or("");
+    let offset = match zone {
+        "UTC" | "GMT" => 0,
+        "ET" if is_us_daylight_time(numbers[0], local) => -4,
+        "ET" => -5,
+        _ => {
+            let (year, month, day) = civil_from_days(local.div_euclid(86400));
+            return format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+                           year,
+                           month,
+                           day,
+                           numbers[3],
+                           numbers[4],
+                           numbers[5]);
+        }
+    };
+
+    let utc = local - offset * 3600;
+    let (year, month, day) = civil_from_days(utc.div_euclid(86400));
+    let seconds = utc.rem_euclid(86400);
+    format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            year,
+            month,
+            day,
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60)
+}
+
+/**
+ * is_us_daylight_time tells if a local time, in seconds since 1970, is between the second Sunday
+ * of March and the first Sunday of November at 2:00
+ */
+fn is_us_daylight_time(year: i64, local: i64) -> bool {
+    let sunday = |month: i64, nth: i64| {
+        let first = days_from_civil(year, month, 1);
+        let weekday = (first + 4).rem_euclid(7); // 1970-01-01 was a Thursday
+        first + (7 - weekday) % 7 + 7 * (nth - 1)
+    };
+    let start = sunday(3, 2) * 86400 + 2 * 3600;
+    let end = sunday(11, 1) * 86400 + 2 * 3600;
+    local >= start && local < end
+}
+
+/**
+ * days_from_civil gets the days since 1970-01-01 of a date of the Gregorian calendar
+ */
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/**
+ * civil_from_days gets the date of the Gregorian calendar of a number of days since 1970-01-01
+ */
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 -
+                       day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400;
+    (if month <= 2 { year + 1 } else { year }, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::utc_date;
+
+    #[test]
+    fn test_utc_date() {
+        assert_eq!(utc_date("2020/03/01 14:00:00 ET"), "2020-03-01T19:00:00Z");
+        assert_eq!(utc_date("2020/07/01 22:30:00 ET"), "2020-07-02T02:30:00Z");
+        assert_eq!(utc_date("2020/12/31 23:59:59 UTC"), "2020-12-31T23:59:59Z");
+        assert_eq!(utc_date("2020/03/01 14:00:00 CET"), "2020-03-01T14:00:00");
+        assert_eq!(utc_date("yesterday"), "yesterday");
+    }
+}
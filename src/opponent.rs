@@ -0,0 +1,438 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// Internal imports
+use board;
+use board::Texture;
+use bot::{Action, Chips, Observation};
+use card::Card;
+use card::CardValue;
+use consts;
+use hand::Hand;
+use outs;
+use outs::Draw;
+use preflop;
+use preflop::HandClass;
+use range::{Combo, Range};
+use strength;
+use types;
+use types::Street;
+
+const SAMPLES: usize = 2000; // Samples of the equity before the turn
+const SEED: u64 = 1;
+const FLOOR: f64 = 0.01; // Lowest likelihood of an action, so no combo is ever ruled out
+const WIDTH: f64 = 0.08; // How smoothly the likelihoods change with the strength
+const DRAW_STRENGTH: f64 = 0.65; // Strength a strong draw is played as
+
+/**
+ * Spot is the situation of a player when it acts
+ */
+#[derive(Clone, Debug)]
+pub struct Spot {
+    pub street: Street,
+    pub board: Vec<Card>,
+    pub texture: Option<Texture>, // Texture of the board, after the flop
+    pub pot: Chips, // Chips on the pot, the bets of the street included
+    pub to_call: Chips,
+    pub raises: usize, // Raises of the street before the action, the first bet included
+}
+
+/**
+ * Holding is what a player knows about the strength of its hole cards on a spot
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Holding {
+    pub strength: f64, // Share of the hole cards it beats, from 0 to 1
+    pub draw: bool, // A flush draw or an open ended straight draw
+}
+
+/**
+ * ActionModel tells how likely a player is to take an action on a spot with some holding
+ */
+pub trait ActionModel {
+    /**
+     * likelihood gets the probability of the action, given the spot and the holding
+     */
+    fn likelihood(&self, spot: &Spot, holding: &Holding, action: &Action) -> f64;
+}
+
+/**
+ * Tendencies model a player that bets and raises its strongest hands and calls while its hand is
+ * good enough for the price, bluffing some of its weakest hands. Strong draws are played as
+ * decent made hands, and bluffs are more frequent on boards with draws.
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tendencies {
+    pub aggression: f64, // Share of the hands bet when nobody has bet yet
+    pub looseness: f64, // How much weaker are the hands that call a bet
+    pub bluff: f64, // Probability of betting or raising with any hand, on a dry board
+}
+
+impl Default for Tendencies {
+    fn default() -> Tendencies {
+        Tendencies {
+            aggression: 0.3,
+            looseness: 0.0,
+            bluff: 0.1,
+        }
+    }
+}
+
+impl ActionModel for Tendencies {
+    fn likelihood(&self, spot: &Spot, holding: &Holding, action: &Action) -> f64 {
+        let strength = if holding.draw {
+            holding.strength.max(DRAW_STRENGTH)
+        } else {
+            holding.strength
+        };
+        let wet = spot.texture.as_ref().map_or(0.0, |texture| {
+            let flush = if texture.flush_draw_possible { 0.5 } else { 0.0 };
+            let straight = if texture.connectedness >= 3 { 0.5 } else { 0.0 };
+            flush + straight
+        });
+        let bluff = self.bluff * (1.0 + wet);
+
+        let likelihood = if spot.to_call == 0 {
+            let bet = bluff + (1.0 - bluff) * sigmoid(strength - (1.0 - self.aggression));
+            match *action {
+                Action::Raise(_) => bet,
+                Action::Fold => 0.0,
+                Action::Check | Action::Call => 1.0 - bet,
+            }
+        } else {
+            let price = spot.to_call as f64 / (spot.pot + spot.to_call) as f64;
+            let call_threshold = (0.25 + price + 0.1 * spot.raises as f64).min(0.95) -
+                                 self.looseness;
+            let raise_threshold = ((1.0 + call_threshold) / 2.0 + 0.1).min(0.97);
+            let raise = bluff / 2.0 + (1.0 - bluff / 2.0) * sigmoid(strength - raise_threshold);
+            let call = sigmoid(strength - call_threshold);
+            match *action {
+                Action::Raise(_) => raise,
+                Action::Call | Action::Check => (1.0 - raise) * call,
+                Action::Fold => (1.0 - raise) * (1.0 - call),
+            }
+        };
+        likelihood.max(FLOOR)
+    }
+}
+
+/**
+ * RangeTracker narrows the range of every opponent as it acts, by Bayes' rule: the weight of
+ * every combo is multiplied by the likelihood of the action taken with it, given by the action
+ * model. Ranges start uniform on every hand, without the cards known to the player.
+ */
+pub struct RangeTracker<M: ActionModel> {
+    model: M,
+    ranges: Vec<Range>, // Posterior range of every seat, with weights adding up to 1
+    events: usize, // Events of the hand already applied
+    preflop: Vec<f64>, // Strength of every hand class before the flop
+}
+
+impl<M: ActionModel> RangeTracker<M> {
+    /**
+     * new creates a tracker of the ranges of some players
+     */
+    pub fn new(model: M, players: usize) -> RangeTracker<M> {
+        let mut tracker = RangeTracker {
+            model,
+            ranges: Vec::new(),
+            events: 0,
+            preflop: preflop_strengths(),
+        };
+        tracker.ranges = (0..players).map(|_| Range::new()).collect();
+        tracker.reset(&[]);
+        tracker
+    }
+
+    /**
+     * model gets the action model
+     */
+    pub fn model(&self) -> &M {
+        &self.model
+    }
+
+    /**
+     * reset starts a hand, with the ranges of every player uniform on the hole cards without the
+     * dead cards
+     */
+    pub fn reset(&mut self, dead: &[Card]) {
+        let dead: Vec<&Card> = dead.iter().collect();
+        let mut range = Range::new();
+        for combo in Range::uniform().combos().iter().filter(|combo| !combo.conflicts(&dead)) {
+            range.add(combo.cards[0], combo.cards[1], combo.weight);
+        }
+        let range = normalize(&range, &[]);
+        for seat in 0..self.ranges.len() {
+            self.ranges[seat] = range.clone();
+        }
+        self.events = 0;
+    }
+
+    /**
+     * start resets the ranges for the hand of an observation, with the hole cards of the player
+     * as dead cards
+     */
+    pub fn start(&mut self, observation: &Observation) {
+        self.reset(&observation.hole);
+    }
+
+    /**
+     * update narrows the ranges of the opponents with the actions of an observation not seen yet,
+     * and removes the combos blocked by the board. Bets are replayed from the history, with the
+     * small blind being half of the big blind.
+     */
+    pub fn update(&mut self, observation: &Observation) {
+        let spots = spots(observation);
+        for (i, event) in observation.history.iter().enumerate().skip(self.events) {
+            if event.seat != observation.seat {
+                self.observe(event.seat, &spots[i], &event.action);
+            }
+        }
+        self.events = observation.history.len();
+
+        let board: Vec<&Card> = observation.board.iter().collect();
+        for range in &mut self.ranges {
+            *range = normalize(range, &board);
+        }
+    }
+
+    /**
+     * observe narrows the range of a player that took an action on a spot
+     */
+    pub fn observe(&mut self, seat: usize, spot: &Spot, action: &Action) {
+        let board: Vec<&Card> = spot.board.iter().collect();
+        let strengths = self.strengths(spot);
+        let mut posterior = Range::new();
+        for combo in self.ranges[seat].combos().iter().filter(|combo| !combo.conflicts(&board)) {
+            let holding = Holding {
+                strength: strengths(combo),
+                draw: outs::draws([&combo.cards[0], &combo.cards[1]], &board).iter().any(|draw| {
+                    matches!(*draw, Draw::FlushDraw | Draw::OpenEnded | Draw::ComboDraw)
+                }),
+            };
+            let likelihood = self.model.likelihood(spot, &holding, action);
+            posterior.add(combo.cards[0], combo.cards[1], combo.weight * likelihood);
+        }
+        self.ranges[seat] = normalize(&posterior, &[]);
+    }
+
+    /**
+     * range gets the posterior range of a player, with weights adding up to 1
+     */
+    pub fn range(&self, seat: usize) -> &Range {
+        &self.ranges[seat]
+    }
+
+    /**
+     * equity gets the probability of the hole cards winning the showdown against the range of a
+     * player. It's exact from the turn on, and sampled before.
+     */
+    pub fn equity(&self, seat: usize, hole: types::Hole, board: &[&Card]) -> f64 {
+        if board.len() + 1 >= consts::TABLE_SIZE {
+            strength::equity(hole, board, &self.ranges[seat])
+        } else {
+            strength::sampled_equity(hole, board, &self.ranges[seat], SAMPLES, SEED)
+        }
+    }
+
+    /**
+     * strengths gets the strength of any combo on a spot: the share of the hole cards it beats
+     * on the board, or of the starting hands it's better than before the flop
+     */
+    fn strengths(&self, spot: &Spot) -> Box<dyn Fn(&Combo) -> f64> {
+        if spot.street == Street::Preflop {
+            let preflop = self.preflop.clone();
+            return Box::new(move |combo| {
+                preflop[HandClass::of(&combo.cards[0], &combo.cards[1]).index()]
+            });
+        }
+
+        let board: Vec<&Card> = spot.board.iter().collect();
+        let combos: Vec<[Card; 2]> = Range::uniform()
+            .combos()
+            .iter()
+            .filter(|combo| !combo.conflicts(&board))
+            .map(|combo| combo.cards)
+            .collect();
+        let mut hands: Vec<(Hand, usize)> = combos.iter()
+            .enumerate()
+            .map(|(i, cards)| {
+                let mut all = vec![&cards[0], &cards[1]];
+                all.extend_from_slice(&board);
+                (Hand::from_cards(&all), i)
+            })
+            .collect();
+        hands.sort_by(|a, b| a.0.cmp(&b.0));
+
+        // Tied hands share the strength of the middle of their group
+        let mut strengths = vec![0.0; combos.len()];
+        let mut start = 0;
+        while start < hands.len() {
+            let mut end = start;
+            while end < hands.len() && hands[end].0 == hands[start].0 {
+                end += 1;
+            }
+            let strength = (start + end - 1) as f64 / 2.0 / (hands.len() - 1) as f64;
+            for hand in &hands[start..end] {
+                strengths[hand.1] = strength;
+            }
+            start = end;
+        }
+
+        let mut by_key = vec![0.0; 52 * 52];
+        for (cards, strength) in combos.iter().zip(strengths.iter()) {
+            by_key[key(cards)] = *strength;
+        }
+        Box::new(move |combo| by_key[key(&combo.cards)])
+    }
+}
+
+/**
+ * spots replays the history of an observation, getting the spot of every event
+ */
+pub fn spots(observation: &Observation) -> Vec<Spot> {
+    let small_blind = observation.big_blind / 2;
+    let mut bets = [0, 0];
+    bets[observation.button] = small_blind;
+    bets[1 - observation.button] = observation.big_blind;
+    let mut pot = 0;
+    let mut street = Street::Preflop;
+    let mut raises = 0;
+    let mut texture = None;
+
+    let mut spots = Vec::with_capacity(observation.history.len());
+    for event in &observation.history {
+        if event.street != street {
+            street = event.street;
+            pot += bets[0] + bets[1];
+            bets = [0, 0];
+            raises = 0;
+            let board: Vec<&Card> = observation.board[..street.board_size()].iter().collect();
+            texture = Some(board::texture(&board));
+        }
+        let highest = bets[0].max(bets[1]);
+        spots.push(Spot {
+            street,
+            board: observation.board[..street.board_size()].to_vec(),
+            texture: texture.clone(),
+            pot: pot + bets[0] + bets[1],
+            to_call: highest - bets[event.seat],
+            raises,
+        });
+
+        match event.action {
+            Action::Raise(to) => {
+                bets[event.seat] = to;
+                raises += 1;
+            }
+            Action::Call => bets[event.seat] = highest,
+            Action::Fold | Action::Check => (),
+        }
+    }
+    spots
+}
+
+/**
+ * preflop_strengths gets the strength of every hand class before the flop: the share of the
+ * starting hands with a lower Chen score
+ */
+fn preflop_strengths() -> Vec<f64> {
+    let classes = HandClass::all();
+    let scores: Vec<f64> = classes.iter().map(chen).collect();
+    let combos: Vec<f64> = classes.iter().map(|class| class.combos().len() as f64).collect();
+    let total: f64 = combos.iter().sum();
+
+    let mut strengths = vec![0.0; preflop::CLASSES];
+    for (i, class) in classes.iter().enumerate() {
+        let count = |compare: &dyn Fn(usize) -> bool| -> f64 {
+            (0..classes.len()).filter(|j| compare(*j)).map(|j| combos[j]).sum()
+        };
+        let below = count(&|j| scores[j] < scores[i]);
+        let tied = count(&|j| scores[j] == scores[i]);
+        strengths[class.index()] = (below + tied / 2.0) / total;
+    }
+    strengths
+}
+
+/**
+ * chen gets the Chen score of a starting hand, a quick estimation of its preflop value
+ */
+fn chen(class: &HandClass) -> f64 {
+    let points = |value: CardValue| match value {
+        CardValue::ValueA => 10.0,
+        CardValue::ValueK => 8.0,
+        CardValue::ValueQ => 7.0,
+        CardValue::ValueJ => 6.0,
+        _ => value as usize as f64 / 2.0,
+    };
+    if class.high == class.low {
+        return (2.0 * points(class.high)).max(5.0);
+    }
+
+    let gap = class.high as usize - class.low as usize - 1;
+    let mut score = points(class.high);
+    if class.suited {
+        score += 2.0;
+    }
+    score -= match gap {
+        0 => 0.0,
+        1 => 1.0,
+        2 => 2.0,
+        3 => 4.0,
+        _ => 5.0,
+    };
+    if gap <= 1 && class.high < CardValue::ValueQ {
+        score += 1.0;
+    }
+    score.ceil()
+}
+
+/**
+ * sigmoid goes smoothly from 0 to 1 as the distance of the strength to a threshold goes from
+ * negative to positive
+ */
+fn sigmoid(distance: f64) -> f64 {
+    1.0 / (1.0 + (-distance / WIDTH).exp())
+}
+
+/**
+ * normalize gets a range without the combos blocked by the cards, with weights adding up to 1
+ */
+fn normalize(range: &Range, blocked: &[&Card]) -> Range {
+    let combos: Vec<&Combo> =
+        range.combos().iter().filter(|combo| !combo.conflicts(blocked)).collect();
+    let total: f64 = combos.iter().map(|combo| combo.weight).sum();
+    let mut normalized = Range::new();
+    for combo in combos {
+        let weight = if total > 0.0 { combo.weight / total } else { 0.0 };
+        normalized.add(combo.cards[0], combo.cards[1], weight);
+    }
+    normalized
+}
+
+/**
+ * key identifies some hole cards in any order
+ */
+fn key(cards: &[Card; 2]) -> usize {
+    let (first, second) = (cards[0].index(), cards[1].index());
+    first.max(second) * 52 + first.min(second)
+}
@@ -0,0 +1,272 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// Internal imports
+use types;
+use consts;
+use card;
+use card::Card;
+use hand;
+use hand::Hand;
+use hand::HandType;
+
+/**
+ * Out is an unseen card that improves the type of the player's hand
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Out {
+    pub card: Card,
+    pub hand_type: HandType, // Type of the hand after the card is dealt
+    pub clean: Option<bool>, // If the player is ahead of the opponent after the card, if given
+}
+
+/**
+ * Draw is any of the possible draws of a player on the flop or the turn
+ */
+#[derive(Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum Draw {
+    FlushDraw, // 4 cards of the same suit
+    OpenEnded, // 2 different values complete a straight, double gutshots included
+    Gutshot, // Only one value completes a straight
+    ComboDraw, // A flush draw together with a straight draw
+    BackdoorFlush, // 3 cards of the same suit on the flop
+    BackdoorStraight, // 2 more cards complete a straight on the flop
+}
+
+/**
+ * outs finds the unseen cards that improve the type of the player's hand on a flop or a turn.
+ * Cards that improve the board in the same way for everybody, like the ones pairing the board,
+ * are not outs. If the opponent hole cards are given they are not considered unseen and every out
+ * is marked as clean when it puts the player ahead of the opponent.
+ */
+pub fn outs(hole: types::Hole, board: &[&Card], opponent: Option<types::Hole>) -> Vec<Out> {
+    assert!(board.len() >= 3 && board.len() <= consts::TABLE_SIZE,
+            "Outs need a flop, a turn or a river");
+
+    let mut known = hole.to_vec();
+    known.extend_from_slice(board);
+    if let Some(opponent) = opponent {
+        known.extend_from_slice(&opponent);
+    }
+
+    let mut player = hole.to_vec();
+    player.extend_from_slice(board);
+    let current = hand::hand_type(&player);
+
+    let mut outs = Vec::new();
+    if board.len() == consts::TABLE_SIZE {
+        return outs;
+    }
+
+    for card in card::deck().iter().filter(|card| !known.contains(card)) {
+        let mut improved = player.clone();
+        improved.push(card);
+        let mut improved_board = board.to_vec();
+        improved_board.push(card);
+
+        let hand_type = hand::hand_type(&improved);
+        if hand_type <= current || hand_type <= hand::hand_type(&improved_board) {
+            continue;
+        }
+
+        let clean = opponent.map(|opponent| {
+            let mut rival = opponent.to_vec();
+            rival.extend_from_slice(&improved_board);
+            Hand::from_cards(&improved) > Hand::from_cards(&rival)
+        });
+        outs.push(Out {
+            card: *card,
+            hand_type,
+            clean,
+        });
+    }
+
+    outs
+}
+
+/**
+ * draws classifies the draws of the player on a flop or a turn. Only draws that use at least one
+ * of the hole cards are considered and made hands are not draws.
+ */
+pub fn draws(hole: types::Hole, board: &[&Card]) -> Vec<Draw> {
+    let mut draws = Vec::new();
+    if board.len() < 3 || board.len() >= consts::TABLE_SIZE {
+        return draws;
+    }
+
+    let mut player = hole.to_vec();
+    player.extend_from_slice(board);
+    let flop = board.len() == 3;
+
+    // Flush draws
+    let made_flush = hand::hand_type(&player) >= HandType::Flush;
+    for suit in &card::SUITS {
+        let count = player.iter().filter(|card| card.suit == *suit).count();
+        let in_hole = hole.iter().any(|card| card.suit == *suit);
+        if !made_flush && in_hole && count == 4 {
+            draws.push(Draw::FlushDraw);
+        } else if flop && in_hole && count == 3 {
+            draws.push(Draw::BackdoorFlush);
+        }
+    }
+
+    // Straight draws, counting how many values complete a straight
+    let values = value_mask(&player);
+    let board_values = value_mask(board);
+    if straight_high(values).is_none() {
+        let completing = (0..card::VALUES.len())
+            .filter(|rank| improves_straight(values, board_values, 1 << rank))
+            .count();
+
+        if completing >= 2 {
+            draws.push(Draw::OpenEnded);
+        } else if completing == 1 {
+            draws.push(Draw::Gutshot);
+        } else if flop {
+            let backdoor = (0..card::VALUES.len()).any(|first| {
+                (first + 1..card::VALUES.len())
+                    .any(|second| improves_straight(values, board_values, 1 << first | 1 << second))
+            });
+            if backdoor {
+                draws.push(Draw::BackdoorStraight);
+            }
+        }
+    }
+
+    if draws.contains(&Draw::FlushDraw) &&
+       (draws.contains(&Draw::OpenEnded) || draws.contains(&Draw::Gutshot)) {
+        draws.push(Draw::ComboDraw);
+    }
+
+    draws.sort();
+    draws.dedup();
+    draws
+}
+
+/**
+ * value_mask gets a bitmask with a bit for every value on the cards, the 2 being the lowest bit
+ */
+pub(crate) fn value_mask(cards: &[&Card]) -> u16 {
+    cards.iter().fold(0, |mask, card| mask | 1 << card.rank())
+}
+
+/**
+ * straight_high gets the rank of the highest card of the best straight on a value mask. An Ace
+ * also counts as the lowest card.
+ */
+pub(crate) fn straight_high(values: u16) -> Option<usize> {
+    let ace = card::VALUES.len() - 1;
+    let extended = (values as u32) << 1 | (values as u32 >> ace & 1);
+    (consts::HAND_SIZE - 1..card::VALUES.len() + 1)
+        .rev()
+        .find(|top| (extended >> (top - (consts::HAND_SIZE - 1))) & 0b11111 == 0b11111)
+        .map(|top| top - 1)
+}
+
+/**
+ * improves_straight checks if adding the new values gives the player a straight that the board
+ * does not give on its own
+ */
+fn improves_straight(values: u16, board_values: u16, new_values: u16) -> bool {
+    if values & new_values != 0 {
+        return false;
+    }
+
+    match straight_high(values | new_values) {
+        Some(high) => straight_high(board_values | new_values) != Some(high),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::outs;
+    use super::draws;
+    use super::Draw;
+    use super::super::card::Card;
+    use super::super::hand::HandType;
+
+    fn parse(cards: &str) -> Vec<Card> {
+        cards.split_whitespace()
+            .map(|card| Card::new(card.chars().next().unwrap(), card.chars().nth(1).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_combo_draw_outs() {
+        let hole = parse("9H 8H");
+        let board = parse("7H 6C 2H");
+        let board = [&board[0], &board[1], &board[2]];
+
+        let outs = outs([&hole[0], &hole[1]], &board, None);
+        let count = |hand_type| outs.iter().filter(|out| out.hand_type == hand_type).count();
+        assert_eq!(outs.len(), 21);
+        assert_eq!(count(HandType::Flush), 9);
+        assert_eq!(count(HandType::Straight), 6);
+        assert_eq!(count(HandType::Pair), 6);
+        assert!(outs.iter().all(|out| out.clean.is_none()));
+
+        assert_eq!(draws([&hole[0], &hole[1]], &board),
+                   vec![Draw::FlushDraw, Draw::OpenEnded, Draw::ComboDraw]);
+    }
+
+    #[test]
+    fn test_clean_outs() {
+        let hole = parse("9H 8H");
+        let board = parse("7H 6C 2H");
+        let opponent = parse("KH QH");
+
+        let outs = outs([&hole[0], &hole[1]],
+                        &[&board[0], &board[1], &board[2]],
+                        Some([&opponent[0], &opponent[1]]));
+        let clean = outs.iter().filter(|out| out.clean == Some(true)).count();
+        let dirty = outs.iter().filter(|out| out.clean == Some(false)).count();
+        assert_eq!(outs.len(), 19);
+        assert_eq!(dirty, 7);
+        assert_eq!(clean, 12);
+    }
+
+    #[test]
+    fn test_board_improvements_are_not_outs() {
+        let hole = parse("AS KD");
+        let board = parse("7C 7D 2H 9S");
+
+        let outs = outs([&hole[0], &hole[1]], &[&board[0], &board[1], &board[2], &board[3]], None);
+        assert_eq!(outs.len(), 6);
+        assert!(outs.iter().all(|out| out.hand_type == HandType::TwoPair));
+    }
+
+    #[test]
+    fn test_draws() {
+        let gutshot = parse("9C 8D JS 7H 2C");
+        assert_eq!(draws([&gutshot[0], &gutshot[1]], &[&gutshot[2], &gutshot[3], &gutshot[4]]),
+                   vec![Draw::Gutshot]);
+
+        let backdoor = parse("AH KH 7H 2C 9S");
+        assert_eq!(draws([&backdoor[0], &backdoor[1]],
+                         &[&backdoor[2], &backdoor[3], &backdoor[4]]),
+                   vec![Draw::BackdoorFlush]);
+
+        let made = parse("9C 8D TS 7H 6C");
+        assert!(draws([&made[0], &made[1]], &[&made[2], &made[3], &made[4]]).is_empty());
+    }
+}
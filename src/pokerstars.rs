@@ -0,0 +1,496 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// Internal imports
+use types::Street;
+use card::Card;
+use history;
+use history::Action;
+use history::ActionKind;
+use history::Amount;
+use history::HandHistory;
+use history::ParseError;
+use history::Seat;
+use history::Tournament;
+
+/**
+ * Section of a hand history being read
+ */
+#[derive(PartialEq, Clone, Copy)]
+enum Section {
+    Seats,
+    Street(Street),
+    Summary,
+}
+
+/**
+ * parse reads all the PokerStars Hold'em hands of a file. Line numbers of the errors are relative
+ * to the start of the text.
+ */
+pub fn parse(text: &str) -> Result<Vec<HandHistory>, ParseError> {
+    let mut hands = Vec::new();
+    let mut current: Vec<(usize, &str)> = Vec::new();
+
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim_start_matches('\u{feff}').trim();
+        if line.starts_with("PokerStars ") && !current.is_empty() {
+            hands.push(parse_lines(&current)?);
+            current.clear();
+        }
+        if !line.is_empty() {
+            current.push((i + 1, line));
+        }
+    }
+
+    if !current.is_empty() {
+        hands.push(parse_lines(&current)?);
+    }
+    Ok(hands)
+}
+
+/**
+ * parse_hand reads a single PokerStars Hold'em hand
+ */
+pub fn parse_hand(text: &str) -> Result<HandHistory, ParseError> {
+    let mut hands = parse(text)?;
+    match hands.len() {
+        1 => Ok(hands.remove(0)),
+        count => {
+            Err(ParseError {
+                line: 1,
+                message: format!("expected a single hand, found {}", count),
+            })
+        }
+    }
+}
+
+/**
+ * error builds a ParseError on a line
+ */
+fn error(line: usize, message: &str) -> ParseError {
+    ParseError {
+        line,
+        message: message.to_string(),
+    }
+}
+
+/**
+ * parse_lines reads a hand from its non empty lines, with their line numbers
+ */
+fn parse_lines(lines: &[(usize, &str)]) -> Result<HandHistory, ParseError> {
+    let (first_line, header) = lines[0];
+    let mut hand = parse_header(header).map_err(|message| error(first_line, &message))?;
+
+    let &(table_line, table) = lines.get(1).ok_or_else(|| error(first_line, "missing table"))?;
+    parse_table(table, &mut hand).map_err(|message| error(table_line, &message))?;
+
+    let mut section = Section::Seats;
+    for &(number, line) in &lines[2..] {
+        if line.starts_with("*** ") {
+            section = parse_section(line, section, &mut hand)
+                .map_err(|message| error(number, &message))?;
+            continue;
+        }
+
+        let result = match section {
+            Section::Seats if line.starts_with("Seat ") => parse_seat(line, &mut hand),
+            Section::Seats => parse_player_line(line, Street::Preflop, &mut hand),
+            Section::Street(street) => parse_player_line(line, street, &mut hand),
+            Section::Summary => parse_summary(line, &mut hand),
+        };
+        result.map_err(|message| error(number, &message))?;
+    }
+
+    if section != Section::Summary {
+        return Err(error(lines[lines.len() - 1].0, "missing summary"));
+    }
+    Ok(hand)
+}
+
+/**
+ * parse_header reads the first line of a hand, for example
+ * "PokerStars Hand #1: Hold'em No Limit ($0.01/$0.02 USD) - 2020/03/01 20:00:00 ET"
+ */
+fn parse_header(line: &str) -> Result<HandHistory, String> {
+    let start = line.find("Hand #").ok_or("missing hand number")? + "Hand #".len();
+    let colon = start + line[start..].find(':').ok_or("missing hand number")?;
+    let id = line[start..colon].parse::<u64>().map_err(|_| "invalid hand number")?;
+    let game = line[colon + 1..].trim();
+
+    let (tournament, game) = if game.starts_with("Tournament #") {
+        let comma = game.find(',').ok_or("invalid tournament")?;
+        let tournament_id = game["Tournament #".len()..comma]
+            .parse::<u64>()
+            .map_err(|_| "invalid tournament number")?;
+        let holdem = game.find("Hold'em").ok_or("unsupported game")?;
+        if holdem < comma {
+            return Err(String::from("invalid tournament"));
+        }
+        let buy_in = game[comma + 1..holdem].trim().to_string();
+        let level = match game.find("Level ") {
+            Some(level) => {
+                let text = &game[level + "Level ".len()..];
+                text[..text.find(" (").unwrap_or(text.len())].to_string()
+            }
+            None => String::new(),
+        };
+
+        (Some(Tournament {
+            id: tournament_id,
+            buy_in,
+            level,
+        }),
+         &game[holdem..])
+    } else {
+        (None, game)
+    };
+
+    if !game.starts_with("Hold'em ") {
+        return Err(String::from("unsupported game"));
+    }
+    let open = game.find(" (").ok_or("missing stakes")?;
+    let close = open + game[open..].find(')').ok_or("missing stakes")?;
+    let limit = game["Hold'em ".len()..open].split(" - ").next().unwrap().trim().to_string();
+
+    // Stakes, like "$0.01/$0.02 USD" or "25/50"
+    let mut stakes = game[open + 2..close].split_whitespace();
+    let blinds: Vec<&str> = stakes.next().ok_or("missing stakes")?.split('/').collect();
+    let currency = stakes.next().map(|currency| currency.to_string());
+    if blinds.len() != 2 {
+        return Err(String::from("invalid stakes"));
+    }
+    let small_blind = history::parse_amount(blinds[0]).ok_or("invalid small blind")?;
+    let big_blind = history::parse_amount(blinds[1]).ok_or("invalid big blind")?;
+
+    let date = match game[close..].find(" - ") {
+        Some(dash) => game[close + dash + 3..].trim().to_string(),
+        None => String::new(),
+    };
+
+    Ok(HandHistory {
+        site: String::from("PokerStars"),
+        id,
+        tournament,
+        currency,
+        limit,
+        small_blind,
+        big_blind,
+        date,
+        table: String::new(),
+        max_seats: 0,
+        button: 0,
+        seats: Vec::new(),
+        hero: None,
+        hero_cards: None,
+        actions: Vec::new(),
+        board: Vec::new(),
+        total_pot: 0,
+        rake: 0,
+    })
+}
+
+/**
+ * parse_table reads the line with the name of the table, its size and the button, for example
+ * "Table 'Aaltje III' 6-max Seat #1 is the button"
+ */
+fn parse_table(line: &str, hand: &mut HandHistory) -> Result<(), String> {
+    if !line.starts_with("Table '") {
+        return Err(String::from("missing table"));
+    }
+    let close = line.rfind('\'').ok_or("invalid table name")?;
+    if close < "Table '".len() {
+        return Err(String::from("invalid table name"));
+    }
+    hand.table = line["Table '".len()..close].to_string();
+
+    for word in line[close + 1..].split_whitespace() {
+        if word.ends_with("-max") {
+            hand.max_seats = word.trim_end_matches("-max")
+                .parse()
+                .map_err(|_| "invalid table size")?;
+        } else if let Some(button) = word.strip_prefix('#') {
+            hand.button = button.parse().map_err(|_| "invalid button")?;
+        }
+    }
+
+    if hand.button == 0 {
+        return Err(String::from("missing button"));
+    }
+    Ok(())
+}
+
+/**
+ * parse_seat reads a player sitting at the table, for example "Seat 1: Alice ($2 in chips)"
+ */
+fn parse_seat(line: &str, hand: &mut HandHistory) -> Result<(), String> {
+    let colon = line.find(": ").ok_or("invalid seat")?;
+    let number = line["Seat ".len()..colon].parse::<u32>().map_err(|_| "invalid seat number")?;
+
+    let rest = &line[colon + 2..];
+    let open = rest.rfind(" (").ok_or("missing stack")?;
+    let chips = rest[open..].find(" in chips").ok_or("missing stack")?;
+    let stack = history::parse_amount(&rest[open + 2..open + chips]).ok_or("invalid stack")?;
+    let sitting_out = rest.ends_with("is sitting out");
+
+    hand.seats.push(Seat {
+        number,
+        player: rest[..open].to_string(),
+        stack,
+        sitting_out,
+    });
+    Ok(())
+}
+
+/**
+ * parse_section reads a line starting a new section, like "*** FLOP *** [Qh Jh 2c]", and adds
+ * the new community cards to the board
+ */
+fn parse_section(line: &str, current: Section, hand: &mut HandHistory) -> Result<Section, String> {
+    let (section, new_cards) = if line.starts_with("*** HOLE CARDS ***") {
+        (Section::Street(Street::Preflop), 0)
+    } else if line.starts_with("*** FLOP ***") {
+        (Section::Street(Street::Flop), 3)
+    } else if line.starts_with("*** TURN ***") {
+        (Section::Street(Street::Turn), 1)
+    } else if line.starts_with("*** RIVER ***") {
+        (Section::Street(Street::River), 1)
+    } else if line.starts_with("*** SHOW DOWN ***") {
+        (current, 0)
+    } else if line.starts_with("*** SUMMARY ***") {
+        (Section::Summary, 0)
+    } else {
+        return Err(format!("unknown section '{}'", line));
+    };
+
+    if new_cards > 0 {
+        let open = line.rfind('[').ok_or("missing board")?;
+        let cards = parse_cards(&line[open..])?;
+        if cards.len() != new_cards {
+            return Err(String::from("invalid board"));
+        }
+        hand.board.extend(cards);
+    }
+    Ok(section)
+}
+
+/**
+ * parse_player_line reads a line of the hand other than a section start: actions of the players,
+ * dealt cards, returned bets and collected pots
+ */
+fn parse_player_line(line: &str, street: Street, hand: &mut HandHistory) -> Result<(), String> {
+    if line.starts_with("Dealt to ") {
+        if let Some(open) = line.rfind(" [") {
+            let player = line["Dealt to ".len()..open].to_string();
+            let cards = parse_cards(&line[open + 1..])?;
+            if cards.len() != 2 {
+                return Err(String::from("invalid hole cards"));
+            }
+            hand.hero = Some(player);
+            hand.hero_cards = Some([cards[0], cards[1]]);
+        }
+        return Ok(());
+    }
+
+    if line.starts_with("Uncalled bet (") {
+        let close = line.find(')').ok_or("invalid uncalled bet")?;
+        let amount = history::parse_amount(&line["Uncalled bet (".len()..close])
+            .ok_or("invalid amount")?;
+        let player = line[close..].trim_start_matches(") returned to ").to_string();
+        push_action(hand, street, player, ActionKind::UncalledBet(amount), false);
+        return Ok(());
+    }
+
+    // Players that are not seated join or leave the table, and their messages are ignored
+    let player = match find_player(hand, line) {
+        Some(player) => player,
+        None => return Ok(()),
+    };
+    let rest = &line[player.len()..];
+
+    if let Some(action) = rest.strip_prefix(": ") {
+        if let Some((kind, all_in)) = parse_action(action)? {
+            push_action(hand, street, player, kind, all_in);
+        }
+    } else if let Some(collected) = rest.strip_prefix(" collected ") {
+        let amount = collected.split_whitespace().next().unwrap_or("");
+        let amount = history::parse_amount(amount).ok_or("invalid collected amount")?;
+        push_action(hand, street, player, ActionKind::Collect(amount), false);
+    }
+    // Chat, connections and other messages of the players are ignored
+
+    Ok(())
+}
+
+/**
+ * parse_action reads what a player does, the text after "Player: ". Returns None for messages
+ * that are not actions, like "doesn't show hand".
+ */
+fn parse_action(text: &str) -> Result<Option<(ActionKind, bool)>, String> {
+    let all_in = text.ends_with(" and is all-in");
+    let text = text.trim_end_matches(" and is all-in");
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let amount = |position: usize| -> Result<Amount, String> {
+        words.get(position)
+            .and_then(|word| history::parse_amount(word))
+            .ok_or_else(|| format!("invalid amount on '{}'", text))
+    };
+
+    let kind = match words.first().cloned().unwrap_or("") {
+        "folds" => ActionKind::Fold,
+        "checks" => ActionKind::Check,
+        "calls" => ActionKind::Call(amount(1)?),
+        "bets" => ActionKind::Bet(amount(1)?),
+        "raises" => {
+            ActionKind::Raise {
+                amount: amount(1)?,
+                to: amount(3)?,
+            }
+        }
+        "posts" if text.starts_with("posts small blind ") => ActionKind::SmallBlind(amount(3)?),
+        "posts" if text.starts_with("posts big blind ") => ActionKind::BigBlind(amount(3)?),
+        "posts" if text.starts_with("posts the ante ") => ActionKind::Ante(amount(3)?),
+        "posts" if text.starts_with("posts small & big blinds ") => {
+            ActionKind::SmallAndBigBlind(amount(5)?)
+        }
+        "shows" => {
+            let cards = parse_cards(text.trim_start_matches("shows "))?;
+            if cards.len() != 2 {
+                return Err(String::from("invalid shown cards"));
+            }
+            ActionKind::Show([cards[0], cards[1]])
+        }
+        "mucks" => ActionKind::Muck,
+        "posts" => return Err(format!("unknown post '{}'", text)),
+        _ => return Ok(None),
+    };
+
+    Ok(Some((kind, all_in)))
+}
+
+/**
+ * parse_summary reads the lines of the summary with the total pot, the rake and the board
+ */
+fn parse_summary(line: &str, hand: &mut HandHistory) -> Result<(), String> {
+    if let Some(total) = line.strip_prefix("Total pot ") {
+        let total = total.split_whitespace().next().unwrap_or("");
+        hand.total_pot = history::parse_amount(total).ok_or("invalid total pot")?;
+        if let Some(rake) = line.find("Rake ") {
+            let rake = line[rake + "Rake ".len()..].split_whitespace().next().unwrap_or("");
+            hand.rake = history::parse_amount(rake).ok_or("invalid rake")?;
+        }
+    } else if let Some(board) = line.strip_prefix("Board ") {
+        let board = parse_cards(board)?;
+        if board != hand.board {
+            return Err(String::from("the board does not match the streets"));
+        }
+    }
+    Ok(())
+}
+
+/**
+ * parse_cards reads the cards between brackets, the first group found, like "[Ah Kh] (a pair)"
+ */
+fn parse_cards(text: &str) -> Result<Vec<Card>, String> {
+    let open = text.find('[').ok_or("missing cards")?;
+    let close = open + text[open..].find(']').ok_or("missing cards")?;
+    text[open + 1..close]
+        .split_whitespace()
+        .map(|card| Card::parse(card).ok_or_else(|| format!("invalid card '{}'", card)))
+        .collect()
+}
+
+/**
+ * find_player gets the name of the seated player that starts the line. The longest name wins, so
+ * names that are prefixes of other names are not confused.
+ */
+fn find_player(hand: &HandHistory, line: &str) -> Option<String> {
+    hand.seats
+        .iter()
+        .map(|seat| &seat.player)
+        .filter(|player| {
+            line.starts_with(player.as_str()) &&
+            (line[player.len()..].starts_with(':') || line[player.len()..].starts_with(' '))
+        })
+        .max_by_key(|player| player.len())
+        .cloned()
+}
+
+/**
+ * push_action adds an action to the hand
+ */
+fn push_action(hand: &mut HandHistory,
+               street: Street,
+               player: String,
+               kind: ActionKind,
+               all_in: bool) {
+    hand.actions.push(Action {
+        street,
+        player,
+        kind,
+        all_in,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_action;
+    use super::parse_header;
+    use super::parse_table;
+    use super::super::history::ActionKind;
+
+    #[test]
+    fn test_parse_header() {
+        let hand = parse_header("PokerStars Hand #1:  Hold'em No Limit ($0.01/$0.02 USD) - \
+                                 2020/03/01 14:00:00 ET")
+            .unwrap();
+        assert_eq!(hand.id, 1);
+        assert_eq!(hand.limit, "No Limit");
+        assert_eq!((hand.small_blind, hand.big_blind), (1, 2));
+        assert_eq!(hand.currency, Some(String::from("USD")));
+        assert_eq!(hand.date, "2020/03/01 14:00:00 ET");
+
+        assert_eq!(parse_header("PokerStars Hand #2: Omaha Pot Limit ($0.01/$0.02 USD)"),
+                   Err(String::from("unsupported game")));
+    }
+
+    #[test]
+    fn test_parse_table() {
+        let mut hand = parse_header("PokerStars Hand #1: Hold'em No Limit ($0.01/$0.02 USD)")
+            .unwrap();
+        parse_table("Table 'Aaltje III' 6-max Seat #1 is the button", &mut hand).unwrap();
+        assert_eq!(hand.table, "Aaltje III");
+        assert_eq!((hand.max_seats, hand.button), (6, 1));
+
+        assert_eq!(parse_table("Table 'Aaltje III 6-max Seat #1 is the button", &mut hand),
+                   Err(String::from("invalid table name")));
+    }
+
+    #[test]
+    fn test_parse_action() {
+        assert_eq!(parse_action("raises $0.04 to $0.06"),
+                   Ok(Some((ActionKind::Raise { amount: 4, to: 6 }, false))));
+        assert_eq!(parse_action("calls 1455 and is all-in"),
+                   Ok(Some((ActionKind::Call(145500), true))));
+        assert_eq!(parse_action("doesn't show hand"), Ok(None));
+        assert!(parse_action("bets lots").is_err());
+    }
+}
@@ -0,0 +1,354 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// External imports
+use std::cmp::Ordering;
+use std::fmt;
+
+// Internal imports
+use card;
+use card::Card;
+use card::CardValue;
+use hand::Hand;
+use range::Range;
+use rng::Rng;
+
+/**
+ * Number of starting hand classes: 13 pairs, 78 suited and 78 offsuit hands
+ */
+pub const CLASSES: usize = 169;
+
+/**
+ * HandClass groups the hole cards that are equivalent before the flop, like "AKs" or "77"
+ */
+#[derive(Hash, PartialEq, Eq, Clone, Copy, Debug)]
+pub struct HandClass {
+    pub high: CardValue,
+    pub low: CardValue,
+    pub suited: bool,
+}
+
+impl HandClass {
+    /**
+     * of gets the class of some hole cards
+     */
+    pub fn of(first: &Card, second: &Card) -> HandClass {
+        let (high, low) = if first.value >= second.value {
+            (first, second)
+        } else {
+            (second, first)
+        };
+        HandClass {
+            high: high.value,
+            low: low.value,
+            suited: high.value != low.value && high.suit == low.suit,
+        }
+    }
+
+    /**
+     * from_index gets the class on a cell of the 13x13 grid, with the Aces on the first row.
+     * Pairs are on the diagonal, suited hands above it and offsuit hands below it.
+     */
+    pub fn from_index(index: usize) -> HandClass {
+        assert!(index < CLASSES, "Invalid hand class index");
+        let row = 12 - index / 13;
+        let column = 12 - index % 13;
+        HandClass {
+            high: card::VALUES[row.max(column)],
+            low: card::VALUES[row.min(column)],
+            suited: row > column,
+        }
+    }
+
+    /**
+     * index gets the position of the class on the 13x13 grid. It's the inverse of from_index.
+     */
+    pub fn index(&self) -> usize {
+        let high = 12 - self.high.rank();
+        let low = 12 - self.low.rank();
+        if self.suited { high * 13 + low } else { low * 13 + high }
+    }
+
+    /**
+     * parse reads a class written like "AKs", "T9o" or "22"
+     */
+    pub fn parse(text: &str) -> Option<HandClass> {
+        let chars: Vec<char> = text.chars().collect();
+        if chars.len() < 2 || chars.len() > 3 {
+            return None;
+        }
+        let first = CardValue::from_symbol(chars[0])?;
+        let second = CardValue::from_symbol(chars[1])?;
+        let suited = match chars.get(2) {
+            Some(&'s') | Some(&'S') if first != second => true,
+            Some(&'o') | Some(&'O') if first != second => false,
+            None if first == second => false,
+            _ => return None,
+        };
+
+        Some(HandClass {
+            high: first.max(second),
+            low: first.min(second),
+            suited,
+        })
+    }
+
+    /**
+     * is_pair checks if both cards have the same value
+     */
+    pub fn is_pair(&self) -> bool {
+        self.high == self.low
+    }
+
+    /**
+     * combos gets all the hole cards of the class: 6 for pairs, 4 for suited hands and 12 for
+     * offsuit ones
+     */
+    pub fn combos(&self) -> Vec<[Card; 2]> {
+        let mut combos = Vec::new();
+        for (i, high_suit) in card::SUITS.iter().enumerate() {
+            for (j, low_suit) in card::SUITS.iter().enumerate() {
+                let wanted = if self.is_pair() {
+                    i < j
+                } else {
+                    (i == j) == self.suited
+                };
+                if wanted {
+                    combos.push([Card {
+                                     value: self.high,
+                                     suit: *high_suit,
+                                 },
+                                 Card {
+                                     value: self.low,
+                                     suit: *low_suit,
+                                 }]);
+                }
+            }
+        }
+        combos
+    }
+
+    /**
+     * all gets the 169 classes ordered by index
+     */
+    pub fn all() -> Vec<HandClass> {
+        (0..CLASSES).map(HandClass::from_index).collect()
+    }
+}
+
+/**
+ * Classes are written as their values, followed by "s" if suited or "o" if offsuit
+ */
+impl fmt::Display for HandClass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let suffix = if self.is_pair() {
+            ""
+        } else if self.suited {
+            "s"
+        } else {
+            "o"
+        };
+        f.pad(&format!("{}{}{}", self.high.symbol(), self.low.symbol(), suffix))
+    }
+}
+
+/**
+ * EquityTable holds the all-in equity of every class against every other class before the flop,
+ * with the number of combos of both classes that do not share cards
+ */
+#[derive(Clone, Debug)]
+pub struct EquityTable {
+    equities: Vec<f64>,
+    weights: Vec<f64>,
+}
+
+impl EquityTable {
+    /**
+     * sampled estimates the equities dealing a number of random boards. Every pair of hole cards
+     * that do not share cards is evaluated on every board, so a few hundred boards give a good
+     * estimation. The same seed gives the same table.
+     */
+    pub fn sampled(boards: usize, seed: u64) -> EquityTable {
+        let mut points = vec![0u64; CLASSES * CLASSES];
+        let mut counts = vec![0u64; CLASSES * CLASSES];
+        let mut rng = Rng::new(seed);
+        let mut deck = card::deck();
+
+        for _ in 0..boards {
+            rng.shuffle(&mut deck);
+            let (board, rest) = deck.split_at(5);
+
+            // Every hole cards with its class, the cards it uses and its rank on the board
+            let mut holes: Vec<(usize, u64, Hand)> = Vec::new();
+            for i in 0..rest.len() {
+                for j in i + 1..rest.len() {
+                    let mut cards: Vec<&Card> = board.iter().collect();
+                    cards.push(&rest[i]);
+                    cards.push(&rest[j]);
+                    holes.push((HandClass::of(&rest[i], &rest[j]).index(),
+                                1 << rest[i].index() | 1 << rest[j].index(),
+                                Hand::from_cards(&cards)));
+                }
+            }
+            holes.sort_by(|a, b| a.2.cmp(&b.2));
+            let mut ranks = Vec::with_capacity(holes.len());
+            for i in 0..holes.len() {
+                let rank = match i {
+                    0 => 0,
+                    _ if holes[i].2 == holes[i - 1].2 => ranks[i - 1],
+                    _ => ranks[i - 1] + 1,
+                };
+                ranks.push(rank);
+            }
+
+            for a in 0..holes.len() {
+                let (first, first_mask, _) = holes[a];
+                for b in a + 1..holes.len() {
+                    let (second, second_mask, _) = holes[b];
+                    if first_mask & second_mask != 0 {
+                        continue;
+                    }
+                    // Two points for a win and one for a tie
+                    let first_points = match ranks[a].cmp(&ranks[b]) {
+                        Ordering::Greater => 2,
+                        Ordering::Equal => 1,
+                        Ordering::Less => 0,
+                    };
+                    points[first * CLASSES + second] += first_points;
+                    points[second * CLASSES + first] += 2 - first_points;
+                    counts[first * CLASSES + second] += 1;
+                    counts[second * CLASSES + first] += 1;
+                }
+            }
+        }
+
+        EquityTable::from_fn(|hero, villain| {
+            let position = hero.index() * CLASSES + villain.index();
+            if counts[position] == 0 {
+                0.5
+            } else {
+                points[position] as f64 / (2 * counts[position]) as f64
+            }
+        })
+    }
+
+    /**
+     * from_fn builds a table from a function that gets the equity of a class against another one.
+     * It must be symmetric: the equity of the villain is 1 minus the one of the hero.
+     */
+    pub fn from_fn<F: Fn(HandClass, HandClass) -> f64>(equity: F) -> EquityTable {
+        let classes = HandClass::all();
+        let masks: Vec<Vec<u64>> = classes.iter()
+            .map(|class| {
+                class.combos()
+                    .iter()
+                    .map(|combo| 1 << combo[0].index() | 1 << combo[1].index())
+                    .collect()
+            })
+            .collect();
+
+        let mut equities = Vec::with_capacity(CLASSES * CLASSES);
+        let mut weights = Vec::with_capacity(CLASSES * CLASSES);
+        for hero in &classes {
+            for villain in &classes {
+                let villain_masks = &masks[villain.index()];
+                let compatible = masks[hero.index()]
+                    .iter()
+                    .flat_map(|first| villain_masks.iter().map(move |second| first & second))
+                    .filter(|shared| *shared == 0)
+                    .count();
+                equities.push(equity(*hero, *villain));
+                weights.push(compatible as f64);
+            }
+        }
+
+        EquityTable { equities, weights }
+    }
+
+    /**
+     * equity gets the probability of the hero winning the showdown against the villain, with ties
+     * counting as half a win
+     */
+    pub fn equity(&self, hero: HandClass, villain: HandClass) -> f64 {
+        self.equities[hero.index() * CLASSES + villain.index()]
+    }
+
+    /**
+     * weight gets the number of ways the hero and the villain can hold their classes at the same
+     * time, which makes some matchups more likely than others (card removal)
+     */
+    pub fn weight(&self, hero: HandClass, villain: HandClass) -> f64 {
+        self.weights[hero.index() * CLASSES + villain.index()]
+    }
+}
+
+/**
+ * frequencies gets how often a range, like "22+, A2+, KTs+", plays every class. Frequencies are
+ * indexed like HandClass::index.
+ */
+pub fn frequencies(range: &Range) -> Vec<f64> {
+    let mut frequencies = vec![0.0; CLASSES];
+    for combo in range.combos() {
+        let class = HandClass::of(&combo.cards[0], &combo.cards[1]);
+        frequencies[class.index()] += combo.weight / class.combos().len() as f64;
+    }
+    frequencies
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EquityTable;
+    use super::HandClass;
+    use super::super::card::Card;
+
+    #[test]
+    fn test_hand_classes() {
+        let classes = HandClass::all();
+        assert_eq!(classes.len(), 169);
+        assert_eq!(classes.iter().map(|class| class.combos().len()).sum::<usize>(), 1326);
+        assert_eq!(classes[0].to_string(), "AA");
+        assert_eq!(classes[1].to_string(), "AKs");
+        assert_eq!(classes[13].to_string(), "AKo");
+        assert_eq!(classes[168].to_string(), "22");
+
+        for class in &classes {
+            assert_eq!(HandClass::parse(&class.to_string()), Some(*class));
+            for combo in class.combos() {
+                assert_eq!(HandClass::of(&combo[1], &combo[0]), *class);
+            }
+        }
+        assert_eq!(HandClass::of(&Card::new('7', 'H'), &Card::new('T', 'H')).to_string(), "T7s");
+        assert_eq!(HandClass::parse("AAs"), None);
+        assert_eq!(HandClass::parse("AK"), None);
+    }
+
+    #[test]
+    fn test_weights() {
+        let table = EquityTable::from_fn(|_, _| 0.5);
+        let class = |text| HandClass::parse(text).unwrap();
+        assert_eq!(table.weight(class("AA"), class("AA")), 6.0);
+        assert_eq!(table.weight(class("AA"), class("KK")), 36.0);
+        assert_eq!(table.weight(class("AA"), class("AKs")), 12.0);
+        assert_eq!(table.weight(class("AKs"), class("AA")), 12.0);
+        assert_eq!(table.weight(class("AKo"), class("AKo")), 84.0);
+    }
+}
@@ -0,0 +1,529 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// External imports
+use std::fmt;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Internal imports
+use bot::{Action, Bot, Chips, Event, HandResult, LegalActions, Observation};
+use card::Card;
+use types::Street;
+
+/**
+ * Message is a line of the protocol between a table and a bot. The bot says hello when it
+ * connects and answers every request with an action; the table tells it everything else:
+ *
+ *   hello NAME                           bot introduces itself
+ *   deal SEAT BUTTON BIG_BLIND C1 C2     a hand starts, with the seat and hole cards of the bot
+ *   board C1 C2 C3 [C4 [C5]]             cards on the board when a street is dealt
+ *   event SEAT ACTION                    a player acted, like "event 1 raise 300"
+ *   request POT S0 S1 B0 B1 LEGAL...     the bot must act, given the pot, stacks and bets of
+ *                                        both seats and the legal actions: "check",
+ *                                        "call N" and "raise MIN MAX"
+ *   ACTION                               answer of the bot, like "call" or "raise 300"
+ *   showdown SEAT C1 C2                  cards shown by a seat
+ *   result W0 W1                         chips won by every seat, the hand is over
+ *   end                                  the match is over
+ */
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Message {
+    Hello(String),
+    Deal {
+        seat: usize,
+        button: usize,
+        big_blind: Chips,
+        hole: [Card; 2],
+    },
+    Board(Vec<Card>),
+    Event { seat: usize, action: Action },
+    Request {
+        pot: Chips,
+        stacks: [Chips; 2],
+        bets: [Chips; 2],
+        legal: LegalActions,
+    },
+    Action(Action),
+    Showdown { seat: usize, hole: [Card; 2] },
+    Result([i64; 2]),
+    End,
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Message::Hello(ref name) => write!(f, "hello {}", name),
+            Message::Deal { seat, button, big_blind, hole } => {
+                write!(f, "deal {} {} {} {} {}", seat, button, big_blind, hole[0], hole[1])
+            }
+            Message::Board(ref board) => {
+                write!(f, "board")?;
+                for card in board {
+                    write!(f, " {}", card)?;
+                }
+                Ok(())
+            }
+            Message::Event { seat, action } => write!(f, "event {} {}", seat, action),
+            Message::Request { pot, stacks, bets, legal } => {
+                write!(f,
+                       "request {} {} {} {} {}",
+                       pot,
+                       stacks[0],
+                       stacks[1],
+                       bets[0],
+                       bets[1])?;
+                if legal.check {
+                    write!(f, " check")?;
+                }
+                if let Some(call) = legal.call {
+                    write!(f, " call {}", call)?;
+                }
+                if let Some((min, max)) = legal.raise {
+                    write!(f, " raise {} {}", min, max)?;
+                }
+                Ok(())
+            }
+            Message::Action(action) => write!(f, "{}", action),
+            Message::Showdown { seat, hole } => {
+                write!(f, "showdown {} {} {}", seat, hole[0], hole[1])
+            }
+            Message::Result(winnings) => write!(f, "result {} {}", winnings[0], winnings[1]),
+            Message::End => write!(f, "end"),
+        }
+    }
+}
+
+impl Message {
+    /**
+     * parse reads a message written like its Display
+     */
+    pub fn parse(line: &str) -> Result<Message, String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let invalid = || format!("Invalid message: {}", line.trim());
+        let number = |word: &str| word.parse::<u64>().map_err(|_| invalid());
+        let seat = |word: &str| match word {
+            "0" => Ok(0),
+            "1" => Ok(1),
+            _ => Err(invalid()),
+        };
+        let cards = |words: &[&str]| -> Result<Vec<Card>, String> {
+            words.iter().map(|word| Card::parse(word).ok_or_else(invalid)).collect()
+        };
+
+        match (words.first().cloned(), words.len()) {
+            (Some("hello"), len) if len >= 2 => Ok(Message::Hello(words[1..].join(" "))),
+            (Some("deal"), 6) => {
+                let hole = cards(&words[4..])?;
+                Ok(Message::Deal {
+                    seat: seat(words[1])?,
+                    button: seat(words[2])?,
+                    big_blind: number(words[3])?,
+                    hole: [hole[0], hole[1]],
+                })
+            }
+            (Some("board"), len) if Street::from_board_size(len - 1).is_some() => {
+                Ok(Message::Board(cards(&words[1..])?))
+            }
+            (Some("event"), _) if words.len() >= 3 => {
+                Ok(Message::Event {
+                    seat: seat(words[1])?,
+                    action: Action::parse(&words[2..].join(" ")).ok_or_else(invalid)?,
+                })
+            }
+            (Some("request"), len) if len >= 6 => {
+                let mut legal = LegalActions::default();
+                let mut rest = &words[6..];
+                while !rest.is_empty() {
+                    match rest {
+                        ["check", ..] => {
+                            legal.check = true;
+                            rest = &rest[1..];
+                        }
+                        ["call", amount, ..] => {
+                            legal.call = Some(number(amount)?);
+                            rest = &rest[2..];
+                        }
+                        ["raise", min, max, ..] => {
+                            legal.raise = Some((number(min)?, number(max)?));
+                            rest = &rest[3..];
+                        }
+                        _ => return Err(invalid()),
+                    }
+                }
+                Ok(Message::Request {
+                    pot: number(words[1])?,
+                    stacks: [number(words[2])?, number(words[3])?],
+                    bets: [number(words[4])?, number(words[5])?],
+                    legal,
+                })
+            }
+            (Some("showdown"), 4) => {
+                let hole = cards(&words[2..])?;
+                Ok(Message::Showdown {
+                    seat: seat(words[1])?,
+                    hole: [hole[0], hole[1]],
+                })
+            }
+            (Some("result"), 3) => {
+                let winning = |word: &str| word.parse::<i64>().map_err(|_| invalid());
+                Ok(Message::Result([winning(words[1])?, winning(words[2])?]))
+            }
+            (Some("end"), 1) => Ok(Message::End),
+            _ => Action::parse(line).map(Message::Action).ok_or_else(invalid),
+        }
+    }
+}
+
+/**
+ * RemoteBot is a bot playing through the protocol, on a TCP connection or as a subprocess talking
+ * on its standard input and output. A bot that doesn't answer on time folds, or checks when it
+ * can, and a disconnected bot does so for the rest of the match.
+ */
+pub struct RemoteBot {
+    name: String,
+    writer: Box<dyn Write + Send>,
+    lines: Receiver<String>, // Lines read by a background thread, so reads can time out
+    timeout: Duration,
+    connected: bool,
+    timeouts: usize,
+    unanswered: usize, // Requests sent without an answer yet, the late ones included
+    events: usize, // Events of the hand already sent
+    board: usize, // Cards of the board already sent
+    child: Option<Child>,
+}
+
+impl RemoteBot {
+    /**
+     * new starts talking to a bot, which must say hello before the timeout
+     */
+    pub fn new<R: Read + Send + 'static>(reader: R,
+                                         writer: Box<dyn Write + Send>,
+                                         timeout: Duration)
+                                         -> Result<RemoteBot, String> {
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(reader).lines() {
+                match line {
+                    Ok(line) => {
+                        if sender.send(line).is_err() {
+                            break;
+                        }
+                    }
+                    Err(_) => break,
+                }
+            }
+        });
+
+        let name = match lines.recv_timeout(timeout) {
+            Ok(line) => match Message::parse(&line)? {
+                Message::Hello(name) => name,
+                _ => return Err(format!("Expected hello, got: {}", line)),
+            },
+            Err(RecvTimeoutError::Timeout) => return Err("The bot didn't say hello".to_string()),
+            Err(RecvTimeoutError::Disconnected) => return Err("The bot disconnected".to_string()),
+        };
+
+        Ok(RemoteBot {
+            name,
+            writer,
+            lines,
+            timeout,
+            connected: true,
+            timeouts: 0,
+            unanswered: 0,
+            events: 0,
+            board: 0,
+            child: None,
+        })
+    }
+
+    /**
+     * connect starts talking to a bot connected to the table
+     */
+    pub fn connect(stream: TcpStream, timeout: Duration) -> Result<RemoteBot, String> {
+        stream.set_nodelay(true).map_err(|error| error.to_string())?;
+        let reader = stream.try_clone().map_err(|error| error.to_string())?;
+        RemoteBot::new(reader, Box::new(stream), timeout)
+    }
+
+    /**
+     * spawn runs a bot as a subprocess. The first word of the command is the program and the rest
+     * are its arguments.
+     */
+    pub fn spawn(command: &str, timeout: Duration) -> Result<RemoteBot, String> {
+        let words: Vec<&str> = command.split_whitespace().collect();
+        if words.is_empty() {
+            return Err("Empty command".to_string());
+        }
+        let mut child = Command::new(words[0])
+            .args(&words[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|error| format!("{}: {}", words[0], error))?;
+
+        let writer = child.stdin.take().expect("The standard input is piped");
+        let reader = child.stdout.take().expect("The standard output is piped");
+        match RemoteBot::new(reader, Box::new(writer), timeout) {
+            Ok(mut bot) => {
+                bot.child = Some(child);
+                Ok(bot)
+            }
+            Err(error) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                Err(error)
+            }
+        }
+    }
+
+    /**
+     * timeouts gets how many times the bot didn't answer on time
+     */
+    pub fn timeouts(&self) -> usize {
+        self.timeouts
+    }
+
+    /**
+     * is_connected tells if the bot is still connected
+     */
+    pub fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    /**
+     * close tells the bot the match is over. It's done when the bot is dropped too.
+     */
+    pub fn close(&mut self) {
+        self.send(&Message::End);
+        self.connected = false;
+    }
+
+    /**
+     * send writes a message, and marks the bot as disconnected if it can't
+     */
+    fn send(&mut self, message: &Message) {
+        if !self.connected {
+            return;
+        }
+        if writeln!(self.writer, "{}", message).and_then(|_| self.writer.flush()).is_err() {
+            self.connected = false;
+        }
+    }
+
+    /**
+     * send_history writes the events and the cards of the board not sent yet, the board of every
+     * street before its first event
+     */
+    fn send_history(&mut self, history: &[Event], board: &[Card]) {
+        for event in &history[self.events.min(history.len())..] {
+            self.send_board(&board[..event.street.board_size().min(board.len())]);
+            self.send(&Message::Event {
+                seat: event.seat,
+                action: event.action,
+            });
+        }
+        self.events = history.len();
+        self.send_board(board);
+    }
+
+    fn send_board(&mut self, board: &[Card]) {
+        if board.len() > self.board {
+            self.send(&Message::Board(board.to_vec()));
+            self.board = board.len();
+        }
+    }
+}
+
+impl Bot for RemoteBot {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn hand_started(&mut self, observation: &Observation) {
+        self.events = 0;
+        self.board = 0;
+        self.send(&Message::Deal {
+            seat: observation.seat,
+            button: observation.button,
+            big_blind: observation.big_blind,
+            hole: observation.hole,
+        });
+    }
+
+    fn act(&mut self, observation: &Observation) -> Action {
+        self.send_history(&observation.history, &observation.board);
+        self.send(&Message::Request {
+            pot: observation.pot,
+            stacks: observation.stacks,
+            bets: observation.bets,
+            legal: observation.legal,
+        });
+        if !self.connected {
+            return Action::Fold;
+        }
+
+        // Every request gets an answer in order, so the ones to requests that timed out are
+        // skipped
+        self.unanswered += 1;
+        let deadline = Instant::now() + self.timeout;
+        loop {
+            let wait = deadline.saturating_duration_since(Instant::now());
+            match self.lines.recv_timeout(wait) {
+                Ok(line) => {
+                    self.unanswered -= 1;
+                    if self.unanswered > 0 {
+                        continue;
+                    }
+                    return match Message::parse(&line) {
+                        Ok(Message::Action(action)) => action,
+                        _ => Action::Fold,
+                    };
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    self.timeouts += 1;
+                    return Action::Fold;
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    self.connected = false;
+                    return Action::Fold;
+                }
+            }
+        }
+    }
+
+    fn hand_over(&mut self, _seat: usize, result: &HandResult) {
+        self.send_history(&result.history, &result.board);
+        for (seat, hole) in result.shown.iter().enumerate() {
+            if let Some(hole) = *hole {
+                self.send(&Message::Showdown { seat, hole });
+            }
+        }
+        self.send(&Message::Result(result.winnings));
+    }
+}
+
+impl Drop for RemoteBot {
+    fn drop(&mut self) {
+        self.close();
+        if let Some(mut child) = self.child.take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+/**
+ * run_client plays with a bot on a table through the protocol until the match is over or the
+ * table disconnects. The bot is told a hand started with its seat, the button, the big blind and
+ * its hole cards; the pot and the stacks are known once it's asked to act.
+ */
+pub fn run_client<R: BufRead, W: Write>(bot: &mut dyn Bot,
+                                        reader: R,
+                                        mut writer: W)
+                                        -> Result<(), String> {
+    let io_error = |error: ::std::io::Error| error.to_string();
+    writeln!(writer, "{}", Message::Hello(bot.name())).map_err(io_error)?;
+    writer.flush().map_err(io_error)?;
+
+    let mut hand: Option<Observation> = None;
+    let mut shown = [None, None];
+    for line in reader.lines() {
+        let line = line.map_err(io_error)?;
+        let message = Message::parse(&line)?;
+        if let Message::Deal { seat, button, big_blind, hole } = message {
+            let observation = Observation {
+                seat,
+                button,
+                hole,
+                board: Vec::new(),
+                street: Street::Preflop,
+                pot: 0,
+                stacks: [0, 0],
+                bets: [0, 0],
+                big_blind,
+                history: Vec::new(),
+                legal: LegalActions::default(),
+            };
+            bot.hand_started(&observation);
+            hand = Some(observation);
+            shown = [None, None];
+            continue;
+        }
+        if message == Message::End {
+            return Ok(());
+        }
+
+        let observation = hand.as_mut().ok_or_else(|| format!("No hand was dealt: {}", line))?;
+        match message {
+            Message::Board(board) => {
+                observation.street = Street::from_board_size(board.len())
+                    .expect("Boards are parsed with a valid size");
+                observation.board = board;
+            }
+            Message::Event { seat, action } => {
+                observation.history.push(Event {
+                    seat,
+                    street: observation.street,
+                    action,
+                })
+            }
+            Message::Request { pot, stacks, bets, legal } => {
+                observation.pot = pot;
+                observation.stacks = stacks;
+                observation.bets = bets;
+                observation.legal = legal;
+                let action = bot.act(observation);
+                writeln!(writer, "{}", action).map_err(io_error)?;
+                writer.flush().map_err(io_error)?;
+            }
+            Message::Showdown { seat, hole } => shown[seat] = Some(hole),
+            Message::Result(winnings) => {
+                let result = HandResult {
+                    button: observation.button,
+                    board: observation.board.clone(),
+                    shown,
+                    winnings,
+                    history: observation.history.clone(),
+                };
+                bot.hand_over(observation.seat, &result);
+            }
+            _ => return Err(format!("Unexpected message: {}", line)),
+        }
+    }
+    Ok(())
+}
+
+/**
+ * connect plays with a bot on the table listening on an address
+ */
+pub fn connect<A: ToSocketAddrs>(bot: &mut dyn Bot, address: A) -> Result<(), String> {
+    let stream = TcpStream::connect(address).map_err(|error| error.to_string())?;
+    stream.set_nodelay(true).map_err(|error| error.to_string())?;
+    let reader = stream.try_clone().map_err(|error| error.to_string())?;
+    run_client(bot, BufReader::new(reader), stream)
+}
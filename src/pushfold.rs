@@ -0,0 +1,242 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// Internal imports
+use preflop;
+use preflop::EquityTable;
+use preflop::HandClass;
+
+/**
+ * Spot is the structure of a heads-up hand where the small blind can only shove or fold and the
+ * big blind call or fold. Amounts are in big blinds.
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Spot {
+    pub stack: f64, // Effective stack at the start of the hand, the blind and the ante included
+    pub small_blind: f64,
+    pub ante: f64, // Ante posted by every player
+}
+
+impl Spot {
+    /**
+     * new creates a spot with a small blind of half a big blind and no antes
+     */
+    pub fn new(stack: f64) -> Spot {
+        Spot {
+            stack,
+            small_blind: 0.5,
+            ante: 0.0,
+        }
+    }
+
+    /**
+     * small_blind_fold_ev gets what the small blind loses folding: its blind and its ante
+     */
+    pub fn small_blind_fold_ev(&self) -> f64 {
+        -(self.small_blind + self.ante)
+    }
+
+    /**
+     * big_blind_fold_ev gets what the big blind loses folding to a shove
+     */
+    pub fn big_blind_fold_ev(&self) -> f64 {
+        -(1.0 + self.ante)
+    }
+
+    /**
+     * showdown_ev gets what a player wins on average when both players are all-in
+     */
+    fn showdown_ev(&self, equity: f64) -> f64 {
+        (2.0 * equity - 1.0) * self.stack
+    }
+}
+
+/**
+ * Solution holds the equilibrium frequencies of shoving and calling of every hand class and their
+ * expected values, indexed by the position of the class on the 13x13 grid. Expected values are in
+ * big blinds, relative to the stacks at the start of the hand.
+ */
+#[derive(Clone, Debug)]
+pub struct Solution {
+    pub spot: Spot,
+    pub shove: Vec<f64>,
+    pub call: Vec<f64>,
+    pub shove_ev: Vec<f64>,
+    pub call_ev: Vec<f64>,
+}
+
+impl Solution {
+    /**
+     * shove_percentage gets the percentage of the hole cards the small blind shoves
+     */
+    pub fn shove_percentage(&self) -> f64 {
+        percentage(&self.shove)
+    }
+
+    /**
+     * call_percentage gets the percentage of the hole cards the big blind calls with
+     */
+    pub fn call_percentage(&self) -> f64 {
+        percentage(&self.call)
+    }
+
+    /**
+     * small_blind_ev gets the expected value of the small blind playing the solution
+     */
+    pub fn small_blind_ev(&self) -> f64 {
+        let total: f64 = HandClass::all()
+            .iter()
+            .map(|class| {
+                let i = class.index();
+                let ev = self.shove[i] * self.shove_ev[i] +
+                         (1.0 - self.shove[i]) * self.spot.small_blind_fold_ev();
+                ev * class.combos().len() as f64
+            })
+            .sum();
+        total / 1326.0
+    }
+}
+
+/**
+ * solve finds the equilibrium by fictitious play: on every iteration each player takes the best
+ * response to the average strategy of the opponent, and the average strategies converge to a Nash
+ * equilibrium. A few hundred iterations are enough for charts.
+ */
+pub fn solve(table: &EquityTable, spot: &Spot, iterations: usize) -> Solution {
+    let matchups = Matchups::new(table);
+    let mut shove = vec![1.0; preflop::CLASSES];
+    let mut call = vec![1.0; preflop::CLASSES];
+
+    for iteration in 1..iterations + 1 {
+        let shove_ev = matchups.shove_evs(spot, &call);
+        let call_ev = matchups.call_evs(spot, &shove);
+        let step = 1.0 / (iteration + 1) as f64;
+        for i in 0..preflop::CLASSES {
+            let best_shove = if shove_ev[i] > spot.small_blind_fold_ev() { 1.0 } else { 0.0 };
+            let best_call = if call_ev[i] > spot.big_blind_fold_ev() { 1.0 } else { 0.0 };
+            shove[i] += (best_shove - shove[i]) * step;
+            call[i] += (best_call - call[i]) * step;
+        }
+    }
+
+    Solution {
+        spot: *spot,
+        shove_ev: matchups.shove_evs(spot, &call),
+        call_ev: matchups.call_evs(spot, &shove),
+        shove,
+        call,
+    }
+}
+
+/**
+ * Matchups keeps the equities and the weights of the table by class index, as they are read many
+ * times on every iteration
+ */
+struct Matchups {
+    equities: Vec<f64>,
+    weights: Vec<f64>,
+}
+
+impl Matchups {
+    fn new(table: &EquityTable) -> Matchups {
+        let classes = HandClass::all();
+        let mut equities = Vec::with_capacity(preflop::CLASSES * preflop::CLASSES);
+        let mut weights = Vec::with_capacity(preflop::CLASSES * preflop::CLASSES);
+        for hero in &classes {
+            for villain in &classes {
+                equities.push(table.equity(*hero, *villain));
+                weights.push(table.weight(*hero, *villain));
+            }
+        }
+        Matchups { equities, weights }
+    }
+
+    /**
+     * shove_evs gets the expected value of shoving every class against a calling strategy
+     */
+    fn shove_evs(&self, spot: &Spot, call: &[f64]) -> Vec<f64> {
+        (0..preflop::CLASSES)
+            .map(|hero| {
+                let mut total = 0.0;
+                let mut weights = 0.0;
+                for (villain, called) in call.iter().enumerate() {
+                    let position = hero * preflop::CLASSES + villain;
+                    let weight = self.weights[position];
+                    total += weight * (1.0 - called) * (1.0 + spot.ante);
+                    total += weight * called * spot.showdown_ev(self.equities[position]);
+                    weights += weight;
+                }
+                total / weights
+            })
+            .collect()
+    }
+
+    /**
+     * call_evs gets the expected value of calling a shove with every class against a shoving
+     * strategy. Classes that never face a shove are indifferent.
+     */
+    fn call_evs(&self, spot: &Spot, shove: &[f64]) -> Vec<f64> {
+        (0..preflop::CLASSES)
+            .map(|hero| {
+                let mut total = 0.0;
+                let mut weights = 0.0;
+                for (villain, shoved) in shove.iter().enumerate() {
+                    let position = hero * preflop::CLASSES + villain;
+                    let weight = self.weights[position] * shoved;
+                    total += weight * spot.showdown_ev(self.equities[position]);
+                    weights += weight;
+                }
+                if weights > 0.0 { total / weights } else { spot.big_blind_fold_ev() }
+            })
+            .collect()
+    }
+}
+
+/**
+ * percentage gets the percentage of the 1326 hole cards played given the frequency of every class
+ */
+fn percentage(frequencies: &[f64]) -> f64 {
+    let combos: f64 = HandClass::all()
+        .iter()
+        .map(|class| frequencies[class.index()] * class.combos().len() as f64)
+        .sum();
+    100.0 * combos / 1326.0
+}
+
+/**
+ * grid writes a value of every class on a 13x13 grid, like the usual charts: the Aces on the first
+ * row, pairs on the diagonal, suited hands above it and offsuit hands below it
+ */
+pub fn grid<F: Fn(f64) -> String>(values: &[f64], format: F) -> String {
+    let mut text = String::new();
+    for row in 0..13 {
+        let cells: Vec<String> = (0..13)
+            .map(|column| {
+                let index = row * 13 + column;
+                format!("{:<3} {:>5}", HandClass::from_index(index), format(values[index]))
+            })
+            .collect();
+        text.push_str(cells.join("  ").trim_end());
+        text.push('\n');
+    }
+    text
+}
@@ -0,0 +1,273 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// Internal imports
+use card;
+use card::Card;
+use card::CardValue;
+
+/**
+ * Combo is a pair of hole cards with the probability of an opponent holding them
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct Combo {
+    pub cards: [Card; 2],
+    pub weight: f64,
+}
+
+impl Combo {
+    /**
+     * conflicts checks if any of the cards of the combo is one of the given cards
+     */
+    pub fn conflicts(&self, cards: &[&Card]) -> bool {
+        cards.iter().any(|card| **card == self.cards[0] || **card == self.cards[1])
+    }
+}
+
+/**
+ * Range is a weighted list of the hole cards that a player can have
+ */
+#[derive(Clone, Debug, Default)]
+pub struct Range {
+    combos: Vec<Combo>,
+}
+
+impl Range {
+    /**
+     * new creates an empty range
+     */
+    pub fn new() -> Range {
+        Range { combos: Vec::new() }
+    }
+
+    /**
+     * uniform creates a range with the 1326 possible hole cards, all of them with the same weight
+     */
+    pub fn uniform() -> Range {
+        let deck = card::deck();
+        let mut range = Range::new();
+        for i in 0..deck.len() {
+            for j in i + 1..deck.len() {
+                range.add(deck[j], deck[i], 1.0);
+            }
+        }
+        range
+    }
+
+    /**
+     * add inserts a combo on the range
+     */
+    pub fn add(&mut self, first: Card, second: Card, weight: f64) {
+        self.combos.push(Combo {
+            cards: [first, second],
+            weight,
+        });
+    }
+
+    /**
+     * set changes the weight of a combo, inserting it if it's not on the range yet
+     */
+    pub fn set(&mut self, first: Card, second: Card, weight: f64) {
+        let position = self.combos.iter().position(|combo| {
+            combo.cards.contains(&first) && combo.cards.contains(&second)
+        });
+
+        match position {
+            Some(position) => self.combos[position].weight = weight,
+            None => self.add(first, second, weight),
+        }
+    }
+
+    /**
+     * parse reads a range written on the usual notation: a list of hands separated by commas or
+     * spaces, like "QQ+, AKs, ATo+, 76s, AhKh". A trailing "+" raises the lowest card up to the
+     * highest one (or up to Aces for pairs), "s" and "o" restrict the hand to suited or offsuit
+     * combos and "random" means any hand. Every item can have a weight, like "AKo:0.5".
+     */
+    pub fn parse(text: &str) -> Result<Range, String> {
+        let mut range = Range::new();
+
+        let items = text.split(|c: char| c == ',' || c.is_whitespace());
+        for item in items.filter(|item| !item.is_empty()) {
+            let (hand, weight) = match item.find(':') {
+                Some(colon) => {
+                    let weight = item[colon + 1..]
+                        .parse::<f64>()
+                        .map_err(|_| format!("Invalid weight on '{}'", item))?;
+                    (&item[..colon], weight)
+                }
+                None => (item, 1.0),
+            };
+
+            let combos = parse_hand(hand).ok_or_else(|| format!("Invalid hand '{}'", item))?;
+            for (first, second) in combos {
+                range.set(first, second, weight);
+            }
+        }
+
+        Ok(range)
+    }
+
+    /**
+     * combos gets all the combos of the range
+     */
+    pub fn combos(&self) -> &[Combo] {
+        &self.combos
+    }
+
+    /**
+     * len gets the number of combos of the range
+     */
+    pub fn len(&self) -> usize {
+        self.combos.len()
+    }
+
+    /**
+     * is_empty checks if the range has no combos
+     */
+    pub fn is_empty(&self) -> bool {
+        self.combos.is_empty()
+    }
+}
+
+/**
+ * parse_hand gets the combos of a single hand of a range, without its weight
+ */
+fn parse_hand(hand: &str) -> Option<Vec<(Card, Card)>> {
+    if hand.eq_ignore_ascii_case("random") || hand.eq_ignore_ascii_case("any") {
+        return Some(Range::uniform()
+            .combos
+            .iter()
+            .map(|combo| (combo.cards[0], combo.cards[1]))
+            .collect());
+    }
+
+    // Specific hole cards, like AhKh
+    let chars: Vec<char> = hand.chars().collect();
+    if chars.len() == 4 {
+        let first: String = chars[..2].iter().collect();
+        let second: String = chars[2..].iter().collect();
+        if let (Some(first), Some(second)) = (Card::parse(&first), Card::parse(&second)) {
+            return if first == second { None } else { Some(vec![(first, second)]) };
+        }
+    }
+
+    if chars.len() < 2 || chars.len() > 4 {
+        return None;
+    }
+    let first = CardValue::from_symbol(chars[0])?.rank();
+    let second = CardValue::from_symbol(chars[1])?.rank();
+    let (high, low) = if first >= second { (first, second) } else { (second, first) };
+    let pair = high == low;
+
+    let mut suited = true;
+    let mut offsuit = true;
+    let mut plus = false;
+    for c in &chars[2..] {
+        match *c {
+            's' | 'S' if suited && offsuit && !plus => offsuit = false,
+            'o' | 'O' if suited && offsuit && !plus => suited = false,
+            '+' if !plus => plus = true,
+            _ => return None,
+        }
+    }
+    if pair && !(suited && offsuit) {
+        return None;
+    }
+
+    // Ranks of the lowest card covered by the hand
+    let lows: Vec<usize> = if !plus {
+        vec![low]
+    } else if pair {
+        (low..card::VALUES.len()).collect()
+    } else {
+        (low..high).collect()
+    };
+
+    let mut combos = Vec::new();
+    for low in lows {
+        let high = if pair { low } else { high };
+        for first_suit in &card::SUITS {
+            for second_suit in &card::SUITS {
+                let wanted = if pair {
+                    first_suit < second_suit
+                } else if first_suit == second_suit {
+                    suited
+                } else {
+                    offsuit
+                };
+
+                if wanted {
+                    combos.push((Card {
+                                     value: card::VALUES[high],
+                                     suit: *first_suit,
+                                 },
+                                 Card {
+                                     value: card::VALUES[low],
+                                     suit: *second_suit,
+                                 }));
+                }
+            }
+        }
+    }
+
+    Some(combos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Range;
+    use super::super::card::Card;
+
+    #[test]
+    fn test_uniform_range() {
+        let range = Range::uniform();
+        assert_eq!(range.len(), 1326);
+
+        let ace = Card::new('A', 'S');
+        let blocked = range.combos().iter().filter(|combo| combo.conflicts(&[&ace])).count();
+        assert_eq!(blocked, 51);
+    }
+
+    #[test]
+    fn test_parse_range() {
+        let count = |text: &str| Range::parse(text).unwrap().len();
+        assert_eq!(count("QQ+"), 18);
+        assert_eq!(count("AKs"), 4);
+        assert_eq!(count("AKo"), 12);
+        assert_eq!(count("KA"), 16);
+        assert_eq!(count("ATs+"), 16);
+        assert_eq!(count("QQ+, AK, AhKh"), 34);
+        assert_eq!(count("random"), 1326);
+
+        let range = Range::parse("AKs:0.5 AsKs").unwrap();
+        assert_eq!(range.len(), 4);
+        let weights: Vec<f64> = range.combos().iter().map(|combo| combo.weight).collect();
+        assert_eq!(weights.iter().filter(|weight| **weight == 1.0).count(), 1);
+
+        assert!(Range::parse("AX").is_err());
+        assert!(Range::parse("QQs").is_err());
+        assert!(Range::parse("AKs:x").is_err());
+        assert!(Range::parse("AhAh").is_err());
+        assert!(Range::parse("A€").is_err());
+    }
+}
@@ -0,0 +1,229 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// Internal imports
+use card::Card;
+use card::CardSuit;
+
+/**
+ * Palette chooses the ANSI colours of the suits on a terminal
+ */
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Palette {
+    Plain, // No colours
+    TwoColor, // Red hearts and diamonds
+    FourColor, // Red hearts, blue diamonds and green clubs
+}
+
+impl Palette {
+    /**
+     * from_name gets the palette with the given name: "plain", "two" or "four"
+     */
+    pub fn from_name(name: &str) -> Option<Palette> {
+        match name {
+            "plain" => Some(Palette::Plain),
+            "two" => Some(Palette::TwoColor),
+            "four" => Some(Palette::FourColor),
+            _ => None,
+        }
+    }
+
+    /**
+     * color gets the ANSI escape sequence that starts the colour of a suit, if any
+     */
+    pub fn color(&self, suit: CardSuit) -> Option<&'static str> {
+        match (*self, suit) {
+            (Palette::TwoColor, CardSuit::Hearts) |
+            (Palette::TwoColor, CardSuit::Diamonds) |
+            (Palette::FourColor, CardSuit::Hearts) => Some(RED),
+            (Palette::FourColor, CardSuit::Diamonds) => Some(BLUE),
+            (Palette::FourColor, CardSuit::Clubs) => Some(GREEN),
+            _ => None,
+        }
+    }
+
+    /**
+     * paint wraps a text on the colour of a suit
+     */
+    fn paint(&self, text: &str, suit: CardSuit) -> String {
+        match self.color(suit) {
+            Some(color) => format!("{}{}{}", color, text, RESET),
+            None => text.to_string(),
+        }
+    }
+}
+
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const BLUE: &str = "\x1b[34m";
+const RESET: &str = "\x1b[0m";
+
+/**
+ * Slot is a place for a card on an ASCII art view
+ */
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+enum Slot {
+    Card(Card),
+    Hidden, // Dealt face down
+    Empty, // Not dealt yet
+}
+
+/**
+ * card writes a card with the symbol of its suit, like "A♠", coloured by the palette
+ */
+pub fn card(card: &Card, palette: Palette) -> String {
+    palette.paint(&format!("{:#}", card), card.suit)
+}
+
+/**
+ * cards writes some cards separated by spaces, like "A♠ K♥"
+ */
+pub fn cards(cards: &[Card], palette: Palette) -> String {
+    cards.iter().map(|current| card(current, palette)).collect::<Vec<String>>().join(" ")
+}
+
+/**
+ * table draws the board and the hole cards of the players as ASCII art. Undealt board cards are
+ * drawn as empty slots and unknown hole cards face down.
+ */
+pub fn table(board: &[Card], players: &[(&str, Option<[Card; 2]>)], palette: Palette) -> String {
+    let label = "Board";
+    let width = players.iter()
+        .map(|&(name, _)| name.chars().count())
+        .chain(Some(label.len()))
+        .max()
+        .unwrap() + 2;
+
+    let board: Vec<Slot> = (0..5)
+        .map(|i| board.get(i).map(|card| Slot::Card(*card)).unwrap_or(Slot::Empty))
+        .collect();
+    let mut output = draw(label, width, &board, palette);
+
+    for &(name, hole) in players {
+        let hole = match hole {
+            Some(hole) => vec![Slot::Card(hole[0]), Slot::Card(hole[1])],
+            None => vec![Slot::Hidden, Slot::Hidden],
+        };
+        output.push('\n');
+        output.push_str(&draw(name, width, &hole, palette));
+    }
+    output
+}
+
+/**
+ * draw draws a row of cards side by side, with a label on the left of the middle line
+ */
+fn draw(label: &str, width: usize, slots: &[Slot], palette: Palette) -> String {
+    let mut output = String::new();
+    for line in 0..5 {
+        let prefix = if line == 2 { label } else { "" };
+        let cards: Vec<String> = slots.iter().map(|slot| art_line(slot, line, palette)).collect();
+        output.push_str(&format!("{:width$}{}", prefix, cards.join(" "), width = width));
+        output.push('\n');
+    }
+    output
+}
+
+/**
+ * art_line gets a line of a card drawn as ASCII art, like
+ *  .-----.
+ *  |A    |
+ *  |  ♠  |
+ *  |    A|
+ *  '-----'
+ */
+fn art_line(slot: &Slot, line: usize, palette: Palette) -> String {
+    match (*slot, line) {
+        (Slot::Empty, 0) => String::from(".- - -."),
+        (Slot::Empty, 4) => String::from("'- - -'"),
+        (Slot::Empty, _) => String::from(":     :"),
+        (_, 0) => String::from(".-----."),
+        (_, 4) => String::from("'-----'"),
+        (Slot::Hidden, _) => String::from("|#####|"),
+        (Slot::Card(card), line) => {
+            let value = card.value.symbol().to_string();
+            let inner = match line {
+                1 => format!("{}    ", palette.paint(&value, card.suit)),
+                2 => format!("  {}  ", palette.paint(&card.suit.glyph().to_string(), card.suit)),
+                _ => format!("    {}", palette.paint(&value, card.suit)),
+            };
+            format!("|{}|", inner)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::card;
+    use super::cards;
+    use super::table;
+    use super::Palette;
+    use super::super::card::Card;
+    use super::super::hand::Hand;
+
+    #[test]
+    fn test_cards() {
+        let ace = Card::new('A', 'S');
+        let king = Card::new('K', 'H');
+        assert_eq!(format!("{} {:#}", ace, king), "As K♥");
+        assert_eq!(cards(&[ace, king], Palette::Plain), "A♠ K♥");
+        assert_eq!(cards(&[ace, king], Palette::TwoColor), "A♠ \x1b[31mK♥\x1b[0m");
+        assert_eq!(card(&Card::new('2', 'D'), Palette::FourColor), "\x1b[34m2♦\x1b[0m");
+        assert_eq!(card(&Card::new('2', 'C'), Palette::FourColor), "\x1b[32m2♣\x1b[0m");
+        assert_eq!(card(&Card::new('2', 'C'), Palette::TwoColor), "2♣");
+
+        let cards = [Card::new('K', 'S'),
+                     Card::new('Q', 'S'),
+                     Card::new('J', 'S'),
+                     Card::new('T', 'S')];
+        let hand = Hand::from_cards(&[&ace, &cards[0], &cards[1], &cards[2], &cards[3]]);
+        assert_eq!(format!("{}", hand), "AKQJT");
+        assert_eq!(format!("{:#}", hand), "A♠ K♠ Q♠ J♠ T♠");
+        assert_eq!(format!("[{:>4}]", ace), "[  As]");
+    }
+
+    #[test]
+    fn test_table() {
+        let board = [Card::new('Q', 'H'), Card::new('J', 'H'), Card::new('2', 'C')];
+        let hole = [Card::new('A', 'H'), Card::new('K', 'H')];
+        let view = table(&board, &[("Alice", Some(hole)), ("Bob", None)], Palette::Plain);
+
+        assert_eq!(view,
+                   "       .-----. .-----. .-----. .- - -. .- - -.\n\
+                    \x20      |Q    | |J    | |2    | :     : :     :\n\
+                    Board  |  ♥  | |  ♥  | |  ♣  | :     : :     :\n\
+                    \x20      |    Q| |    J| |    2| :     : :     :\n\
+                    \x20      '-----' '-----' '-----' '- - -' '- - -'\n\
+                    \n\
+                    \x20      .-----. .-----.\n\
+                    \x20      |A    | |K    |\n\
+                    Alice  |  ♥  | |  ♥  |\n\
+                    \x20      |    A| |    K|\n\
+                    \x20      '-----' '-----'\n\
+                    \n\
+                    \x20      .-----. .-----.\n\
+                    \x20      |#####| |#####|\n\
+                    Bob    |#####| |#####|\n\
+                    \x20      |#####| |#####|\n\
+                    \x20      '-----' '-----'\n");
+    }
+}
@@ -0,0 +1,437 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// External imports
+use std::cmp::Ordering;
+use std::fmt;
+
+// Internal imports
+use types::Street;
+use card::Card;
+use hand::Hand;
+use description;
+use history;
+use history::Action;
+use history::ActionKind;
+use history::Amount;
+use history::HandHistory;
+
+/**
+ * Replayer steps through a recorded hand action by action, keeping the stacks and the pot
+ */
+pub struct Replayer<'a> {
+    hand: &'a HandHistory,
+    position: usize, // Index of the next action
+    street: Street,
+    stacks: Vec<Amount>, // By seat, on the order of the hand
+    contributed: Vec<Amount>, // Chips put on the pot during the whole hand
+    committed: Vec<Amount>, // Chips put on the pot during the current street
+    folded: Vec<bool>,
+    mucked: Vec<bool>,
+    shown: Vec<Option<[Card; 2]>>,
+}
+
+/**
+ * Pot is a main or side pot, as computed by the replayer
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pot {
+    pub amount: Amount, // Before the rake
+    pub players: Vec<String>, // Players who can win the pot
+    pub winners: Vec<String>, // Empty if the winner can not be known
+    pub description: Option<String>, // Winning hand, if the pot went to showdown
+}
+
+/**
+ * Discrepancy is a difference between the recorded hand and the computation of the crate
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub enum Discrepancy {
+    TotalPot { recorded: Amount, computed: Amount },
+    Stack { player: String, stack: Amount, contributed: Amount }, // Bets over the stack
+    Winnings { player: String, recorded: Amount, computed: Amount },
+}
+
+/**
+ * Replay is the result of replaying a whole hand
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Replay {
+    pub pots: Vec<Pot>,
+    pub stacks: Vec<(String, Amount)>, // Final stacks after the recorded collections
+    pub winnings: Vec<(String, Amount)>, // Computed winnings of every player, after the rake
+    pub verified: bool, // Every pot was awarded without unknown hole cards
+    pub discrepancies: Vec<Discrepancy>,
+}
+
+impl fmt::Display for Discrepancy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Discrepancy::TotalPot { recorded, computed } => {
+                write!(f,
+                       "total pot is {} but the players put {}",
+                       history::format_amount(recorded),
+                       history::format_amount(computed))
+            }
+            Discrepancy::Stack { ref player, stack, contributed } => {
+                write!(f,
+                       "{} put {} on the pot with a stack of {}",
+                       player,
+                       history::format_amount(contributed),
+                       history::format_amount(stack))
+            }
+            Discrepancy::Winnings { ref player, recorded, computed } => {
+                write!(f,
+                       "{} collected {} but should have won {}",
+                       player,
+                       history::format_amount(recorded),
+                       history::format_amount(computed))
+            }
+        }
+    }
+}
+
+impl<'a> Replayer<'a> {
+    /**
+     * Initializes a replayer before the first action of a hand
+     */
+    pub fn new(hand: &'a HandHistory) -> Replayer<'a> {
+        let players = hand.seats.len();
+        Replayer {
+            hand,
+            position: 0,
+            street: Street::Preflop,
+            stacks: hand.seats.iter().map(|seat| seat.stack).collect(),
+            contributed: vec![0; players],
+            committed: vec![0; players],
+            folded: vec![false; players],
+            mucked: vec![false; players],
+            shown: vec![None; players],
+        }
+    }
+
+    /**
+     * step applies the next action of the hand and returns it, or None at the end of the hand
+     */
+    pub fn step(&mut self) -> Option<&'a Action> {
+        let action = self.hand.actions.get(self.position)?;
+        self.position += 1;
+
+        if action.street != self.street {
+            self.street = action.street;
+            self.committed = vec![0; self.committed.len()];
+        }
+
+        let player = match self.hand.seats.iter().position(|seat| seat.player == action.player) {
+            Some(player) => player,
+            None => return Some(action),
+        };
+
+        match action.kind {
+            ActionKind::Ante(amount) => {
+                self.stacks[player] -= amount;
+                self.contributed[player] += amount;
+            }
+            ActionKind::SmallAndBigBlind(amount) => {
+                // The small blind is dead, only the big blind counts as a bet
+                self.pay(player, amount);
+                self.committed[player] -= amount - amount.min(self.hand.big_blind);
+            }
+            ActionKind::SmallBlind(amount) |
+            ActionKind::BigBlind(amount) |
+            ActionKind::Call(amount) |
+            ActionKind::Bet(amount) => self.pay(player, amount),
+            ActionKind::Raise { to, .. } => {
+                let amount = to - self.committed[player];
+                self.pay(player, amount);
+            }
+            ActionKind::UncalledBet(amount) => self.pay(player, -amount),
+            ActionKind::Collect(amount) => self.stacks[player] += amount,
+            ActionKind::Fold => self.folded[player] = true,
+            ActionKind::Muck => self.mucked[player] = true,
+            ActionKind::Show(cards) => self.shown[player] = Some(cards),
+            ActionKind::Check => (),
+        }
+
+        Some(action)
+    }
+
+    /**
+     * street gets the street of the last action
+     */
+    pub fn street(&self) -> Street {
+        self.street
+    }
+
+    /**
+     * stack gets the chips of a player that are not on the pot
+     */
+    pub fn stack(&self, player: &str) -> Option<Amount> {
+        self.index(player).map(|index| self.stacks[index])
+    }
+
+    /**
+     * pot gets the chips put on the pot by all the players
+     */
+    pub fn pot(&self) -> Amount {
+        self.contributed.iter().sum()
+    }
+
+    /**
+     * is_folded tells if a player has folded
+     */
+    pub fn is_folded(&self, player: &str) -> bool {
+        self.index(player).map(|index| self.folded[index]).unwrap_or(false)
+    }
+
+    /**
+     * pots splits the chips on the pot on the main pot and the side pots, and awards them to the
+     * best hands shown
+     */
+    pub fn pots(&self) -> Vec<Pot> {
+        let mut levels: Vec<Amount> = self.contributed.clone();
+        levels.sort();
+        levels.dedup();
+
+        let mut pots: Vec<Pot> = Vec::new();
+        let mut previous = 0;
+        for &level in levels.iter().filter(|&&level| level > 0) {
+            let amount: Amount = self.contributed
+                .iter()
+                .map(|&chips| chips.min(level) - chips.min(previous))
+                .sum();
+            let players: Vec<usize> = (0..self.contributed.len())
+                .filter(|&index| !self.folded[index] && self.contributed[index] >= level)
+                .collect();
+            previous = level;
+
+            // Levels reached only by folded players are dead money of the previous pot
+            let names: Vec<String> = players.iter()
+                .map(|&index| self.hand.seats[index].player.clone())
+                .collect();
+            match pots.last_mut() {
+                Some(pot) if names.is_empty() || pot.players == names => {
+                    pot.amount += amount;
+                    continue;
+                }
+                _ => (),
+            }
+
+            let (winners, description) = self.award(&players);
+            pots.push(Pot {
+                amount,
+                players: names,
+                winners,
+                description,
+            });
+        }
+        pots
+    }
+
+    /**
+     * finish applies the remaining actions and verifies the results of the hand
+     */
+    pub fn finish(mut self) -> Replay {
+        while self.step().is_some() {}
+
+        let mut discrepancies = Vec::new();
+        let computed = self.pot();
+        if computed != self.hand.total_pot {
+            discrepancies.push(Discrepancy::TotalPot {
+                recorded: self.hand.total_pot,
+                computed,
+            });
+        }
+        for (index, seat) in self.hand.seats.iter().enumerate() {
+            if self.contributed[index] > seat.stack {
+                discrepancies.push(Discrepancy::Stack {
+                    player: seat.player.clone(),
+                    stack: seat.stack,
+                    contributed: self.contributed[index],
+                });
+            }
+        }
+
+        let pots = self.pots();
+        let verified = pots.iter().all(|pot| !pot.winners.is_empty());
+        let winnings = self.winnings(&pots);
+        if verified {
+            for &(ref player, computed) in &winnings {
+                let recorded = self.collected(player);
+                if recorded != computed {
+                    discrepancies.push(Discrepancy::Winnings {
+                        player: player.clone(),
+                        recorded,
+                        computed,
+                    });
+                }
+            }
+        }
+
+        Replay {
+            stacks: self.hand
+                .seats
+                .iter()
+                .zip(self.stacks.iter())
+                .map(|(seat, &stack)| (seat.player.clone(), stack))
+                .collect(),
+            pots,
+            winnings,
+            verified,
+            discrepancies,
+        }
+    }
+
+    /**
+     * pay moves chips of a player to the pot
+     */
+    fn pay(&mut self, player: usize, amount: Amount) {
+        self.stacks[player] -= amount;
+        self.contributed[player] += amount;
+        self.committed[player] += amount;
+    }
+
+    /**
+     * index gets the position of a player on the seats of the hand
+     */
+    fn index(&self, player: &str) -> Option<usize> {
+        self.hand.seats.iter().position(|seat| seat.player == player)
+    }
+
+    /**
+     * award finds the winners of a pot between some players. Players who mucked or did not show
+     * their cards give up the pot.
+     */
+    fn award(&self, players: &[usize]) -> (Vec<String>, Option<String>) {
+        if players.len() == 1 {
+            return (vec![self.hand.seats[players[0]].player.clone()], None);
+        }
+
+        let hero = self.hand.hero.as_ref().and_then(|hero| self.index(hero));
+        let holes: Vec<(usize, [Card; 2])> = players.iter()
+            .filter(|&&index| !self.mucked[index])
+            .filter_map(|&index| {
+                let cards = match self.shown[index] {
+                    Some(cards) => Some(cards),
+                    None if Some(index) == hero => self.hand.hero_cards,
+                    None => None,
+                };
+                cards.map(|cards| (index, cards))
+            })
+            .collect();
+        if holes.is_empty() || self.hand.board.len() < 3 {
+            return (Vec::new(), None);
+        }
+
+        let hands: Vec<(usize, Hand)> = holes.iter()
+            .map(|&(index, ref cards)| {
+                let mut all: Vec<&Card> = cards.iter().collect();
+                all.extend(self.hand.board.iter());
+                (index, Hand::from_cards(&all))
+            })
+            .collect();
+
+        let mut best: Vec<&(usize, Hand)> = Vec::new();
+        for hand in &hands {
+            match best.first().map(|best| hand.1.cmp(&best.1)) {
+                Some(Ordering::Less) => (),
+                Some(Ordering::Equal) => best.push(hand),
+                _ => best = vec![hand],
+            }
+        }
+
+        let description = description::describe(&best[0].1);
+        (best.iter().map(|&&(index, _)| self.hand.seats[index].player.clone()).collect(),
+         Some(description))
+    }
+
+    /**
+     * winnings splits the pots between their winners. The rake is taken from the main pot first
+     * and the odd chips go to the first winners to the left of the button.
+     */
+    fn winnings(&self, pots: &[Pot]) -> Vec<(String, Amount)> {
+        // Tournament chips are whole numbers, cash games are split in cents
+        let unit = if self.hand.tournament.is_some() { 100 } else { 1 };
+        let mut rake = self.hand.rake;
+        let mut winnings: Vec<(String, Amount)> = Vec::new();
+
+        for pot in pots.iter().filter(|pot| !pot.winners.is_empty()) {
+            let raked = rake.min(pot.amount);
+            rake -= raked;
+            let amount = pot.amount - raked;
+
+            let mut winners: Vec<(u32, &String)> = pot.winners
+                .iter()
+                .map(|player| (self.hand.seat(player).map(|seat| seat.number).unwrap_or(0), player))
+                .collect();
+            let button = self.hand.button;
+            winners.sort_by_key(|&(number, _)| (number <= button, number));
+
+            let count = winners.len() as Amount;
+            let share = amount / (count * unit) * unit;
+            let mut odd = (amount - share * count) / unit;
+            for (_, player) in winners {
+                let mut won = share;
+                if odd > 0 {
+                    won += unit;
+                    odd -= 1;
+                }
+                match winnings.iter_mut().find(|&&mut (ref name, _)| name == player) {
+                    Some(&mut (_, ref mut total)) => *total += won,
+                    None => winnings.push((player.clone(), won)),
+                }
+            }
+        }
+
+        // Players who collected anything are listed even if they should not
+        for action in &self.hand.actions {
+            if let ActionKind::Collect(_) = action.kind {
+                if !winnings.iter().any(|(player, _)| *player == action.player) {
+                    winnings.push((action.player.clone(), 0));
+                }
+            }
+        }
+        winnings
+    }
+
+    /**
+     * collected gets the amount collected by a player on the recorded hand
+     */
+    fn collected(&self, player: &str) -> Amount {
+        self.hand
+            .actions
+            .iter()
+            .filter(|action| action.player == player)
+            .filter_map(|action| match action.kind {
+                ActionKind::Collect(amount) => Some(amount),
+                _ => None,
+            })
+            .sum()
+    }
+}
+
+/**
+ * replay replays a whole hand and verifies its results
+ */
+pub fn replay(hand: &HandHistory) -> Replay {
+    Replayer::new(hand).finish()
+}
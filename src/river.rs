@@ -0,0 +1,610 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// Internal imports
+use card::Card;
+use cfr::Variant;
+use cfr::normalize;
+use hand::Hand;
+use range::Combo;
+use range::Range;
+
+/**
+ * RiverAction is a decision of a player on the river. Amounts are the whole bet of the player on
+ * the street.
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RiverAction {
+    Check,
+    Bet(f64),
+    Fold,
+    Call,
+    Raise(f64),
+}
+
+/**
+ * RiverSpot is the situation at the start of the river. The first range is the one of the player
+ * out of position, who acts first. Bets and raises are fractions of the pot, where a raise is a
+ * call followed by a bet of a fraction of the pot after calling.
+ */
+#[derive(Clone, Debug)]
+pub struct RiverSpot {
+    pub board: [Card; 5],
+    pub ranges: [Range; 2],
+    pub pot: f64,
+    pub stack: f64, // Effective stack behind
+    pub bet_sizes: Vec<f64>,
+    pub max_raises: usize,
+}
+
+/**
+ * Kind tells if a node of the tree is a decision or the end of the hand
+ */
+#[derive(Clone, Debug)]
+enum Kind {
+    Decision {
+        player: usize,
+        actions: Vec<RiverAction>,
+        children: Vec<usize>,
+        regrets: Vec<Vec<f64>>, // Regret of every action for every combo of the player
+        strategy_sum: Vec<Vec<f64>>,
+    },
+    Fold(usize), // Player who folded
+    Showdown,
+}
+
+/**
+ * Node is a point of the betting tree with the bets of both players
+ */
+#[derive(Clone, Debug)]
+struct Node {
+    kind: Kind,
+    bets: [f64; 2],
+}
+
+/**
+ * Mode is the strategy used by a player when evaluating the tree
+ */
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    Average,
+    BestResponse,
+}
+
+/**
+ * RiverSolver computes an equilibrium of a river spot with counterfactual regret minimization.
+ * Every node of the tree keeps the regrets of all the combos of the player at once, so an
+ * iteration walks the tree once for every player.
+ */
+pub struct RiverSolver {
+    spot: RiverSpot,
+    variant: Variant,
+    nodes: Vec<Node>,
+    combos: [Vec<Combo>; 2],
+    ranks: [Vec<u32>; 2], // Strength of every combo on the board, higher is better
+    order: [Vec<usize>; 2], // Combos sorted by strength, the weakest first
+    same: [Vec<Option<usize>>; 2], // Position of the same cards on the range of the opponent
+    iterations: u64,
+}
+
+impl RiverSolver {
+    /**
+     * new builds the betting tree of the spot. Combos sharing cards with the board are removed
+     * from the ranges.
+     */
+    pub fn new(spot: RiverSpot, variant: Variant) -> Result<RiverSolver, String> {
+        let board: Vec<&Card> = spot.board.iter().collect();
+        let combos = [playable(&spot.ranges[0], &board), playable(&spot.ranges[1], &board)];
+        if combos.iter().any(|combos| combos.is_empty()) {
+            return Err("Every range needs a combo that does not conflict with the board"
+                .to_string());
+        }
+        if spot.pot <= 0.0 || spot.stack < 0.0 {
+            return Err("The pot must be positive and the stack can not be negative".to_string());
+        }
+
+        // Rank every combo against all the others, so showdowns only compare numbers
+        let mut all: Vec<(usize, usize, Hand)> = Vec::new();
+        for (player, player_combos) in combos.iter().enumerate() {
+            for (i, combo) in player_combos.iter().enumerate() {
+                let mut cards = board.clone();
+                cards.push(&combo.cards[0]);
+                cards.push(&combo.cards[1]);
+                all.push((player, i, Hand::from_cards(&cards)));
+            }
+        }
+        all.sort_by(|a, b| a.2.cmp(&b.2));
+        let mut ranks = [vec![0; combos[0].len()], vec![0; combos[1].len()]];
+        let mut rank = 0;
+        for i in 0..all.len() {
+            if i > 0 && all[i].2 != all[i - 1].2 {
+                rank += 1;
+            }
+            ranks[all[i].0][all[i].1] = rank;
+        }
+
+        let sorted = |player: usize| {
+            let mut order: Vec<usize> = (0..combos[player].len()).collect();
+            order.sort_by_key(|i| ranks[player][*i]);
+            order
+        };
+        let order = [sorted(0), sorted(1)];
+        let same = [same_combos(&combos[0], &combos[1]), same_combos(&combos[1], &combos[0])];
+
+        let mut solver = RiverSolver {
+            spot,
+            variant,
+            nodes: Vec::new(),
+            combos,
+            ranks,
+            order,
+            same,
+            iterations: 0,
+        };
+        solver.build([0.0, 0.0], 0, 0);
+        Ok(solver)
+    }
+
+    /**
+     * iterations gets the number of iterations run
+     */
+    pub fn iterations(&self) -> u64 {
+        self.iterations
+    }
+
+    /**
+     * train runs a number of iterations
+     */
+    pub fn train(&mut self, iterations: u64) {
+        for _ in 0..iterations {
+            self.iterations += 1;
+            for player in 0..2 {
+                let reaches = self.initial_reaches();
+                self.traverse(0, player, &reaches);
+            }
+        }
+    }
+
+    /**
+     * combos gets the combos of a player, in the order used by strategy and ev
+     */
+    pub fn combos(&self, player: usize) -> &[Combo] {
+        &self.combos[player]
+    }
+
+    /**
+     * root gets the first node of the tree, where the player out of position acts
+     */
+    pub fn root(&self) -> usize {
+        0
+    }
+
+    /**
+     * player gets who acts on a node, or None if the hand is over
+     */
+    pub fn player(&self, node: usize) -> Option<usize> {
+        match self.nodes[node].kind {
+            Kind::Decision { player, .. } => Some(player),
+            _ => None,
+        }
+    }
+
+    /**
+     * actions gets the actions available on a node
+     */
+    pub fn actions(&self, node: usize) -> &[RiverAction] {
+        match self.nodes[node].kind {
+            Kind::Decision { ref actions, .. } => actions,
+            _ => &[],
+        }
+    }
+
+    /**
+     * child gets the node reached taking an action, by its position on actions
+     */
+    pub fn child(&self, node: usize, action: usize) -> usize {
+        match self.nodes[node].kind {
+            Kind::Decision { ref children, .. } => children[action],
+            _ => panic!("The hand is over on node {}", node),
+        }
+    }
+
+    /**
+     * strategy gets the average frequency of every action for every combo of the player acting on
+     * a node, which converges to an equilibrium
+     */
+    pub fn strategy(&self, node: usize) -> Vec<Vec<f64>> {
+        match self.nodes[node].kind {
+            Kind::Decision { ref strategy_sum, .. } => {
+                (0..strategy_sum[0].len())
+                    .map(|i| normalize(strategy_sum.iter().map(|sum| sum[i]).collect()))
+                    .collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /**
+     * ev gets the expected value of every combo of a player when both play the average strategy:
+     * the share of the pot it wins minus what it puts on the river
+     */
+    pub fn ev(&self, player: usize) -> Vec<f64> {
+        let reaches = self.initial_reaches();
+        let values = self.evaluate(0, player, &reaches, Mode::Average);
+        let compatible = self.compatible(player, &reaches[1 - player]);
+        values.iter()
+            .zip(&compatible)
+            .map(|(value, weight)| if *weight > 0.0 { value / weight } else { 0.0 })
+            .collect()
+    }
+
+    /**
+     * value gets the expected value of a player over its whole range. The values of both players
+     * add up to the pot.
+     */
+    pub fn value(&self, player: usize) -> f64 {
+        self.range_value(player, Mode::Average)
+    }
+
+    /**
+     * exploitability gets how much a best response wins on average over the equilibrium value,
+     * as the mean of both players. It's zero on a Nash equilibrium.
+     */
+    pub fn exploitability(&self) -> f64 {
+        let responses = self.range_value(0, Mode::BestResponse) +
+                        self.range_value(1, Mode::BestResponse);
+        (responses - self.spot.pot) / 2.0
+    }
+
+    /**
+     * build adds a decision node and its subtree, returning its position
+     */
+    fn build(&mut self, bets: [f64; 2], player: usize, raises: usize) -> usize {
+        let position = self.nodes.len();
+        self.nodes.push(Node {
+            kind: Kind::Showdown,
+            bets,
+        });
+
+        let opponent = 1 - player;
+        let mut actions = Vec::new();
+        let mut children = Vec::new();
+        if bets[opponent] > bets[player] {
+            actions.push(RiverAction::Fold);
+            children.push(self.terminal(Kind::Fold(player), bets));
+            let mut called = bets;
+            called[player] = bets[opponent];
+            actions.push(RiverAction::Call);
+            children.push(self.terminal(Kind::Showdown, called));
+
+            if raises < self.spot.max_raises && bets[opponent] < self.spot.stack {
+                let pot = self.spot.pot + 2.0 * bets[opponent];
+                for to in self.sizes(bets[opponent], pot) {
+                    let mut raised = bets;
+                    raised[player] = to;
+                    actions.push(RiverAction::Raise(to));
+                    children.push(self.build(raised, opponent, raises + 1));
+                }
+            }
+        } else {
+            actions.push(RiverAction::Check);
+            children.push(if player == 0 {
+                self.build(bets, 1, raises)
+            } else {
+                self.terminal(Kind::Showdown, bets)
+            });
+            for to in self.sizes(0.0, self.spot.pot) {
+                let mut bet = bets;
+                bet[player] = to;
+                actions.push(RiverAction::Bet(to));
+                children.push(self.build(bet, opponent, raises));
+            }
+        }
+
+        let combos = self.combos[player].len();
+        self.nodes[position].kind = Kind::Decision {
+            player,
+            regrets: vec![vec![0.0; combos]; actions.len()],
+            strategy_sum: vec![vec![0.0; combos]; actions.len()],
+            actions,
+            children,
+        };
+        position
+    }
+
+    /**
+     * terminal adds a node where the hand is over
+     */
+    fn terminal(&mut self, kind: Kind, bets: [f64; 2]) -> usize {
+        self.nodes.push(Node { kind, bets });
+        self.nodes.len() - 1
+    }
+
+    /**
+     * sizes gets the whole bets for every allowed size on top of a call, capped at the stack and
+     * without repetitions
+     */
+    fn sizes(&self, call: f64, pot: f64) -> Vec<f64> {
+        let mut sizes: Vec<f64> = Vec::new();
+        for size in &self.spot.bet_sizes {
+            let to = (call + size * pot).min(self.spot.stack);
+            if to > call && !sizes.contains(&to) {
+                sizes.push(to);
+            }
+        }
+        sizes
+    }
+
+    /**
+     * initial_reaches gets the weights of the combos of both players
+     */
+    fn initial_reaches(&self) -> [Vec<f64>; 2] {
+        [self.combos[0].iter().map(|combo| combo.weight).collect(),
+         self.combos[1].iter().map(|combo| combo.weight).collect()]
+    }
+
+    /**
+     * traverse gets the counterfactual values of the combos of the traverser on a node, updating
+     * its regrets and its average strategy
+     */
+    fn traverse(&mut self, node: usize, traverser: usize, reaches: &[Vec<f64>; 2]) -> Vec<f64> {
+        let (player, children, strategy) = match self.nodes[node].kind {
+            Kind::Decision { player, ref children, ref regrets, .. } => {
+                (player, children.clone(), current_strategy(regrets))
+            }
+            _ => return self.payoffs(node, traverser, &reaches[1 - traverser]),
+        };
+
+        if player != traverser {
+            let mut values = vec![0.0; self.combos[traverser].len()];
+            for (child, probabilities) in children.iter().zip(&strategy) {
+                let mut next = reaches.clone();
+                for (reach, probability) in next[player].iter_mut().zip(probabilities) {
+                    *reach *= probability;
+                }
+                let child_values = self.traverse(*child, traverser, &next);
+                for (value, child_value) in values.iter_mut().zip(child_values) {
+                    *value += child_value;
+                }
+            }
+            return values;
+        }
+
+        let mut action_values = Vec::with_capacity(children.len());
+        for (child, probabilities) in children.iter().zip(&strategy) {
+            let mut next = reaches.clone();
+            for (reach, probability) in next[player].iter_mut().zip(probabilities) {
+                *reach *= probability;
+            }
+            action_values.push(self.traverse(*child, traverser, &next));
+        }
+        let combos = self.combos[traverser].len();
+        let values: Vec<f64> = (0..combos)
+            .map(|i| (0..children.len()).map(|a| strategy[a][i] * action_values[a][i]).sum())
+            .collect();
+
+        let weight = match self.variant {
+            Variant::Vanilla => 1.0,
+            Variant::Plus => self.iterations as f64,
+        };
+        let variant = self.variant;
+        let node = &mut self.nodes[node];
+        if let Kind::Decision { ref mut regrets, ref mut strategy_sum, .. } = node.kind {
+            for a in 0..children.len() {
+                for i in 0..combos {
+                    regrets[a][i] += action_values[a][i] - values[i];
+                    if variant == Variant::Plus {
+                        regrets[a][i] = regrets[a][i].max(0.0);
+                    }
+                    strategy_sum[a][i] += weight * reaches[traverser][i] * strategy[a][i];
+                }
+            }
+        }
+        values
+    }
+
+    /**
+     * evaluate gets the counterfactual values of the combos of a player on a node when the
+     * opponent plays the average strategy and the player the average strategy or the best
+     * response
+     */
+    fn evaluate(&self,
+                node: usize,
+                player: usize,
+                reaches: &[Vec<f64>; 2],
+                mode: Mode)
+                -> Vec<f64> {
+        let (actor, children) = match self.nodes[node].kind {
+            Kind::Decision { player, ref children, .. } => (player, children),
+            _ => return self.payoffs(node, player, &reaches[1 - player]),
+        };
+
+        let strategy = self.strategy(node);
+        let child_values: Vec<Vec<f64>> = children.iter()
+            .enumerate()
+            .map(|(a, child)| {
+                let mut next = reaches.clone();
+                if actor != player || mode == Mode::Average {
+                    for (i, reach) in next[actor].iter_mut().enumerate() {
+                        *reach *= strategy[i][a];
+                    }
+                }
+                self.evaluate(*child, player, &next, mode)
+            })
+            .collect();
+
+        (0..self.combos[player].len())
+            .map(|i| {
+                let values = child_values.iter().map(|values| values[i]);
+                if actor != player {
+                    values.sum()
+                } else if mode == Mode::BestResponse {
+                    values.fold(f64::NEG_INFINITY, f64::max)
+                } else {
+                    values.zip(&strategy[i]).map(|(value, probability)| value * probability).sum()
+                }
+            })
+            .collect()
+    }
+
+    /**
+     * range_value gets the expected value of a player over its whole range
+     */
+    fn range_value(&self, player: usize, mode: Mode) -> f64 {
+        let reaches = self.initial_reaches();
+        let values = self.evaluate(0, player, &reaches, mode);
+        let compatible = self.compatible(player, &reaches[1 - player]);
+        let total: f64 = compatible.iter().zip(&reaches[player]).map(|(c, r)| c * r).sum();
+        values.iter().zip(&reaches[player]).map(|(v, r)| v * r).sum::<f64>() / total
+    }
+
+    /**
+     * payoffs gets the counterfactual values of the combos of a player at the end of the hand,
+     * given the reach of the combos of the opponent
+     */
+    fn payoffs(&self, node: usize, player: usize, opponent_reach: &[f64]) -> Vec<f64> {
+        let bets = self.nodes[node].bets;
+        let opponent = 1 - player;
+        match self.nodes[node].kind {
+            Kind::Fold(folder) => {
+                let payoff = if folder == player {
+                    -bets[player]
+                } else {
+                    self.spot.pot + bets[opponent]
+                };
+                self.compatible(player, opponent_reach).iter().map(|reach| payoff * reach).collect()
+            }
+            _ => {
+                let compatible = self.compatible(player, opponent_reach);
+                let wins = self.sweep(player, opponent_reach, false);
+                let losses = self.sweep(player, opponent_reach, true);
+                (0..compatible.len())
+                    .map(|i| {
+                        let ties = compatible[i] - wins[i] - losses[i];
+                        wins[i] * (self.spot.pot + bets[player]) - losses[i] * bets[player] +
+                        ties * self.spot.pot / 2.0
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /**
+     * compatible gets, for every combo of a player, the reach of the combos of the opponent that
+     * do not share cards with it
+     */
+    fn compatible(&self, player: usize, opponent_reach: &[f64]) -> Vec<f64> {
+        let opponent = 1 - player;
+        let mut cards = [0.0; 52];
+        for (combo, reach) in self.combos[opponent].iter().zip(opponent_reach) {
+            cards[combo.cards[0].index()] += reach;
+            cards[combo.cards[1].index()] += reach;
+        }
+        let total: f64 = opponent_reach.iter().sum();
+
+        self.combos[player]
+            .iter()
+            .zip(&self.same[player])
+            .map(|(combo, same)| {
+                // The same cards were subtracted twice
+                total - cards[combo.cards[0].index()] - cards[combo.cards[1].index()] +
+                same.map_or(0.0, |same| opponent_reach[same])
+            })
+            .collect()
+    }
+
+    /**
+     * sweep gets, for every combo of a player, the reach of the compatible combos of the
+     * opponent that it beats, or that beat it. Both ranges are walked once sorted by strength.
+     */
+    fn sweep(&self, player: usize, opponent_reach: &[f64], losses: bool) -> Vec<f64> {
+        let opponent = 1 - player;
+        let mut player_order = self.order[player].clone();
+        let mut opponent_order = self.order[opponent].clone();
+        if losses {
+            player_order.reverse();
+            opponent_order.reverse();
+        }
+        let passed = |opponent_rank: u32, rank: u32| {
+            if losses { opponent_rank > rank } else { opponent_rank < rank }
+        };
+
+        let mut result = vec![0.0; self.combos[player].len()];
+        let mut cards = [0.0; 52];
+        let mut total = 0.0;
+        let mut next = 0;
+        for i in player_order {
+            let rank = self.ranks[player][i];
+            while next < opponent_order.len() &&
+                  passed(self.ranks[opponent][opponent_order[next]], rank) {
+                let combo = &self.combos[opponent][opponent_order[next]];
+                let reach = opponent_reach[opponent_order[next]];
+                total += reach;
+                cards[combo.cards[0].index()] += reach;
+                cards[combo.cards[1].index()] += reach;
+                next += 1;
+            }
+            let combo = &self.combos[player][i];
+            result[i] = total - cards[combo.cards[0].index()] - cards[combo.cards[1].index()];
+        }
+        result
+    }
+}
+
+/**
+ * playable gets the combos of a range that do not share cards with the board
+ */
+fn playable(range: &Range, board: &[&Card]) -> Vec<Combo> {
+    range.combos()
+        .iter()
+        .filter(|combo| combo.weight > 0.0 && !combo.conflicts(board))
+        .cloned()
+        .collect()
+}
+
+/**
+ * same_combos finds the position of every combo on another list of combos
+ */
+fn same_combos(combos: &[Combo], others: &[Combo]) -> Vec<Option<usize>> {
+    combos.iter()
+        .map(|combo| {
+            others.iter().position(|other| {
+                other.cards.contains(&combo.cards[0]) && other.cards.contains(&combo.cards[1])
+            })
+        })
+        .collect()
+}
+
+/**
+ * current_strategy gets the frequency of every action for every combo by regret matching
+ */
+fn current_strategy(regrets: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let combos = regrets[0].len();
+    let mut strategy = vec![vec![0.0; combos]; regrets.len()];
+    for i in 0..combos {
+        let positive: Vec<f64> = regrets.iter().map(|regret| regret[i].max(0.0)).collect();
+        for (a, probability) in normalize(positive).into_iter().enumerate() {
+            strategy[a][i] = probability;
+        }
+    }
+    strategy
+}
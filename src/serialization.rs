@@ -0,0 +1,294 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// External imports
+use std::fmt;
+use serde::de;
+use serde::de::Visitor;
+use serde::ser::SerializeStruct;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+
+// Internal imports
+use card::Card;
+use card::CardSuit;
+use card::CardValue;
+use consts;
+use hand::Hand;
+use hand::HandType;
+
+/**
+ * All the hand types, in ascending order
+ */
+const HAND_TYPES: [HandType; 9] = [HandType::HighCard,
+                                   HandType::Pair,
+                                   HandType::TwoPair,
+                                   HandType::ThreeOfAKind,
+                                   HandType::Straight,
+                                   HandType::Flush,
+                                   HandType::FullHouse,
+                                   HandType::FourOfAKind,
+                                   HandType::StraightFlush];
+
+/**
+ * Cards are written as their value and a lowercase suit, like "As" or "Td"
+ */
+impl Serialize for Card {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Card {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Card, D::Error> {
+        deserializer.deserialize_str(SymbolVisitor {
+            expected: "a card like \"As\"",
+            parse: Card::parse,
+        })
+    }
+}
+
+/**
+ * Values are written as their symbol, like "A" or "T", and read in any case like cards
+ */
+impl Serialize for CardValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.symbol().to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for CardValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<CardValue, D::Error> {
+        deserializer.deserialize_str(SymbolVisitor {
+            expected: "a card value like \"A\"",
+            parse: |text| single_char(text).and_then(CardValue::from_symbol),
+        })
+    }
+}
+
+/**
+ * Suits are written as their lowercase symbol, like "s" or "d", and read in any case like cards
+ */
+impl Serialize for CardSuit {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.symbol().to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for CardSuit {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<CardSuit, D::Error> {
+        deserializer.deserialize_str(SymbolVisitor {
+            expected: "a card suit like \"s\"",
+            parse: |text| single_char(text).and_then(CardSuit::from_symbol),
+        })
+    }
+}
+
+/**
+ * Hand types are written as their names, like "PAIR" or "FLUSH"
+ */
+impl Serialize for HandType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for HandType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<HandType, D::Error> {
+        deserializer.deserialize_str(SymbolVisitor {
+            expected: "a hand type like \"PAIR\"",
+            parse: |text| HAND_TYPES.iter().cloned().find(|hand_type| text == hand_type.name()),
+        })
+    }
+}
+
+/**
+ * Hands are written with their type and their five cards, like
+ * {"hand_type": "PAIR", "cards": ["Ts", "Td", "Ks", "Qh", "Jc"]}. A hand borrows its cards, so it
+ * is read back as an OwnedHand.
+ */
+impl<'a> Serialize for Hand<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut hand = serializer.serialize_struct("Hand", 2)?;
+        hand.serialize_field("hand_type", &self.hand_type())?;
+        hand.serialize_field("cards", &self.cards())?;
+        hand.end()
+    }
+}
+
+/**
+ * OwnedHand is a hand that owns its cards, so it can be read from the format written for a Hand.
+ * Reading fails unless the cards build a hand of the written type.
+ */
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct OwnedHand {
+    hand_type: HandType,
+    cards: Vec<Card>,
+}
+
+impl OwnedHand {
+    /**
+     * new copies the cards of a hand
+     */
+    pub fn new(hand: &Hand) -> OwnedHand {
+        OwnedHand {
+            hand_type: hand.hand_type(),
+            cards: hand.cards().iter().map(|card| **card).collect(),
+        }
+    }
+
+    /**
+     * hand_type gets the type of the hand
+     */
+    pub fn hand_type(&self) -> HandType {
+        self.hand_type
+    }
+
+    /**
+     * cards gets the 5 cards of the hand on the order used to compare hands
+     */
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    /**
+     * hand gets a Hand that borrows the cards, to compare it with other hands
+     */
+    pub fn hand(&self) -> Hand<'_> {
+        let cards: Vec<&Card> = self.cards.iter().collect();
+        Hand::from_cards(&cards)
+    }
+}
+
+impl<'de> Deserialize<'de> for OwnedHand {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<OwnedHand, D::Error> {
+        #[derive(Deserialize)]
+        struct Fields {
+            hand_type: HandType,
+            cards: Vec<Card>,
+        }
+
+        let fields = Fields::deserialize(deserializer)?;
+        if fields.cards.len() != consts::HAND_SIZE {
+            return Err(de::Error::invalid_length(fields.cards.len(), &"the 5 cards of a hand"));
+        }
+        let cards: Vec<&Card> = fields.cards.iter().collect();
+        if cards.iter().enumerate().any(|(i, card)| cards[..i].contains(card)) {
+            return Err(de::Error::custom("repeated cards on a hand"));
+        }
+        let hand = OwnedHand::new(&Hand::from_cards(&cards));
+        if hand.hand_type != fields.hand_type {
+            return Err(de::Error::custom(format!("the cards build a {}, not a {}",
+                                                 hand.hand_type.name(),
+                                                 fields.hand_type.name())));
+        }
+        Ok(hand)
+    }
+}
+
+/**
+ * SymbolVisitor reads a type written as a string
+ */
+struct SymbolVisitor<T> {
+    expected: &'static str,
+    parse: fn(&str) -> Option<T>,
+}
+
+impl<'de, T> Visitor<'de> for SymbolVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.expected)
+    }
+
+    fn visit_str<E: de::Error>(self, text: &str) -> Result<T, E> {
+        (self.parse)(text).ok_or_else(|| E::invalid_value(de::Unexpected::Str(text), &self))
+    }
+}
+
+/**
+ * single_char gets the only character of a text, if it has exactly one
+ */
+fn single_char(text: &str) -> Option<char> {
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(symbol), None) => Some(symbol),
+        _ => None,
+    }
+}
+
+/**
+ * Alternative encoding of cards as their index, from 0 for the Two of Clubs to 51 for the Ace of
+ * Spades. Use it with #[serde(with = "poker::serialization::numeric")] on a Card field, or with
+ * "poker::serialization::numeric::cards" on a Vec<Card> field.
+ */
+pub mod numeric {
+    use serde::de;
+    use serde::Deserialize;
+    use serde::Deserializer;
+    use serde::Serializer;
+
+    use card;
+    use card::Card;
+
+    pub fn serialize<S: Serializer>(card: &Card, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(card.index() as u8)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Card, D::Error> {
+        from_index(u8::deserialize(deserializer)?)
+    }
+
+    /**
+     * from_index gets the card of an index, if valid
+     */
+    fn from_index<E: de::Error>(index: u8) -> Result<Card, E> {
+        if index as usize >= card::VALUES.len() * card::SUITS.len() {
+            return Err(E::invalid_value(de::Unexpected::Unsigned(index as u64),
+                                        &"a card index from 0 to 51"));
+        }
+        Ok(Card::from_index(index as usize))
+    }
+
+    pub mod cards {
+        use serde::Deserialize;
+        use serde::Deserializer;
+        use serde::Serializer;
+
+        use card::Card;
+
+        pub fn serialize<S: Serializer>(cards: &[Card], serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(cards.iter().map(|card| card.index() as u8))
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<Card>, D::Error>
+            where D: Deserializer<'de>
+        {
+            Vec::<u8>::deserialize(deserializer)?
+                .into_iter()
+                .map(super::from_index)
+                .collect()
+        }
+    }
+}
@@ -0,0 +1,433 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// External imports
+use std::fmt::Write;
+
+// Internal imports
+use types::Street;
+use history::ActionKind;
+use history::Amount;
+use history::HandHistory;
+use replay;
+
+/**
+ * Position of a player at the table, relative to the button
+ */
+#[derive(Hash, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Position {
+    Early, // Any seat before the hijack
+    Hijack,
+    Cutoff,
+    Button,
+    SmallBlind,
+    BigBlind,
+}
+
+impl Position {
+    /**
+     * name gets the short name of the position, like "BTN"
+     */
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Position::Early => "EP",
+            Position::Hijack => "HJ",
+            Position::Cutoff => "CO",
+            Position::Button => "BTN",
+            Position::SmallBlind => "SB",
+            Position::BigBlind => "BB",
+        }
+    }
+
+    /**
+     * from_name gets the position with the given short name
+     */
+    pub fn from_name(name: &str) -> Option<Position> {
+        match name.to_uppercase().as_str() {
+            "EP" => Some(Position::Early),
+            "HJ" => Some(Position::Hijack),
+            "CO" => Some(Position::Cutoff),
+            "BTN" => Some(Position::Button),
+            "SB" => Some(Position::SmallBlind),
+            "BB" => Some(Position::BigBlind),
+            _ => None,
+        }
+    }
+}
+
+/**
+ * Filter selects the hands of the players that are aggregated. Dates are compared as text, so they
+ * must be written on the format of the site, like "2020/03/01".
+ */
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Filter {
+    pub positions: Vec<Position>, // Any position if empty
+    pub big_blind: Option<Amount>,
+    pub from: Option<String>, // First date included
+    pub to: Option<String>, // Last date included
+}
+
+/**
+ * Stats are the counters of a player. Every ratio has the number of times something happened
+ * and the number of times it could have happened.
+ */
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stats {
+    pub player: String,
+    pub hands: u32,
+    pub vpip: u32, // Voluntarily put money in the pot before the flop
+    pub pfr: u32, // Raised before the flop
+    pub three_bets: u32,
+    pub three_bet_chances: u32,
+    pub folds_to_three_bet: u32,
+    pub faced_three_bets: u32,
+    pub cbets: u32, // Continuation bets on the flop after raising before it
+    pub cbet_chances: u32,
+    pub saw_flop: u32,
+    pub went_to_showdown: u32,
+    pub won_at_showdown: u32,
+    pub aggressive_actions: u32, // Bets and raises after the flop
+    pub calls: u32, // Calls after the flop
+    pub won: f64, // Big blinds won
+}
+
+impl Stats {
+    pub fn vpip(&self) -> Option<f64> {
+        percentage(self.vpip, self.hands)
+    }
+
+    pub fn pfr(&self) -> Option<f64> {
+        percentage(self.pfr, self.hands)
+    }
+
+    pub fn three_bet(&self) -> Option<f64> {
+        percentage(self.three_bets, self.three_bet_chances)
+    }
+
+    pub fn fold_to_three_bet(&self) -> Option<f64> {
+        percentage(self.folds_to_three_bet, self.faced_three_bets)
+    }
+
+    pub fn cbet(&self) -> Option<f64> {
+        percentage(self.cbets, self.cbet_chances)
+    }
+
+    /**
+     * wtsd gets how often the player went to showdown after seeing the flop
+     */
+    pub fn wtsd(&self) -> Option<f64> {
+        percentage(self.went_to_showdown, self.saw_flop)
+    }
+
+    /**
+     * wsd gets how often the player won money when going to showdown
+     */
+    pub fn wsd(&self) -> Option<f64> {
+        percentage(self.won_at_showdown, self.went_to_showdown)
+    }
+
+    /**
+     * aggression_factor gets the bets and raises for every call after the flop
+     */
+    pub fn aggression_factor(&self) -> Option<f64> {
+        if self.calls == 0 {
+            return None;
+        }
+        Some(self.aggressive_actions as f64 / self.calls as f64)
+    }
+
+    /**
+     * big_blinds_per_100 gets the winrate on big blinds won every 100 hands
+     */
+    pub fn big_blinds_per_100(&self) -> Option<f64> {
+        if self.hands == 0 {
+            return None;
+        }
+        Some(self.won * 100.0 / self.hands as f64)
+    }
+
+    /**
+     * add adds the counters of a hand
+     */
+    fn add(&mut self, other: &Stats) {
+        self.hands += other.hands;
+        self.vpip += other.vpip;
+        self.pfr += other.pfr;
+        self.three_bets += other.three_bets;
+        self.three_bet_chances += other.three_bet_chances;
+        self.folds_to_three_bet += other.folds_to_three_bet;
+        self.faced_three_bets += other.faced_three_bets;
+        self.cbets += other.cbets;
+        self.cbet_chances += other.cbet_chances;
+        self.saw_flop += other.saw_flop;
+        self.went_to_showdown += other.went_to_showdown;
+        self.won_at_showdown += other.won_at_showdown;
+        self.aggressive_actions += other.aggressive_actions;
+        self.calls += other.calls;
+        self.won += other.won;
+    }
+}
+
+/**
+ * aggregate computes the statistics of every player on the hands selected by the filter, sorted
+ * by name
+ */
+pub fn aggregate(hands: &[HandHistory], filter: &Filter) -> Vec<Stats> {
+    let mut stats: Vec<Stats> = Vec::new();
+
+    for hand in hands {
+        // The last date includes the whole day, or any other prefix of the date of the hand
+        let after = |to: &String| hand.date > *to && !hand.date.starts_with(to.as_str());
+        if filter.big_blind.is_some_and(|big_blind| big_blind != hand.big_blind) ||
+           filter.from.as_ref().is_some_and(|from| hand.date < *from) ||
+           filter.to.as_ref().is_some_and(after) {
+            continue;
+        }
+
+        for (player, position) in positions(hand) {
+            if !filter.positions.is_empty() && !filter.positions.contains(&position) {
+                continue;
+            }
+
+            let counters = hand_stats(hand, &player);
+            match stats.iter_mut().find(|stats| stats.player == player) {
+                Some(stats) => stats.add(&counters),
+                None => stats.push(counters),
+            }
+        }
+    }
+
+    stats.sort_by(|a, b| a.player.cmp(&b.player));
+    stats
+}
+
+/**
+ * positions gets the position of every player dealt into the hand
+ */
+pub fn positions(hand: &HandHistory) -> Vec<(String, Position)> {
+    // Players dealt in, from the first seat after the button to the button
+    let mut seats: Vec<(u32, &str)> = hand.seats
+        .iter()
+        .filter(|seat| {
+            !seat.sitting_out || hand.actions.iter().any(|action| action.player == seat.player)
+        })
+        .map(|seat| (seat.number, seat.player.as_str()))
+        .collect();
+    seats.sort_by_key(|&(number, _)| (number <= hand.button, number));
+
+    let count = seats.len();
+    seats.iter()
+        .enumerate()
+        .map(|(i, &(_, player))| {
+            let position = match (count, i) {
+                (2, 0) => Position::BigBlind,
+                (_, i) if i == count - 1 => Position::Button,
+                (_, 0) => Position::SmallBlind,
+                (_, 1) => Position::BigBlind,
+                (_, i) if i == count - 2 => Position::Cutoff,
+                (_, i) if i == count - 3 => Position::Hijack,
+                _ => Position::Early,
+            };
+            (player.to_string(), position)
+        })
+        .collect()
+}
+
+/**
+ * hand_stats computes the counters of a player on a single hand
+ */
+fn hand_stats(hand: &HandHistory, player: &str) -> Stats {
+    let mut stats = Stats {
+        player: player.to_string(),
+        hands: 1,
+        ..Stats::default()
+    };
+
+    // Before the flop
+    let mut raises = 0;
+    let mut opened = false; // The player made the first raise
+    let mut aggressor = None; // Player who made the last raise
+    let mut folded_preflop = false;
+    for action in hand.actions_on(Street::Preflop) {
+        let raise = matches!(action.kind, ActionKind::Raise { .. } | ActionKind::Bet(_));
+
+        if action.player == player {
+            match action.kind {
+                ActionKind::Call(_) | ActionKind::Raise { .. } | ActionKind::Bet(_) => {
+                    stats.vpip = 1;
+                }
+                ActionKind::Fold => folded_preflop = true,
+                _ => (),
+            }
+            if raise {
+                stats.pfr = 1;
+            }
+
+            let voluntary = is_voluntary(&action.kind);
+            if voluntary && raises == 1 && aggressor.as_deref() != Some(player) {
+                stats.three_bet_chances += 1;
+                if raise {
+                    stats.three_bets += 1;
+                }
+            }
+            if voluntary && opened && raises == 2 && stats.faced_three_bets == 0 {
+                stats.faced_three_bets = 1;
+                if action.kind == ActionKind::Fold {
+                    stats.folds_to_three_bet = 1;
+                }
+            }
+        }
+
+        if raise {
+            raises += 1;
+            opened |= raises == 1 && action.player == player;
+            aggressor = Some(action.player.clone());
+        }
+    }
+
+    if !folded_preflop && hand.board.len() >= 3 {
+        stats.saw_flop = 1;
+    }
+
+    // After the flop
+    let mut bet_on_flop = false;
+    let mut acted_on_flop = false;
+    let mut folded = folded_preflop;
+    for action in hand.actions.iter().filter(|action| action.street != Street::Preflop) {
+        let aggressive = matches!(action.kind, ActionKind::Bet(_) | ActionKind::Raise { .. });
+
+        if action.player == player {
+            if action.street == Street::Flop && !acted_on_flop && is_voluntary(&action.kind) {
+                acted_on_flop = true;
+                if aggressor.as_deref() == Some(player) && !bet_on_flop {
+                    stats.cbet_chances = 1;
+                    stats.cbets = aggressive as u32;
+                }
+            }
+            match action.kind {
+                ActionKind::Bet(_) | ActionKind::Raise { .. } => stats.aggressive_actions += 1,
+                ActionKind::Call(_) => stats.calls += 1,
+                ActionKind::Fold => folded = true,
+                _ => (),
+            }
+        }
+
+        if action.street == Street::Flop && aggressive {
+            bet_on_flop = true;
+        }
+    }
+
+    // A showdown happens when more than one player reaches the end of the hand
+    let remaining = positions(hand)
+        .iter()
+        .filter(|(name, _)| {
+            !hand.actions
+                .iter()
+                .any(|action| action.player == *name && action.kind == ActionKind::Fold)
+        })
+        .count();
+    if stats.saw_flop == 1 && !folded && remaining > 1 {
+        stats.went_to_showdown = 1;
+        let collected = hand.actions.iter().any(|action| {
+            action.player == player &&
+            match action.kind {
+                ActionKind::Collect(amount) => amount > 0,
+                _ => false,
+            }
+        });
+        stats.won_at_showdown = collected as u32;
+    }
+
+    // Winnings from the stacks before and after the hand
+    let replay = replay::replay(hand);
+    let start = hand.seat(player).map(|seat| seat.stack).unwrap_or(0);
+    let end = replay.stacks
+        .iter()
+        .find(|(name, _)| name == player)
+        .map(|&(_, stack)| stack)
+        .unwrap_or(start);
+    if hand.big_blind > 0 {
+        stats.won = (end - start) as f64 / hand.big_blind as f64;
+    }
+
+    stats
+}
+
+/**
+ * is_voluntary tells if an action is a decision of the player, not a forced bet or a result
+ */
+fn is_voluntary(kind: &ActionKind) -> bool {
+    matches!(*kind,
+             ActionKind::Fold | ActionKind::Check | ActionKind::Call(_) | ActionKind::Bet(_) |
+             ActionKind::Raise { .. })
+}
+
+/**
+ * to_csv writes the statistics as CSV, with a header. Percentages and ratios have one decimal and
+ * are empty when there is no chance to compute them.
+ */
+pub fn to_csv(stats: &[Stats]) -> String {
+    let mut csv = String::from("player,hands,vpip,pfr,three_bet,fold_to_three_bet,cbet,wtsd,wsd,\
+                                aggression_factor,bb_per_100\n");
+    for player in stats {
+        let ratios: Vec<String> = [player.vpip(),
+                                   player.pfr(),
+                                   player.three_bet(),
+                                   player.fold_to_three_bet(),
+                                   player.cbet(),
+                                   player.wtsd(),
+                                   player.wsd(),
+                                   player.aggression_factor(),
+                                   player.big_blinds_per_100()]
+            .iter()
+            .map(|ratio| ratio.map(|ratio| format!("{:.1}", ratio)).unwrap_or_default())
+            .collect();
+        writeln!(csv,
+                 "{},{},{}",
+                 csv_field(&player.player),
+                 player.hands,
+                 ratios.join(","))
+            .unwrap();
+    }
+    csv
+}
+
+/**
+ * csv_field quotes a field when it has commas, quotes or line breaks
+ */
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/**
+ * percentage gets the percentage of a count over the chances, if any
+ */
+fn percentage(count: u32, chances: u32) -> Option<f64> {
+    if chances == 0 {
+        return None;
+    }
+    Some(count as f64 * 100.0 / chances as f64)
+}
@@ -0,0 +1,443 @@
+// MIT License
+//
+// Copyright (c) 2016 Diego Fernández Barrera
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// External imports
+use std::cmp::Ordering;
+
+// Internal imports
+use types;
+use consts;
+use card;
+use card::Card;
+use combinatorics::CardSet;
+use hand::Hand;
+use range::Combo;
+use range::Range;
+use rng::Rng;
+
+// Positions of the counters of a hand that is ahead, tied or behind the opponent
+const AHEAD: usize = 0;
+const TIED: usize = 1;
+const BEHIND: usize = 2;
+
+/**
+ * Potential holds how likely is a hand to change its situation against the opponent when the
+ * next cards are dealt
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Potential {
+    pub positive: f64, // Chances of getting ahead when behind or tied (PPOT)
+    pub negative: f64, // Chances of falling behind when ahead or tied (NPOT)
+}
+
+/**
+ * Transitions counts the weight of the opponent hands that go from one situation (ahead, tied or
+ * behind) to another after the next cards are dealt
+ */
+struct Transitions {
+    counts: [[f64; 3]; 3],
+}
+
+impl Transitions {
+    fn new() -> Transitions {
+        Transitions { counts: [[0.0; 3]; 3] }
+    }
+
+    fn add(&mut self, before: usize, after: usize, weight: f64) {
+        self.counts[before][after] += weight;
+    }
+
+    fn total(&self, before: usize) -> f64 {
+        self.counts[before].iter().sum()
+    }
+
+    /**
+     * potential computes PPOT and NPOT as defined by Billings et al.
+     */
+    fn potential(&self) -> Potential {
+        let c = &self.counts;
+        let positive = (c[BEHIND][AHEAD] + c[BEHIND][TIED] / 2.0 + c[TIED][AHEAD] / 2.0) /
+                       (self.total(BEHIND) + self.total(TIED) / 2.0);
+        let negative = (c[AHEAD][BEHIND] + c[TIED][BEHIND] / 2.0 + c[AHEAD][TIED] / 2.0) /
+                       (self.total(AHEAD) + self.total(TIED) / 2.0);
+
+        Potential {
+            positive: if positive.is_nan() { 0.0 } else { positive },
+            negative: if negative.is_nan() { 0.0 } else { negative },
+        }
+    }
+}
+
+/**
+ * ComboSampler picks combos of a range proportionally to their weight, leaving out the ones
+ * blocked by the known cards
+ */
+struct ComboSampler<'a> {
+    combos: Vec<&'a Combo>,
+    cumulative: Vec<f64>, // Cumulative weights of the combos
+}
+
+impl<'a> ComboSampler<'a> {
+    /**
+     * new creates a sampler for the range, or None if every combo is blocked
+     */
+    fn new(range: &'a Range, known: &[&Card]) -> Option<ComboSampler<'a>> {
+        let combos: Vec<&Combo> = range.combos()
+            .iter()
+            .filter(|combo| !combo.conflicts(known) && combo.weight > 0.0)
+            .collect();
+        if combos.is_empty() {
+            return None;
+        }
+
+        let mut cumulative = Vec::new();
+        let mut total = 0.0;
+        for combo in &combos {
+            total += combo.weight;
+            cumulative.push(total);
+        }
+
+        Some(ComboSampler { combos, cumulative })
+    }
+
+    fn sample(&self, rng: &mut Rng) -> &'a Combo {
+        let target = rng.next_f64() * self.cumulative[self.cumulative.len() - 1];
+        let position = self.cumulative
+            .iter()
+            .position(|sum| *sum > target)
+            .unwrap_or(self.combos.len() - 1);
+        self.combos[position]
+    }
+}
+
+/**
+ * hand_strength computes the probability of the hole cards being ahead of an opponent holding
+ * any combo of the range on the current board (3 to 5 cards). Ties count as half a win.
+ */
+pub fn hand_strength(hole: types::Hole, board: &[&Card], range: &Range) -> f64 {
+    assert!(board.len() >= 3 && board.len() <= consts::TABLE_SIZE,
+            "Hand strength needs a flop, a turn or a river");
+
+    let known = known_cards(hole, board);
+    let mut totals = [0.0; 3];
+    for combo in range.combos().iter().filter(|combo| !combo.conflicts(&known)) {
+        totals[compare(hole, combo, board)] += combo.weight;
+    }
+
+    let total: f64 = totals.iter().sum();
+    if total == 0.0 {
+        return 0.0;
+    }
+    (totals[AHEAD] + totals[TIED] / 2.0) / total
+}
+
+/**
+ * hand_potential computes the positive and negative potential of the hole cards against the range
+ * enumerating every possible combination of the next cards_to_come cards
+ */
+pub fn hand_potential(hole: types::Hole,
+                      board: &[&Card],
+                      range: &Range,
+                      cards_to_come: usize)
+                      -> Potential {
+    assert!(board.len() >= 3 && board.len() + cards_to_come <= consts::TABLE_SIZE,
+            "Not enough cards left to come");
+
+    let known = known_cards(hole, board);
+    let runouts = runouts(&known, cards_to_come);
+    let runouts: Vec<Vec<&Card>> = runouts.iter().map(|runout| runout.iter().collect()).collect();
+
+    let mut transitions = Transitions::new();
+    for combo in range.combos().iter().filter(|combo| !combo.conflicts(&known)) {
+        let before = compare(hole, combo, board);
+        for runout in &runouts {
+            if combo.conflicts(runout) {
+                continue;
+            }
+
+            let mut next_board = board.to_vec();
+            next_board.extend_from_slice(runout);
+            transitions.add(before, compare(hole, combo, &next_board), combo.weight);
+        }
+    }
+
+    transitions.potential()
+}
+
+/**
+ * sampled_hand_potential estimates the potential of the hole cards drawing samples opponent
+ * combos (according to their weights) and runouts. The same seed gives the same estimation.
+ */
+pub fn sampled_hand_potential(hole: types::Hole,
+                              board: &[&Card],
+                              range: &Range,
+                              cards_to_come: usize,
+                              samples: usize,
+                              seed: u64)
+                              -> Potential {
+    assert!(board.len() >= 3 && board.len() + cards_to_come <= consts::TABLE_SIZE,
+            "Not enough cards left to come");
+
+    let known = known_cards(hole, board);
+    let sampler = match ComboSampler::new(range, &known) {
+        Some(sampler) => sampler,
+        None => return Transitions::new().potential(),
+    };
+
+    let mut rng = Rng::new(seed);
+    let mut transitions = Transitions::new();
+    for _ in 0..samples {
+        let combo = sampler.sample(&mut rng);
+
+        let mut deck: Vec<Card> = card::deck()
+            .into_iter()
+            .filter(|card| !known.contains(&card) && !combo.cards.contains(card))
+            .collect();
+        rng.shuffle(&mut deck);
+
+        let mut next_board = board.to_vec();
+        next_board.extend(deck[..cards_to_come].iter());
+        transitions.add(compare(hole, combo, board),
+                        compare(hole, combo, &next_board),
+                        1.0);
+    }
+
+    transitions.potential()
+}
+
+/**
+ * equity computes the probability of winning the showdown against an opponent holding any combo
+ * of the range, enumerating every possible runout of the board. Ties count as half a win.
+ */
+pub fn equity(hole: types::Hole, board: &[&Card], range: &Range) -> f64 {
+    assert!(board.len() <= consts::TABLE_SIZE, "Too many community cards");
+
+    let known = known_cards(hole, board);
+    let runouts = runouts(&known, consts::TABLE_SIZE - board.len());
+    let runouts: Vec<Vec<&Card>> = runouts.iter().map(|runout| runout.iter().collect()).collect();
+
+    let mut totals = [0.0; 3];
+    for combo in range.combos().iter().filter(|combo| !combo.conflicts(&known)) {
+        for runout in runouts.iter().filter(|runout| !combo.conflicts(runout)) {
+            let mut full_board = board.to_vec();
+            full_board.extend_from_slice(runout);
+            totals[compare(hole, combo, &full_board)] += combo.weight;
+        }
+    }
+
+    let total: f64 = totals.iter().sum();
+    if total == 0.0 {
+        return 0.0;
+    }
+    (totals[AHEAD] + totals[TIED] / 2.0) / total
+}
+
+/**
+ * sampled_equity estimates the equity of the hole cards against the range drawing samples
+ * opponent combos (according to their weights) and runouts. It works on any street, the preflop
+ * included. The same seed gives the same estimation.
+ */
+pub fn sampled_equity(hole: types::Hole,
+                      board: &[&Card],
+                      range: &Range,
+                      samples: usize,
+                      seed: u64)
+                      -> f64 {
+    assert!(board.len() <= consts::TABLE_SIZE, "Too many community cards");
+    assert!(samples > 0, "At least one sample is needed to estimate the equity");
+
+    let known = known_cards(hole, board);
+    let sampler = match ComboSampler::new(range, &known) {
+        Some(sampler) => sampler,
+        None => return 0.0,
+    };
+
+    let mut rng = Rng::new(seed);
+    let mut points = 0.0;
+    for _ in 0..samples {
+        let combo = sampler.sample(&mut rng);
+        let mut deck: Vec<Card> = card::deck()
+            .into_iter()
+            .filter(|card| !known.contains(&card) && !combo.cards.contains(card))
+            .collect();
+        rng.shuffle(&mut deck);
+
+        let mut full_board = board.to_vec();
+        full_board.extend(deck[..consts::TABLE_SIZE - board.len()].iter());
+        points += match compare(hole, combo, &full_board) {
+            AHEAD => 1.0,
+            TIED => 0.5,
+            _ => 0.0,
+        };
+    }
+
+    points / samples as f64
+}
+
+/**
+ * effective_hand_strength combines the hand strength and its potential into the probability of
+ * being ahead or getting ahead: EHS = HS * (1 - NPOT) + (1 - HS) * PPOT
+ */
+pub fn effective_hand_strength(strength: f64, potential: Potential) -> f64 {
+    strength * (1.0 - potential.negative) + (1.0 - strength) * potential.positive
+}
+
+/**
+ * known_cards gets the hole cards and the board on a single vector
+ */
+fn known_cards<'a>(hole: types::Hole<'a>, board: &[&'a Card]) -> Vec<&'a Card> {
+    let mut known = hole.to_vec();
+    known.extend_from_slice(board);
+    known
+}
+
+/**
+ * compare checks if the hole cards are ahead, tied or behind the combo on the given board
+ */
+fn compare(hole: types::Hole, combo: &Combo, board: &[&Card]) -> usize {
+    let player = Hand::from_cards(&known_cards(hole, board));
+    let opponent = Hand::from_cards(&known_cards([&combo.cards[0], &combo.cards[1]], board));
+
+    match player.cmp(&opponent) {
+        Ordering::Greater => AHEAD,
+        Ordering::Equal => TIED,
+        Ordering::Less => BEHIND,
+    }
+}
+
+/**
+ * runouts gets all the ways to deal k cards that are not known
+ */
+fn runouts(known: &[&Card], k: usize) -> Vec<Vec<Card>> {
+    let dead: Vec<Card> = known.iter().map(|card| **card).collect();
+    CardSet::deck(&dead).combinations(k).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::hand_strength;
+    use super::hand_potential;
+    use super::sampled_hand_potential;
+    use super::effective_hand_strength;
+    use super::equity;
+    use super::sampled_equity;
+    use super::super::card::Card;
+    use super::super::range::Range;
+
+    fn parse(cards: &str) -> Vec<Card> {
+        cards.split_whitespace()
+            .map(|card| Card::new(card.chars().next().unwrap(), card.chars().nth(1).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_nuts_strength() {
+        let hole = parse("AS KS");
+        let board = parse("QS JS TS");
+
+        let strength = hand_strength([&hole[0], &hole[1]],
+                                     &[&board[0], &board[1], &board[2]],
+                                     &Range::uniform());
+        assert_eq!(strength, 1.0);
+    }
+
+    #[test]
+    fn test_weighted_strength() {
+        let hole = parse("AH AD");
+        let board = parse("KC 7S 2D");
+        let mut range = Range::new();
+        range.add(Card::new('K', 'S'), Card::new('K', 'H'), 1.0);
+        range.add(Card::new('Q', 'S'), Card::new('Q', 'H'), 3.0);
+        range.add(Card::new('A', 'S'), Card::new('A', 'H'), 1.0); // Blocked by the hole cards
+
+        let strength = hand_strength([&hole[0], &hole[1]],
+                                     &[&board[0], &board[1], &board[2]],
+                                     &range);
+        assert_eq!(strength, 0.75);
+    }
+
+    #[test]
+    fn test_turn_potential() {
+        let hole = parse("AH AD");
+        let board = parse("QC 7S 2D 3H");
+        let mut range = Range::new();
+        range.add(Card::new('K', 'S'), Card::new('K', 'H'), 1.0);
+
+        // Only the two remaining kings out of 44 cards beat the aces on the river
+        let potential = hand_potential([&hole[0], &hole[1]],
+                                       &[&board[0], &board[1], &board[2], &board[3]],
+                                       &range,
+                                       1);
+        assert_eq!(potential.positive, 0.0);
+        assert!((potential.negative - 2.0 / 44.0).abs() < 1e-9);
+
+        let strength = effective_hand_strength(1.0, potential);
+        assert!((strength - 42.0 / 44.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_sampled_potential() {
+        let hole = parse("9H 8H");
+        let board = parse("AH 2H 7C");
+        let hole = [&hole[0], &hole[1]];
+        let board = [&board[0], &board[1], &board[2]];
+        let range = Range::uniform();
+
+        let exact = hand_potential(hole, &board, &range, 1);
+        let sampled = sampled_hand_potential(hole, &board, &range, 1, 20000, 1);
+        assert!((exact.positive - sampled.positive).abs() < 0.02);
+        assert!((exact.negative - sampled.negative).abs() < 0.02);
+
+        // Same seed, same results
+        assert_eq!(sampled, sampled_hand_potential(hole, &board, &range, 1, 20000, 1));
+    }
+
+    #[test]
+    fn test_equity() {
+        let hole = parse("AH AD");
+        let board = parse("QC 7S 2D");
+        let mut range = Range::new();
+        range.add(Card::new('K', 'S'), Card::new('K', 'H'), 1.0);
+
+        // Kings need one of the two remaining kings (or runner runner straights) on 990 runouts
+        let exact = equity([&hole[0], &hole[1]], &[&board[0], &board[1], &board[2]], &range);
+        assert!(exact > 0.90 && exact < 0.93);
+
+        let sampled = sampled_equity([&hole[0], &hole[1]],
+                                     &[&board[0], &board[1], &board[2]],
+                                     &range,
+                                     5000,
+                                     3);
+        assert!((exact - sampled).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_preflop_equity() {
+        let hole = parse("AH AD");
+        let range = Range::parse("KK").unwrap();
+
+        // Aces against Kings win about 82% of the time
+        let equity = sampled_equity([&hole[0], &hole[1]], &[], &range, 5000, 11);
+        assert!((equity - 0.82).abs() < 0.02);
+        assert_eq!(equity, sampled_equity([&hole[0], &hole[1]], &[], &range, 5000, 11));
+    }
+}
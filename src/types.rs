@@ -27,3 +27,42 @@ use consts;
 pub type Hole<'a> = [&'a Card; consts::HOLE_SIZE];
 pub type Table<'a> = [&'a Card; consts::TABLE_SIZE];
 pub type Player<'a> = [&'a Card; consts::TABLE_SIZE + consts::HOLE_SIZE];
+
+/**
+ * Street represents each of the betting rounds of a hand, named after the community cards that
+ * are visible during it
+ */
+#[derive(Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum Street {
+    Preflop,
+    Flop,
+    Turn,
+    River,
+}
+
+impl Street {
+    /**
+     * board_size gets the number of community cards visible on the street
+     */
+    pub fn board_size(&self) -> usize {
+        match *self {
+            Street::Preflop => 0,
+            Street::Flop => 3,
+            Street::Turn => 4,
+            Street::River => consts::TABLE_SIZE,
+        }
+    }
+
+    /**
+     * from_board_size gets the street matching a number of community cards, if any
+     */
+    pub fn from_board_size(size: usize) -> Option<Street> {
+        match size {
+            0 => Some(Street::Preflop),
+            3 => Some(Street::Flop),
+            4 => Some(Street::Turn),
+            5 => Some(Street::River),
+            _ => None,
+        }
+    }
+}
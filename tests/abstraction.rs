@@ -0,0 +1,102 @@
+extern crate poker;
+
+use std::env;
+
+use poker::abstraction;
+use poker::abstraction::Abstraction;
+use poker::abstraction::Config;
+use poker::abstraction::Distance;
+use poker::calculator;
+use poker::isomorphism;
+use poker::types::Street;
+
+fn small_config(buckets: usize) -> Config {
+    let mut config = Config::new(buckets);
+    config.runouts = 20;
+    config.opponents = 20;
+    config
+}
+
+#[test]
+fn test_histogram() {
+    let cards = calculator::parse_cards("AsAh Ad7c2h Td4s").unwrap();
+    let strong = abstraction::histogram([&cards[0], &cards[1]], &[&cards[2], &cards[3], &cards[4]],
+                                        10, 30, 20, 1);
+    let weak = abstraction::histogram([&cards[5], &cards[6]], &[&cards[2], &cards[3], &cards[4]],
+                                      10, 30, 20, 1);
+    assert!((strong.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    assert!(strong[9] > 0.8);
+    assert!(abstraction::earth_movers_distance(&strong, &weak) > 3.0);
+}
+
+#[test]
+fn test_kmeans() {
+    let points: Vec<Vec<f64>> = [0.1, 0.9, 0.12, 0.88, 0.11, 0.5, 0.52]
+        .iter()
+        .map(|x| vec![*x])
+        .collect();
+    let clusters = abstraction::kmeans(&points, 3, Distance::Euclidean, 20, 4);
+    let a = &clusters.assignments;
+    assert_eq!(clusters.centroids.len(), 3);
+    assert!(a[0] == a[2] && a[2] == a[4]);
+    assert!(a[1] == a[3] && a[5] == a[6]);
+    assert!(a[0] != a[1] && a[1] != a[5] && a[0] != a[5]);
+    assert_eq!(clusters, abstraction::kmeans(&points, 3, Distance::Euclidean, 20, 4));
+}
+
+#[test]
+fn test_degenerate_config() {
+    let indices: Vec<u64> = (0..10).map(|i| i * 1_000_003).collect();
+    let abstraction = Abstraction::build(Street::River, &[], &small_config(5));
+    assert!(abstraction.is_empty());
+    let abstraction = Abstraction::build(Street::River, &indices, &small_config(0));
+    assert!(abstraction.is_empty());
+
+    let mut config = small_config(3);
+    config.iterations = 0;
+    let abstraction = Abstraction::build(Street::River, &indices, &config);
+    assert_eq!(abstraction.len(), 10);
+    assert!(indices.iter().all(|index| abstraction.bucket_of(*index).unwrap() < 3));
+}
+
+#[test]
+fn test_river_buckets() {
+    let indices: Vec<u64> = (0..60).map(|i| i * 1_000_003).collect();
+    let abstraction = Abstraction::build(Street::River, &indices, &small_config(5));
+    assert_eq!(abstraction.len(), 60);
+    assert_eq!(abstraction.buckets(), 5);
+
+    // Buckets are sorted by strength
+    let cards = calculator::parse_cards("AsKs QsJsTs 2h3d").unwrap();
+    let nuts = [&cards[0], &cards[1]];
+    let board = [&cards[2], &cards[3], &cards[4], &cards[5], &cards[6]];
+    let index = isomorphism::index(nuts, &board);
+    let mut with_nuts = indices.clone();
+    with_nuts.push(index);
+    let abstraction = Abstraction::build(Street::River, &with_nuts, &small_config(5));
+    assert_eq!(abstraction.bucket(nuts, &board), Some(4));
+    assert_eq!(abstraction.bucket(nuts, &board[..3]), None);
+}
+
+#[test]
+fn test_save_and_load() {
+    let indices: Vec<u64> = (0..30).map(|i| i * 40_009).collect();
+    let abstraction = Abstraction::build(Street::Flop, &indices, &small_config(4));
+    assert_eq!(abstraction.street(), Street::Flop);
+    for index in &indices {
+        assert!(abstraction.bucket_of(*index).unwrap() < 4);
+    }
+
+    let path = env::temp_dir().join(format!("poker-abstraction-{}.bin", std::process::id()));
+    abstraction.save(&path).unwrap();
+    let loaded = Abstraction::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded, abstraction);
+
+    let mut bytes = Vec::new();
+    abstraction.write(&mut bytes).unwrap();
+    assert_eq!(bytes.len(), 18 + 12 * 30);
+    assert!(Abstraction::read(&mut &bytes[..bytes.len() - 1]).is_err());
+    bytes[0] = b'X';
+    assert!(Abstraction::read(&mut &bytes[..]).is_err());
+}
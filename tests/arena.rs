@@ -0,0 +1,42 @@
+extern crate poker;
+
+mod common;
+
+use poker::arena;
+use poker::arena::ArenaConfig;
+use poker::baseline::CallingStation;
+
+use common::Folder;
+use common::Raiser;
+
+#[test]
+fn test_duplicate_cancels_luck() {
+    let result = arena::play(&mut CallingStation, &mut CallingStation, &ArenaConfig::new(200, 1));
+    assert_eq!(result.hands, 400);
+    assert_eq!(result.winnings, 0);
+    assert_eq!(result.bb_per_100, 0.0);
+    assert_eq!(result.confidence, 0.0);
+}
+
+#[test]
+fn test_blind_stealing() {
+    // The raiser wins the big blind on the button and the small blind otherwise
+    let mut folder = Folder::new();
+    let result = arena::play(&mut Raiser, &mut folder, &ArenaConfig::new(50, 2));
+    assert_eq!(folder.hands, 100);
+    assert_eq!(result.winnings, 50 * 150);
+    assert_eq!(result.bb_per_100, 75.0);
+    assert_eq!(result.confidence, 0.0);
+}
+
+#[test]
+fn test_deterministic() {
+    let mut config = ArenaConfig::new(100, 3);
+    config.duplicate = false;
+    let first = arena::play(&mut Raiser, &mut CallingStation, &config);
+    let second = arena::play(&mut Raiser, &mut CallingStation, &config);
+    assert_eq!(first, second);
+    assert_eq!(first.hands, 100);
+    assert!(first.confidence > 0.0);
+    assert_ne!(first, arena::play(&mut Raiser, &mut CallingStation, &ArenaConfig::new(50, 4)));
+}
@@ -0,0 +1,127 @@
+extern crate poker;
+
+use poker::arena;
+use poker::arena::ArenaConfig;
+use poker::baseline;
+use poker::baseline::{CallingStation, Made, RandomBot, Tier, TightAggressive};
+use poker::bot::{Action, Bot, HandResult, Observation};
+use poker::card;
+use poker::card::Card;
+use poker::engine::{HandState, TableConfig};
+use poker::preflop::HandClass;
+
+/**
+ * Checker plays like another bot, checking that every action it takes is legal
+ */
+struct Checker<B: Bot> {
+    bot: B,
+    actions: Vec<Action>,
+}
+
+impl<B: Bot> Bot for Checker<B> {
+    fn name(&self) -> String {
+        self.bot.name()
+    }
+
+    fn act(&mut self, observation: &Observation) -> Action {
+        let action = self.bot.act(observation);
+        assert!(observation.legal.contains(&action), "{} is not legal", action);
+        self.actions.push(action);
+        action
+    }
+
+    fn hand_over(&mut self, seat: usize, result: &HandResult) {
+        self.bot.hand_over(seat, result);
+    }
+}
+
+fn checker<B: Bot>(bot: B) -> Checker<B> {
+    Checker { bot, actions: Vec::new() }
+}
+
+fn cards(text: &str) -> Vec<Card> {
+    text.split_whitespace().map(|card| Card::parse(card).unwrap()).collect()
+}
+
+/**
+ * deck puts the hole cards of both seats and the board on top of a deck
+ */
+fn deck(text: &str) -> Vec<Card> {
+    let mut deck = cards(text);
+    deck.extend(card::deck().into_iter().filter(|card| !deck.contains(card)).collect::<Vec<_>>());
+    deck
+}
+
+#[test]
+fn test_bots_play_legal_actions_deterministically() {
+    let config = ArenaConfig::new(30, 11);
+    let mut random = checker(RandomBot::new(1));
+    let mut tag = checker(TightAggressive::new(2));
+    let result = arena::play(&mut random, &mut tag, &config);
+    assert!(random.actions.iter().any(|action| matches!(*action, Action::Raise(_))));
+    assert!(random.actions.contains(&Action::Fold));
+
+    let mut station = checker(CallingStation::new());
+    arena::play(&mut station, &mut TightAggressive::new(2), &config);
+    assert!(station.actions.iter().all(|action| matches!(*action, Action::Check | Action::Call)));
+
+    // The same seeds play the same match
+    let again = arena::play(&mut RandomBot::new(1), &mut TightAggressive::new(2), &config);
+    assert_eq!(again, result);
+    let other = arena::play(&mut RandomBot::new(3), &mut TightAggressive::new(2), &config);
+    assert_ne!(other, result);
+}
+
+#[test]
+fn test_tight_aggressive_preflop() {
+    let tag = TightAggressive::new(1);
+    assert_eq!(tag.tier(HandClass::parse("AA").unwrap()), Tier::Premium);
+    assert_eq!(tag.tier(HandClass::parse("AKo").unwrap()), Tier::Premium);
+    assert_eq!(tag.tier(HandClass::parse("KQo").unwrap()), Tier::Strong);
+    assert_eq!(tag.tier(HandClass::parse("76s").unwrap()), Tier::Opening);
+    assert_eq!(tag.tier(HandClass::parse("72o").unwrap()), Tier::Trash);
+
+    // The button raises its opening hands to 3 big blinds and folds the rest
+    let config = TableConfig::new(100, 10000);
+    let mut tag = TightAggressive::new(1);
+    let state = HandState::new(config, 0, &deck("7h 6h 2c 2d"));
+    assert_eq!(tag.act(&state.observation(0)), Action::Raise(300));
+    let state = HandState::new(config, 0, &deck("7h 2s 2c 2d"));
+    assert_eq!(tag.act(&state.observation(0)), Action::Fold);
+
+    // The big blind reraises premium hands, calls strong ones and folds the rest
+    let raised = |hole: &str| {
+        let mut state = HandState::new(config, 0, &deck(&format!("7h 6h {}", hole)));
+        state.apply(Action::Raise(300)).unwrap();
+        state.observation(1)
+    };
+    assert_eq!(tag.act(&raised("Ah As")), Action::Raise(900));
+    assert_eq!(tag.act(&raised("Kh Qs")), Action::Call);
+    assert_eq!(tag.act(&raised("7c 6c")), Action::Fold);
+}
+
+#[test]
+fn test_made_hands() {
+    let made = |hole: &str, board: &str| {
+        let hole = cards(hole);
+        let board = cards(board);
+        baseline::made([&hole[0], &hole[1]], &board.iter().collect::<Vec<_>>())
+    };
+    assert_eq!(made("Ah Kd", "Kc 7s 2d"), Made::Good);
+    assert_eq!(made("Qh Qd", "Jc 7s 2d"), Made::Good);
+    assert_eq!(made("7h 6d", "Kc 7s 2d"), Made::Weak);
+    assert_eq!(made("Ah 5d", "Kc 7s 2d"), Made::Nothing);
+    assert_eq!(made("5h 5d", "Kc Ks 2d"), Made::Weak);
+    assert_eq!(made("Kh 7d", "Kc 7s 2d"), Made::Strong);
+    assert_eq!(made("Ah Qh", "Kh 7h 2h"), Made::Strong);
+    assert_eq!(made("Ah Qd", "Kh Kd Ks 7c 7d"), Made::Nothing);
+}
+
+#[test]
+fn test_tight_aggressive_beats_the_baselines() {
+    let config = ArenaConfig::new(1000, 5);
+    let result = arena::play(&mut TightAggressive::new(1), &mut RandomBot::new(2), &config);
+    assert!(result.bb_per_100 - result.confidence > 0.0, "{:?}", result);
+    let result = arena::play(&mut TightAggressive::new(1), &mut CallingStation::new(), &config);
+    assert!(result.bb_per_100 - result.confidence > 0.0, "{:?}", result);
+}
@@ -0,0 +1,48 @@
+use std::io::Write;
+use std::process::Command;
+use std::process::Output;
+use std::process::Stdio;
+
+fn batch(args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_batch"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn test_all_match() {
+    let input = "8D 7C\n7D 6C\nKS 9D 5C 3S 2D\n1 HIGH_CARD K9875\n\
+                 TC JC AD 4S 2H 7H TH QS KC 1 PAIR TTKQJ\n";
+    let output = batch(&["--expected", "--format", "csv"], input);
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(),
+               "line,player1,player2,table,result,expected,match\n\
+                1,8D 7C,7D 6C,KS 9D 5C 3S 2D,1 HIGH_CARD K9875,1 HIGH_CARD K9875,true\n\
+                5,TC JC,AD 4S,2H 7H TH QS KC,1 PAIR TTKQJ,1 PAIR TTKQJ,true\n");
+}
+
+#[test]
+fn test_mismatch() {
+    let input = "TC JC AD 4S 2H 7H TH QS KC DRAW\n";
+    let output = batch(&["--expected"], input);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "1 PAIR TTKQJ\n");
+    assert_eq!(String::from_utf8(output.stderr).unwrap(),
+               "1 of 1 deals do not match:\n  line 1: expected 'DRAW', got '1 PAIR TTKQJ'\n");
+}
+
+#[test]
+fn test_invalid_input() {
+    let output = batch(&[], "TC JC\nAD 4S\n");
+
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(String::from_utf8(output.stderr).unwrap(), "line 1: incomplete deal\n");
+}
@@ -0,0 +1,57 @@
+extern crate poker;
+
+use poker::cfr;
+use poker::cfr::Solver;
+use poker::cfr::Variant;
+use poker::kuhn::Kuhn;
+use poker::leduc::Leduc;
+
+#[test]
+fn test_kuhn_cfr() {
+    let mut solver = Solver::new(Kuhn, Variant::Vanilla);
+    solver.train(10000);
+    let strategy = solver.strategy();
+
+    let value = cfr::expected_value(&Kuhn, &strategy, 0);
+    assert!((value + 1.0 / 18.0).abs() < 0.005, "value {}", value);
+    assert!(cfr::exploitability(&Kuhn, &strategy) < 0.01);
+
+    // The second player always calls a bet with a King and never with a Jack
+    assert!(solver.average_strategy("Kb").unwrap()[1] > 0.99);
+    assert!(solver.average_strategy("Jb").unwrap()[0] > 0.99);
+}
+
+#[test]
+fn test_kuhn_cfr_plus() {
+    let mut solver = Solver::new(Kuhn, Variant::Plus);
+    solver.train(500);
+    let strategy = solver.strategy();
+
+    let value = cfr::expected_value(&Kuhn, &strategy, 0);
+    assert!((value + 1.0 / 18.0).abs() < 0.001, "value {}", value);
+    assert!(cfr::exploitability(&Kuhn, &strategy) < 0.001);
+
+    // The first player bets a King three times as often as a Jack
+    let jack = solver.average_strategy("J").unwrap()[1];
+    let king = solver.average_strategy("K").unwrap()[1];
+    assert!((king - 3.0 * jack).abs() < 0.02, "jack {} king {}", jack, king);
+}
+
+#[test]
+fn test_exploitability_uniform() {
+    // Both players pass or bet at random when the strategy is empty
+    let strategy = cfr::Strategy::new();
+    assert!(cfr::expected_value(&Kuhn, &strategy, 0).abs() > 0.1);
+    assert!(cfr::exploitability(&Kuhn, &strategy) > 0.4);
+}
+
+#[test]
+fn test_leduc_cfr_plus() {
+    let mut solver = Solver::new(Leduc, Variant::Plus);
+    solver.train(200);
+    let strategy = solver.strategy();
+
+    let value = cfr::expected_value(&Leduc, &strategy, 0);
+    assert!((value + 0.0856).abs() < 0.002, "value {}", value);
+    assert!(cfr::exploitability(&Leduc, &strategy) < 0.01);
+}
@@ -9,3 +9,33 @@ fn test_high_card() {
     let result = poker::play(player1, player2, table);
     assert_eq!(result, "1 HIGH_CARD K9875");
 }
+
+#[test]
+fn test_pair() {
+    let player1 = String::from("TC JC");
+    let player2 = String::from("AD 4S");
+    let table = String::from("2H 7H TH QS KC");
+
+    let result = poker::play(player1, player2, table);
+    assert_eq!(result, "1 PAIR TTKQJ");
+}
+
+#[test]
+fn test_straight_with_low_ace() {
+    let player1 = String::from("AD 5S");
+    let player2 = String::from("KD KS");
+    let table = String::from("2H 3C 4H QS KC");
+
+    let result = poker::play(player1, player2, table);
+    assert_eq!(result, "1 STRAIGHT 5432A");
+}
+
+#[test]
+fn test_draw() {
+    let player1 = String::from("2D 3S");
+    let player2 = String::from("2C 3H");
+    let table = String::from("AH KC QH JS TC");
+
+    let result = poker::play(player1, player2, table);
+    assert_eq!(result, "DRAW");
+}
@@ -0,0 +1,76 @@
+extern crate poker;
+
+use std::collections::HashSet;
+
+use poker::card::Card;
+use poker::combinatorics;
+use poker::combinatorics::CardSet;
+use poker::preflop::HandClass;
+
+fn cards(text: &str) -> Vec<Card> {
+    text.split_whitespace().map(|card| Card::parse(card).unwrap()).collect()
+}
+
+#[test]
+fn test_combinations_without_dead_cards() {
+    let dead = cards("As Kd 7c");
+    let set = CardSet::deck(&dead);
+    assert_eq!(set.len(), 49);
+    assert_eq!(set.count(2), 1176);
+
+    let mut seen = HashSet::new();
+    for (rank, combination) in set.combinations(2).enumerate() {
+        assert!(combination.iter().all(|card| !dead.contains(card)));
+        assert_eq!(set.rank(&combination), Some(rank as u64));
+        assert_eq!(set.unrank(rank as u64, 2), combination);
+        let mut sorted = combination.clone();
+        sorted.sort();
+        assert!(seen.insert(sorted));
+    }
+    assert_eq!(seen.len(), 1176);
+
+    // Turn and river runouts of a flop with known hole cards
+    let set = CardSet::deck(&cards("Ah Kh Qh Jh Th"));
+    assert_eq!(set.combinations(2).count() as u64, set.count(2));
+    assert_eq!(set.combinations(2).count(), 1081);
+
+    assert_eq!(set.rank(&cards("Kd 2c")), set.rank(&cards("2c Kd")));
+    assert_eq!(set.rank(&cards("Ah 2c")), None);
+    assert_eq!(set.rank(&cards("2c 2c")), None);
+    assert_eq!(set.combinations(0).collect::<Vec<_>>(), vec![Vec::<Card>::new()]);
+    assert_eq!(CardSet::new(&cards("2c 3c"), &[]).combinations(3).count(), 0);
+
+    let small = CardSet::new(&cards("5h 2c 9s 2c Td"), &cards("9s"));
+    assert_eq!(small.cards(), &cards("2c 5h Td")[..]);
+    assert_eq!(small.combinations(2).collect::<Vec<_>>(),
+               vec![cards("2c 5h"), cards("2c Td"), cards("5h Td")]);
+}
+
+#[test]
+fn test_combo_counting() {
+    let board = cards("As Kd 7c");
+    assert_eq!(combinatorics::hand_combos("AK", &board), Ok(9));
+    assert_eq!(combinatorics::hand_combos("AKs", &board), Ok(2));
+    assert_eq!(combinatorics::hand_combos("AA, KK", &board), Ok(6));
+    assert_eq!(combinatorics::hand_combos("AK", &[]), Ok(16));
+    assert!(combinatorics::hand_combos("AX", &board).is_err());
+    assert_eq!(combinatorics::remaining(board[0].value, &board), 3);
+
+    // Repeated dead cards don't remove more cards
+    let repeated = cards("As As Ks Kd Kd Kh Kc Kc");
+    assert_eq!(combinatorics::remaining(repeated[0].value, &repeated), 3);
+    assert_eq!(combinatorics::remaining(repeated[2].value, &repeated), 0);
+    assert_eq!(combinatorics::hand_combos("AK", &repeated), Ok(0));
+    assert_eq!(combinatorics::class_combos(HandClass::parse("AKo").unwrap(), &repeated), 0);
+    assert_eq!(combinatorics::class_combos(HandClass::parse("AA").unwrap(), &repeated), 3);
+
+    // Counting by class agrees with enumerating the combos of the class
+    let dead = cards("As Ad Kd 7c 7h 2s");
+    for class in HandClass::all() {
+        let expected = class.combos()
+            .iter()
+            .filter(|combo| !dead.contains(&combo[0]) && !dead.contains(&combo[1]))
+            .count();
+        assert_eq!(combinatorics::class_combos(class, &dead), expected, "{}", class);
+    }
+}
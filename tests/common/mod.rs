@@ -0,0 +1,51 @@
+// Bots shared by the integration tests. A calling station is baseline::CallingStation.
+
+use poker::bot::Action;
+use poker::bot::Bot;
+use poker::bot::HandResult;
+use poker::bot::Observation;
+
+/**
+ * Folder folds every bet and checks otherwise, counting the hands it's told about
+ */
+pub struct Folder {
+    pub hands: usize,
+}
+
+impl Folder {
+    pub fn new() -> Folder {
+        Folder { hands: 0 }
+    }
+}
+
+impl Bot for Folder {
+    fn name(&self) -> String {
+        "folder".to_string()
+    }
+
+    fn act(&mut self, _: &Observation) -> Action {
+        Action::Fold
+    }
+
+    fn hand_over(&mut self, _: usize, _: &HandResult) {
+        self.hands += 1;
+    }
+}
+
+/**
+ * Raiser raises the smallest amount while it can, and calls otherwise
+ */
+pub struct Raiser;
+
+impl Bot for Raiser {
+    fn name(&self) -> String {
+        "raiser".to_string()
+    }
+
+    fn act(&mut self, observation: &Observation) -> Action {
+        match observation.legal.raise {
+            Some((min, _)) => Action::Raise(min),
+            None => Action::Call,
+        }
+    }
+}
@@ -0,0 +1,121 @@
+extern crate poker;
+
+use poker::card;
+use poker::card::Card;
+use poker::encoding;
+use poker::encoding::Decoder;
+use poker::encoding::Encoder;
+use poker::encoding::Record;
+use poker::rng::Rng;
+
+fn random_records(players: usize, count: usize, seed: u64) -> Vec<Record> {
+    let mut rng = Rng::new(seed);
+    let mut deck = card::deck();
+    (0..count)
+        .map(|i| {
+            rng.shuffle(&mut deck);
+            let board_size = [0, 3, 4, 5][i % 4];
+            Record {
+                holes: (0..players)
+                    .map(|player| [deck[2 * player], deck[2 * player + 1]])
+                    .collect(),
+                board: deck[2 * players..2 * players + board_size].to_vec(),
+            }
+        })
+        .collect()
+}
+
+fn encode(players: usize, records: &[Record]) -> Vec<u8> {
+    let mut encoder = Encoder::new(Vec::new(), players).unwrap();
+    for record in records {
+        encoder.write(record).unwrap();
+    }
+    encoder.finish().unwrap()
+}
+
+#[test]
+fn test_round_trip() {
+    for &players in [1, 2, 6, 9].iter() {
+        let records = random_records(players, 1000, players as u64);
+        let bytes = encode(players, &records);
+        assert_eq!(bytes.len(),
+                   encoding::HEADER_SIZE + 1000 * encoding::record_size(players) +
+                   encoding::TRAILER_SIZE);
+
+        let decoder = Decoder::new(&bytes).unwrap();
+        assert_eq!(decoder.players(), players);
+        assert_eq!(decoder.len(), 1000);
+        assert!(decoder.verify());
+        let decoded: Vec<Record> = decoder.records()
+            .map(|record| record.to_record().unwrap())
+            .collect();
+        assert_eq!(decoded, records);
+    }
+}
+
+#[test]
+fn test_record_access() {
+    let records = random_records(2, 10, 7);
+    let bytes = encode(2, &records);
+    let decoder = Decoder::new(&bytes).unwrap();
+
+    let record = decoder.get(3).unwrap();
+    assert_eq!(record.bytes().len(), 7);
+    assert_eq!(record.hole(1), Some(records[3].holes[1]));
+    assert_eq!(record.hole(2), None);
+    assert_eq!(record.board(), records[3].board);
+    assert!(decoder.get(10).is_none());
+    assert!(decoder.get(usize::MAX / 7 + 1).is_none());
+    assert!(decoder.get(usize::MAX).is_none());
+
+    // A deal of 2 players and a full board takes 7 bytes
+    let mut buffer = [0; 7];
+    let deal = Record {
+        holes: vec![[Card::new('A', 'S'), Card::new('K', 'S')],
+                    [Card::new('2', 'C'), Card::new('2', 'D')]],
+        board: vec![Card::new('Q', 'S'),
+                    Card::new('J', 'S'),
+                    Card::new('T', 'S'),
+                    Card::new('3', 'H'),
+                    Card::new('4', 'H')],
+    };
+    encoding::pack(&deal, &mut buffer);
+    assert_eq!(encoding::unpack(&buffer, 2), Ok(deal));
+}
+
+#[test]
+fn test_corruption() {
+    let records = random_records(2, 100, 11);
+    let mut bytes = encode(2, &records);
+
+    bytes[encoding::HEADER_SIZE + 10] ^= 0x01;
+    assert!(!Decoder::new(&bytes).unwrap().verify());
+
+    bytes.remove(encoding::HEADER_SIZE);
+    assert_eq!(Decoder::new(&bytes).unwrap_err(),
+               "expected 100 records of 7 bytes, found 699 bytes");
+    bytes.truncate(bytes.len() - 1);
+    assert!(Decoder::new(&bytes).is_err());
+    assert_eq!(Decoder::new(b"not a stream").unwrap_err(), "not a stream of deals");
+
+    // A board with a gap is not valid
+    let mut buffer = [0; 6];
+    encoding::pack(&Record {
+                       holes: vec![[Card::new('A', 'S'), Card::new('K', 'S')]],
+                       board: vec![],
+                   },
+                   &mut buffer);
+    buffer[5] = 0x40; // Last slot of the board
+    assert_eq!(encoding::unpack(&buffer, 1), Err(String::from("invalid board")));
+}
+
+#[test]
+fn test_invalid_records() {
+    let mut encoder = Encoder::new(Vec::new(), 2).unwrap();
+    let record = Record {
+        holes: vec![[Card::new('A', 'S'), Card::new('K', 'S')]],
+        board: vec![],
+    };
+    assert!(encoder.write(&record).is_err());
+    assert!(Encoder::new(Vec::new(), 0).is_err());
+}
@@ -0,0 +1,121 @@
+extern crate poker;
+
+use poker::card::Card;
+use poker::history::ActionKind;
+use poker::pokerstars;
+use poker::types::Street;
+
+const CASH: &str = include_str!("data/pokerstars/cash.txt");
+const TOURNAMENT: &str = include_str!("data/pokerstars/tournament.txt");
+const ALL_IN: &str = include_str!("data/pokerstars/allin.txt");
+const JOINS: &str = include_str!("data/pokerstars/joins.txt");
+
+fn cards(text: &str) -> Vec<Card> {
+    text.split_whitespace().map(|card| Card::parse(card).unwrap()).collect()
+}
+
+#[test]
+fn test_cash_game() {
+    let hands = pokerstars::parse(CASH).unwrap();
+    assert_eq!(hands.len(), 2);
+
+    let first = &hands[0];
+    assert_eq!(first.id, 210000000001);
+    assert_eq!(first.tournament, None);
+    assert_eq!(first.currency, Some(String::from("USD")));
+    assert_eq!((first.small_blind, first.big_blind), (1, 2));
+    assert_eq!(first.table, "Aaltje III");
+    assert_eq!((first.max_seats, first.button), (6, 1));
+    assert_eq!(first.seats.len(), 3);
+    assert_eq!(first.seat("Bob").unwrap().stack, 215);
+    assert_eq!(first.hero, Some(String::from("Alice")));
+    assert_eq!(first.hero_cards.unwrap().to_vec(), cards("Ah Kh"));
+    assert_eq!(first.board, cards("Qh Jc 2d"));
+    assert_eq!(first.actions_on(Street::Preflop).len(), 5);
+    assert_eq!(first.actions_on(Street::Flop).last().unwrap().kind,
+               ActionKind::Collect(13));
+    assert_eq!((first.total_pot, first.rake), (13, 0));
+
+    let second = &hands[1];
+    assert_eq!(second.board, cards("7h Ts 3c 2d Kd"));
+    let shown: Vec<_> = second.actions
+        .iter()
+        .filter_map(|action| match action.kind {
+            ActionKind::Show(cards) => Some((action.player.as_str(), cards.to_vec())),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(shown, vec![("Alice", cards("7c 7d")), ("Bob", cards("Ah Tc"))]);
+    assert_eq!((second.total_pot, second.rake), (173, 9));
+}
+
+#[test]
+fn test_tournament() {
+    let hand = pokerstars::parse_hand(TOURNAMENT).unwrap();
+    let tournament = hand.tournament.as_ref().unwrap();
+
+    assert_eq!(tournament.id, 3000000001);
+    assert_eq!(tournament.buy_in, "$1.40+$0.10 USD");
+    assert_eq!(tournament.level, "III");
+    assert_eq!(hand.currency, None);
+    assert_eq!((hand.small_blind, hand.big_blind), (2500, 5000));
+    assert!(hand.seat("Frank").unwrap().sitting_out);
+    let antes = hand.actions.iter().filter(|action| action.kind == ActionKind::Ante(500)).count();
+    assert_eq!(antes, 4);
+    assert_eq!(hand.actions_on(Street::Turn)[2].kind, ActionKind::UncalledBet(10000));
+    assert_eq!(hand.total_pot, 37000);
+}
+
+#[test]
+fn test_all_in() {
+    let hand = pokerstars::parse_hand(ALL_IN).unwrap();
+
+    let all_in: Vec<&str> = hand.actions
+        .iter()
+        .filter(|action| action.all_in)
+        .map(|action| action.player.as_str())
+        .collect();
+    assert_eq!(all_in, vec!["Grace", "Dave"]);
+    assert_eq!(hand.actions_on(Street::Flop).len(), 0);
+    assert_eq!(hand.board, cards("Qs 7h 3c Td 2s"));
+
+    let collected: Vec<_> = hand.actions
+        .iter()
+        .filter_map(|action| match action.kind {
+            ActionKind::Collect(amount) => Some((action.player.as_str(), amount)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(collected, vec![("Dave", 199000), ("Grace", 187500)]);
+    assert_eq!(hand.total_pot, 386500);
+}
+
+#[test]
+fn test_joins_and_leaves() {
+    let hand = pokerstars::parse_hand(JOINS).unwrap();
+    assert_eq!(hand.seats.len(), 3);
+    assert!(hand.actions.iter().all(|action| action.player != "Dave" && action.player != "Erin"));
+
+    let players: Vec<&str> = hand.actions.iter().map(|action| action.player.as_str()).collect();
+    assert_eq!(players, vec!["Alice", "Bob", "Carol", "Alice", "Bob", "Alice", "Alice"]);
+    assert_eq!(hand.total_pot, 4);
+}
+
+#[test]
+fn test_errors() {
+    let broken = CASH.replace("Bob: calls $0.50", "Bob: calls lots");
+    let error = pokerstars::parse(&broken).unwrap_err();
+    assert_eq!(error.line, 50);
+    assert_eq!(error.to_string(), "line 50: invalid amount on 'calls lots'");
+
+    let broken = TOURNAMENT.replace("[Ac 8d 4h]\n", "[Ac 8d 4x]\n");
+    assert_eq!(pokerstars::parse(&broken).unwrap_err().to_string(),
+               "line 19: invalid card '4x'");
+
+    let truncated: String = ALL_IN.lines().take(20).collect::<Vec<_>>().join("\n");
+    assert_eq!(pokerstars::parse(&truncated).unwrap_err().to_string(),
+               "line 20: missing summary");
+
+    let oversized = CASH.replace("Bob ($2.15 in chips)", "Bob ($999999999999999999 in chips)");
+    assert_eq!(pokerstars::parse(&oversized).unwrap_err().line, 4);
+}
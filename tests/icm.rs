@@ -0,0 +1,100 @@
+extern crate poker;
+
+use poker::icm;
+use poker::icm::Confrontation;
+use poker::preflop;
+use poker::preflop::EquityTable;
+use poker::preflop::HandClass;
+use poker::range::Range;
+
+const PAYOUTS: [f64; 3] = [50.0, 30.0, 20.0];
+
+#[test]
+fn test_equal_stacks() {
+    let equities = icm::exact(&[1000.0; 5], &PAYOUTS);
+    for equity in &equities {
+        assert!((equity - 20.0).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_winner_takes_all() {
+    // With a single prize the equity is proportional to the chips
+    let equities = icm::exact(&[1000.0, 3000.0, 0.0, 4000.0], &[80.0]);
+    assert_eq!(equities.len(), 4);
+    assert!((equities[0] - 10.0).abs() < 1e-9);
+    assert!((equities[1] - 30.0).abs() < 1e-9);
+    assert_eq!(equities[2], 0.0);
+    assert!((equities[3] - 40.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_sampled() {
+    let stacks = [4000.0, 2500.0, 1500.0, 1000.0, 700.0, 300.0];
+    let exact = icm::exact(&stacks, &PAYOUTS);
+    let sampled = icm::sampled(&stacks, &PAYOUTS, 50000, 3);
+    for (exact, sampled) in exact.iter().zip(&sampled) {
+        assert!((exact - sampled).abs() < 0.3, "{} {}", exact, sampled);
+    }
+    assert_eq!(sampled, icm::sampled(&stacks, &PAYOUTS, 50000, 3));
+
+    // Chips are worth less the more a player has
+    assert!(exact[0] / stacks[0] < exact[5] / stacks[5]);
+}
+
+#[test]
+fn test_large_field() {
+    let stacks: Vec<f64> = (1..41).map(|stack| stack as f64 * 100.0).collect();
+    let payouts = [30.0, 20.0, 15.0, 12.0, 10.0, 8.0, 5.0];
+    let equities = icm::equities(&stacks, &payouts);
+    assert!((equities.iter().sum::<f64>() - 100.0).abs() < 1e-6);
+    assert!(equities[39] > equities[20] && equities[20] > equities[0]);
+}
+
+#[test]
+fn test_push_decision() {
+    let table = EquityTable::sampled(30, 11);
+    let calling = preflop::frequencies(&Range::parse("22+, A2+, KTs+, KQo").unwrap());
+
+    // On the bubble, the short stack on the small blind shoves into the chip leader
+    let bubble = Confrontation {
+        stacks: vec![1000.0, 6000.0, 1500.0, 1500.0],
+        posted: vec![50.0, 100.0, 0.0, 0.0],
+        pusher: 0,
+        caller: 1,
+    };
+    let hand = |text| HandClass::parse(text).unwrap();
+    let aces = bubble.push_decision(&PAYOUTS, &table, hand("AA"), &calling);
+    assert!(aces.is_profitable());
+    assert!(aces.equity > 0.8);
+    assert!(aces.probability > 0.1 && aces.probability < 0.3);
+
+    let trash = bubble.push_decision(&PAYOUTS, &table, hand("72o"), &calling);
+    assert!(!trash.is_profitable());
+    assert_eq!(trash.fold, aces.fold);
+
+    // The chip leader risks little calling, but it must be ahead of the shoving range
+    let shoving = preflop::frequencies(&Range::parse("random").unwrap());
+    assert!(bubble.call_decision(&PAYOUTS, &table, hand("KK"), &shoving).is_profitable());
+    assert!(!bubble.call_decision(&PAYOUTS, &table, hand("32o"), &shoving).is_profitable());
+}
+
+#[test]
+fn test_icm_pressure() {
+    let table = EquityTable::sampled(30, 11);
+    let shoving = preflop::frequencies(&Range::parse("random").unwrap());
+    let bubble = Confrontation {
+        stacks: vec![3000.0, 3000.0, 500.0, 500.0],
+        posted: vec![50.0, 100.0, 0.0, 0.0],
+        pusher: 0,
+        caller: 1,
+    };
+
+    // Calling an any two cards shove with a small edge makes chips, but not prize equity
+    let hand = HandClass::parse("K9o").unwrap();
+    let chips = bubble.call_decision(&[100.0], &table, hand, &shoving);
+    let prizes = bubble.call_decision(&PAYOUTS, &table, hand, &shoving);
+    assert!(chips.equity > 0.5);
+    assert!(chips.is_profitable());
+    assert!(!prizes.is_profitable());
+}
@@ -0,0 +1,113 @@
+#![cfg(feature = "serde")]
+
+extern crate poker;
+
+use poker::card::Card;
+use poker::history::ActionKind;
+use poker::ohh;
+use poker::ohh::ActionType;
+use poker::ohh::OpenHandHistory;
+use poker::ohh::RoundStreet;
+use poker::pokerstars;
+use poker::types::Street;
+
+const SAMPLE: &str = include_str!("data/ohh/sample.ohh");
+const CASH: &str = include_str!("data/pokerstars/cash.txt");
+const ALL_IN: &str = include_str!("data/pokerstars/allin.txt");
+
+fn cards(text: &str) -> Vec<Card> {
+    text.split_whitespace().map(|card| Card::parse(card).unwrap()).collect()
+}
+
+#[test]
+fn test_read_sample() {
+    let hand = ohh::read(SAMPLE).unwrap();
+
+    assert_eq!(hand.game_number, "1234567890");
+    assert_eq!(hand.bet_limit.bet_type, "NL");
+    assert_eq!(hand.players[1].starting_stack, 120.5);
+    assert_eq!(hand.rounds[1].cards, cards("Qc 8h 3s"));
+    assert_eq!(hand.rounds[0].actions[2].action, ActionType::DealtCards);
+    assert_eq!(hand.rounds[4].street, RoundStreet::Showdown);
+    assert!(hand.rounds[3].actions[0].is_allin);
+    assert_eq!(hand.pots[0].player_wins[0].win_amount, 197.0);
+}
+
+#[test]
+fn test_round_trip() {
+    let hand = ohh::read(SAMPLE).unwrap();
+    let written = ohh::write(&hand);
+    assert!(written.contains("\"action\": \"Post SB\""));
+    assert!(written.contains("\"cards\": [\n"));
+    assert_eq!(ohh::read(&written).unwrap(), hand);
+
+    let hands: Vec<OpenHandHistory> = pokerstars::parse(CASH)
+        .unwrap()
+        .iter()
+        .map(OpenHandHistory::from_history)
+        .collect();
+    assert_eq!(ohh::read_all(&ohh::write_all(&hands)).unwrap(), hands);
+}
+
+#[test]
+fn test_from_history() {
+    let hand = pokerstars::parse(CASH).unwrap().remove(1);
+    let converted = OpenHandHistory::from_history(&hand);
+
+    assert_eq!(converted.spec_version, ohh::SPEC_VERSION);
+    assert_eq!(converted.game_number, "210000000002");
+    assert_eq!(converted.start_date_utc, "2020-03-01T19:01:10Z");
+    assert_eq!(converted.small_blind_amount, 0.01);
+    assert_eq!(converted.hero_player_id, Some(1));
+    let streets: Vec<RoundStreet> = converted.rounds.iter().map(|round| round.street).collect();
+    assert_eq!(streets,
+               vec![RoundStreet::Preflop,
+                    RoundStreet::Flop,
+                    RoundStreet::Turn,
+                    RoundStreet::River,
+                    RoundStreet::Showdown]);
+    assert_eq!(converted.rounds[0].actions[0].cards, cards("7c 7d"));
+    assert_eq!(converted.rounds[2].cards, cards("2d"));
+    // Raises are written with the chips they add, not with the increment over the bet
+    assert_eq!(converted.rounds[2].actions[2].action, ActionType::Raise);
+    assert_eq!(converted.rounds[2].actions[2].amount, 0.7);
+    assert_eq!(converted.rounds[4].actions.len(), 2);
+    assert_eq!(converted.pots[0].amount, 1.73);
+    assert_eq!(converted.pots[0].player_wins[0].win_amount, 1.64);
+}
+
+#[test]
+fn test_to_history() {
+    let hand = pokerstars::parse_hand(ALL_IN).unwrap();
+    let converted = OpenHandHistory::from_history(&hand).to_history().unwrap();
+
+    assert_eq!(converted.id, hand.id);
+    assert_eq!(converted.seats, hand.seats);
+    assert_eq!(converted.board, hand.board);
+    assert_eq!(converted.hero_cards, hand.hero_cards);
+    assert_eq!(converted.total_pot, hand.total_pot);
+    assert_eq!(converted.tournament.as_ref().unwrap().buy_in, "1.4+0.1 USD");
+    assert_eq!(converted.actions_on(Street::Preflop), hand.actions_on(Street::Preflop));
+
+    // Shown cards come first, and the pots are collected after the showdown
+    let collected: Vec<(String, ActionKind)> = converted.actions
+        .iter()
+        .filter(|action| action.street == Street::River)
+        .map(|action| (action.player.clone(), action.kind.clone()))
+        .collect();
+    assert_eq!(collected,
+               vec![(String::from("Dave"), ActionKind::Show(hand.hero_cards.unwrap())),
+                    (String::from("Erin"), ActionKind::Muck),
+                    (String::from("Grace"), ActionKind::Show([cards("Kh")[0], cards("Kd")[0]])),
+                    (String::from("Dave"), ActionKind::Collect(199000)),
+                    (String::from("Grace"), ActionKind::Collect(187500))]);
+}
+
+#[test]
+fn test_invalid_card() {
+    let broken = SAMPLE.replace("\"9c\"", "\"9x\"");
+    let error = ohh::read(&broken).unwrap_err();
+    assert!(error.starts_with("invalid value: string \"9x\", expected a card like \"As\""),
+            "{}",
+            error);
+}
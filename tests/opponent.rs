@@ -0,0 +1,163 @@
+extern crate poker;
+
+use poker::board;
+use poker::bot::Action;
+use poker::card;
+use poker::card::Card;
+use poker::engine::{HandState, TableConfig};
+use poker::opponent::{ActionModel, Holding, RangeTracker, Spot, Tendencies};
+use poker::range::Range;
+use poker::types::Street;
+
+fn cards(text: &str) -> Vec<Card> {
+    text.split_whitespace().map(|card| Card::parse(card).unwrap()).collect()
+}
+
+/**
+ * weight gets the total weight of the combos of a range written on the usual notation
+ */
+fn weight(range: &Range, hands: &str) -> f64 {
+    let hands = Range::parse(hands).unwrap();
+    range.combos()
+        .iter()
+        .filter(|combo| {
+            hands.combos().iter().any(|hand| {
+                hand.cards.contains(&combo.cards[0]) && hand.cards.contains(&combo.cards[1])
+            })
+        })
+        .map(|combo| combo.weight)
+        .sum()
+}
+
+fn river_spot(board: &str, to_call: u64) -> Spot {
+    let board = cards(board);
+    let texture = board::texture(&board.iter().collect::<Vec<_>>());
+    Spot {
+        street: Street::River,
+        board,
+        texture: Some(texture),
+        pot: 1000 + to_call,
+        to_call,
+        raises: if to_call > 0 { 1 } else { 0 },
+    }
+}
+
+#[test]
+fn test_likelihoods() {
+    let model = Tendencies::default();
+    let facing = river_spot("Kc 7s 2d 9h 4c", 500);
+    let unopened = river_spot("Kc 7s 2d 9h 4c", 0);
+    for &strength in &[0.0, 0.3, 0.6, 0.9, 1.0] {
+        let holding = Holding {
+            strength,
+            draw: false,
+        };
+        let actions = [Action::Fold, Action::Call, Action::Raise(1500)];
+        let total: f64 =
+            actions.iter().map(|action| model.likelihood(&facing, &holding, action)).sum();
+        assert!((total - 1.0).abs() < 0.03, "{}", total);
+        let total = model.likelihood(&unopened, &holding, &Action::Check) +
+                    model.likelihood(&unopened, &holding, &Action::Raise(500));
+        assert!((total - 1.0).abs() < 0.03, "{}", total);
+    }
+
+    // Strong hands bet more than weak ones, and weak hands bluff more on boards with draws
+    let likelihood = |spot: &Spot, strength: f64| {
+        let holding = Holding {
+            strength,
+            draw: false,
+        };
+        model.likelihood(spot, &holding, &Action::Raise(500))
+    };
+    assert!(likelihood(&unopened, 0.95) > 0.9);
+    assert!(likelihood(&unopened, 0.2) < 0.15);
+    let wet = river_spot("Th 9h 8c 2h 3d", 0);
+    assert!(likelihood(&wet, 0.2) > likelihood(&unopened, 0.2));
+
+    // Draws are played like decent made hands
+    let flop = Spot {
+        street: Street::Flop,
+        ..river_spot("Kc 7s 2d", 0)
+    };
+    let draw = Holding {
+        strength: 0.1,
+        draw: true,
+    };
+    let nothing = Holding {
+        strength: 0.1,
+        draw: false,
+    };
+    assert!(model.likelihood(&flop, &draw, &Action::Raise(500)) >
+            model.likelihood(&flop, &nothing, &Action::Raise(500)));
+}
+
+#[test]
+fn test_river_actions_narrow_the_range() {
+    let hero = cards("Kh Qh");
+    let board = cards("Kc 7s 2d 9h 4c");
+    let board_refs: Vec<&Card> = board.iter().collect();
+    let hole = [&hero[0], &hero[1]];
+
+    let mut tracker = RangeTracker::new(Tendencies::default(), 2);
+    tracker.reset(&hero);
+    let prior = tracker.equity(1, hole, &board_refs);
+    assert_eq!(tracker.range(1).len(), 1225);
+
+    // A bet and a raise make the range stronger
+    let mut bettor = RangeTracker::new(Tendencies::default(), 2);
+    bettor.reset(&hero);
+    bettor.observe(1, &river_spot("Kc 7s 2d 9h 4c", 0), &Action::Raise(700));
+    assert!(bettor.range(1).len() < 1225); // Combos with a card of the board are gone
+    let total: f64 = bettor.range(1).combos().iter().map(|combo| combo.weight).sum();
+    assert!((total - 1.0).abs() < 1e-9);
+    let after_bet = bettor.equity(1, hole, &board_refs);
+    bettor.observe(1, &river_spot("Kc 7s 2d 9h 4c", 1400), &Action::Raise(4000));
+    let after_raise = bettor.equity(1, hole, &board_refs);
+    assert!(after_bet < prior, "{} {}", after_bet, prior);
+    assert!(after_raise < after_bet, "{} {}", after_raise, after_bet);
+    assert!(weight(bettor.range(1), "77, 99, K9") > 10.0 * weight(bettor.range(1), "65, J3"));
+
+    // A check makes it weaker, and the other seat is untouched
+    let mut checker = RangeTracker::new(Tendencies::default(), 2);
+    checker.reset(&hero);
+    checker.observe(1, &river_spot("Kc 7s 2d 9h 4c", 0), &Action::Check);
+    assert!(checker.equity(1, hole, &board_refs) > prior);
+    assert_eq!(checker.range(0).len(), 1225);
+}
+
+#[test]
+fn test_update_from_observations() {
+    // Seat 1 has the button, raises preflop and bets the flop
+    let mut deck = cards("Ah Kd Qs Qc 7c 8d 2h 3s 4s");
+    deck.extend(card::deck().into_iter().filter(|card| !deck.contains(card)).collect::<Vec<_>>());
+    let mut state = HandState::new(TableConfig::new(100, 10000), 1, &deck);
+    let mut tracker = RangeTracker::new(Tendencies::default(), 2);
+    tracker.start(&state.observation(0));
+    let uniform = tracker.range(1).clone();
+
+    state.apply(Action::Raise(300)).unwrap();
+    tracker.update(&state.observation(0));
+    let raised = tracker.range(1).clone();
+    let odds = |range: &Range| weight(range, "QQ") / weight(range, "72o");
+    assert!(odds(&raised) > 5.0 * odds(&uniform), "{} {}", odds(&raised), odds(&uniform));
+    assert_eq!(tracker.range(0).len(), uniform.len()); // Own actions are not tracked
+
+    state.apply(Action::Call).unwrap();
+    state.apply(Action::Check).unwrap();
+    state.apply(Action::Raise(400)).unwrap();
+    let observation = state.observation(0);
+    tracker.update(&observation);
+    assert!(tracker.range(1).combos().iter().all(|combo| !combo.cards.contains(&deck[4])));
+    assert!(weight(tracker.range(1), "QQ") > weight(&raised, "QQ"));
+
+    // Updating again with the same actions changes nothing
+    let before = tracker.range(1).combos().iter().map(|combo| combo.weight).collect::<Vec<_>>();
+    tracker.update(&observation);
+    let after = tracker.range(1).combos().iter().map(|combo| combo.weight).collect::<Vec<_>>();
+    assert_eq!(before, after);
+
+    let hole = [&deck[0], &deck[1]];
+    let board: Vec<&Card> = observation.board.iter().collect();
+    let equity = tracker.equity(1, hole, &board);
+    assert!(equity > 0.0 && equity < 0.5, "{}", equity);
+}
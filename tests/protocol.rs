@@ -0,0 +1,218 @@
+extern crate poker;
+
+mod common;
+
+use std::io::BufReader;
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+use std::time::Duration;
+
+use poker::arena;
+use poker::arena::ArenaConfig;
+use poker::baseline::CallingStation;
+use poker::bot::{Action, Bot, HandResult, LegalActions, Observation};
+use poker::card::Card;
+use poker::protocol;
+use poker::protocol::{Message, RemoteBot};
+
+use common::Folder;
+use common::Raiser;
+
+/**
+ * Sleeper thinks for too long before calling
+ */
+struct Sleeper;
+
+impl Bot for Sleeper {
+    fn name(&self) -> String {
+        "sleeper".to_string()
+    }
+
+    fn act(&mut self, _: &Observation) -> Action {
+        thread::sleep(Duration::from_millis(60));
+        Action::Call
+    }
+}
+
+/**
+ * Recorder plays like another bot and writes down everything it's told
+ */
+struct Recorder<B: Bot> {
+    bot: B,
+    log: Vec<String>,
+}
+
+impl<B: Bot> Recorder<B> {
+    fn new(bot: B) -> Recorder<B> {
+        Recorder { bot, log: Vec::new() }
+    }
+}
+
+impl<B: Bot> Bot for Recorder<B> {
+    fn name(&self) -> String {
+        self.bot.name()
+    }
+
+    fn hand_started(&mut self, observation: &Observation) {
+        self.log.push(format!("{} {:?}", observation.seat, observation.hole));
+    }
+
+    fn act(&mut self, observation: &Observation) -> Action {
+        self.log.push(format!("{:?}", observation));
+        self.bot.act(observation)
+    }
+
+    fn hand_over(&mut self, seat: usize, result: &HandResult) {
+        self.log.push(format!("{} {:?}", seat, result));
+    }
+}
+
+/**
+ * Client is a thread playing with a bot, which ends with the bot and how the client ended
+ */
+type Client<B> = thread::JoinHandle<(B, Result<(), String>)>;
+
+/**
+ * connect runs a bot on a new thread, playing on the table listening on the address, and gets
+ * the bot and how its client ended
+ */
+fn connect<B: Bot + Send + 'static>(mut bot: B,
+                                    listener: &TcpListener,
+                                    timeout: Duration)
+                                    -> (RemoteBot, Client<B>) {
+    let address = listener.local_addr().unwrap();
+    let client = thread::spawn(move || {
+        let result = protocol::connect(&mut bot, address);
+        (bot, result)
+    });
+    let (stream, _) = listener.accept().unwrap();
+    (RemoteBot::connect(stream, timeout).unwrap(), client)
+}
+
+fn cards(text: &str) -> Vec<Card> {
+    text.split_whitespace().map(|card| Card::parse(card).unwrap()).collect()
+}
+
+#[test]
+fn test_messages() {
+    let hole = cards("As Kd");
+    let messages = vec![Message::Hello("my bot".to_string()),
+                        Message::Deal {
+                            seat: 1,
+                            button: 0,
+                            big_blind: 100,
+                            hole: [hole[0], hole[1]],
+                        },
+                        Message::Board(cards("2c 7h Ts")),
+                        Message::Event {
+                            seat: 0,
+                            action: Action::Raise(300),
+                        },
+                        Message::Request {
+                            pot: 400,
+                            stacks: [9700, 9900],
+                            bets: [300, 100],
+                            legal: LegalActions {
+                                check: false,
+                                call: Some(200),
+                                raise: Some((500, 10000)),
+                            },
+                        },
+                        Message::Action(Action::Check),
+                        Message::Showdown {
+                            seat: 1,
+                            hole: [hole[0], hole[1]],
+                        },
+                        Message::Result([-300, 300]),
+                        Message::End];
+    for message in messages {
+        assert_eq!(Message::parse(&message.to_string()), Ok(message));
+    }
+
+    assert_eq!(Message::parse("request 400 9700 9900 300 100 call 200 raise 500 10000")
+                   .unwrap()
+                   .to_string(),
+               "request 400 9700 9900 300 100 call 200 raise 500 10000");
+    assert!(Message::parse("deal 2 0 100 As Kd").is_err());
+    assert!(Message::parse("board 2c 7h").is_err());
+    assert!(Message::parse("request 400 9700 9900 300 100 bet 5").is_err());
+    assert!(Message::parse("raise lots").is_err());
+}
+
+#[test]
+fn test_match_over_tcp() {
+    let config = ArenaConfig::new(20, 3);
+    let mut first = Recorder::new(Raiser);
+    let mut second = Recorder::new(CallingStation);
+    let local = arena::play(&mut first, &mut second, &config);
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let timeout = Duration::from_secs(10);
+    let (mut remote_first, first_client) = connect(Recorder::new(Raiser), &listener, timeout);
+    let (mut remote_second, second_client) = connect(Recorder::new(CallingStation), &listener, timeout);
+    assert_eq!(remote_first.name(), "raiser");
+
+    let remote = arena::play(&mut remote_first, &mut remote_second, &config);
+    remote_first.close();
+    remote_second.close();
+    assert_eq!(remote, local);
+    assert_eq!(remote_first.timeouts(), 0);
+
+    // The remote bots are told exactly the same as the local ones
+    let (first_client, first_result) = first_client.join().unwrap();
+    let (second_client, second_result) = second_client.join().unwrap();
+    assert_eq!(first_result, Ok(()));
+    assert_eq!(second_result, Ok(()));
+    assert_eq!(first_client.log, first.log);
+    assert_eq!(second_client.log, second.log);
+}
+
+#[test]
+fn test_timeouts_and_disconnections() {
+    let config = ArenaConfig::new(3, 5);
+    let local = arena::play(&mut Folder::new(), &mut CallingStation, &config);
+
+    // A bot that doesn't answer on time folds or checks, and late answers are ignored
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let (mut sleeper, sleeper_client) = connect(Sleeper, &listener, Duration::from_millis(10));
+    let (mut caller, caller_client) = connect(CallingStation, &listener, Duration::from_secs(10));
+    let result = arena::play(&mut sleeper, &mut caller, &config);
+    assert_eq!(result, local);
+    assert!(sleeper.timeouts() > 0);
+    assert!(sleeper.is_connected());
+    drop(sleeper);
+    drop(caller);
+    // The sleeper may still be answering old requests when the table leaves
+    let _ = sleeper_client.join().unwrap();
+    assert_eq!(caller_client.join().unwrap().1, Ok(()));
+
+    // A bot that leaves after saying hello folds or checks for the rest of the match
+    let address = listener.local_addr().unwrap();
+    let client = thread::spawn(move || {
+        let stream = TcpStream::connect(address).unwrap();
+        protocol::run_client(&mut Folder::new(), BufReader::new(&[][..]), stream).unwrap();
+    });
+    let (stream, _) = listener.accept().unwrap();
+    let mut leaver = RemoteBot::connect(stream, Duration::from_secs(10)).unwrap();
+    client.join().unwrap();
+    let result = arena::play(&mut leaver, &mut CallingStation, &config);
+    assert_eq!(result, local);
+    assert!(!leaver.is_connected());
+    assert_eq!(leaver.timeouts(), 0);
+}
+
+#[test]
+fn test_subprocess_bots() {
+    let config = ArenaConfig::new(10, 7);
+    let local = arena::play(&mut CallingStation, &mut Folder::new(), &config);
+
+    let command = env!("CARGO_BIN_EXE_botclient");
+    let timeout = Duration::from_secs(10);
+    let mut caller = RemoteBot::spawn(&format!("{} --stdio --bot call", command), timeout).unwrap();
+    let mut folder = RemoteBot::spawn(&format!("{} --stdio --bot fold", command), timeout).unwrap();
+    assert_eq!(folder.name(), "folder");
+    assert_eq!(arena::play(&mut caller, &mut folder, &config), local);
+
+    assert!(RemoteBot::spawn("", timeout).is_err());
+    assert!(RemoteBot::spawn(&format!("{} --unknown", command), timeout).is_err());
+}
@@ -0,0 +1,86 @@
+extern crate poker;
+
+use std::sync::OnceLock;
+
+use poker::preflop::EquityTable;
+use poker::preflop::HandClass;
+use poker::pushfold;
+use poker::pushfold::Spot;
+
+/**
+ * table gets an equity table shared by all the tests, as sampling it is slow without optimizations
+ */
+fn table() -> &'static EquityTable {
+    static TABLE: OnceLock<EquityTable> = OnceLock::new();
+    TABLE.get_or_init(|| EquityTable::sampled(50, 7))
+}
+
+fn class(text: &str) -> usize {
+    HandClass::parse(text).unwrap().index()
+}
+
+#[test]
+fn test_equity_table() {
+    let table = table();
+    let equity = |hero, villain| {
+        table.equity(HandClass::parse(hero).unwrap(), HandClass::parse(villain).unwrap())
+    };
+
+    assert!(equity("AA", "72o") > 0.75);
+    assert!((equity("AA", "72o") + equity("72o", "AA") - 1.0).abs() < 1e-9);
+    assert!((equity("AKs", "AKs") - 0.5).abs() < 1e-9);
+    assert!(equity("KK", "QQ") > equity("QQ", "KK"));
+}
+
+#[test]
+fn test_solve() {
+    let table = table();
+    let solution = pushfold::solve(table, &Spot::new(10.0), 300);
+
+    // Around 58% shoves and 37% calls at 10 big blinds
+    assert!((50.0..66.0).contains(&solution.shove_percentage()));
+    assert!((30.0..45.0).contains(&solution.call_percentage()));
+    for hand in ["AA", "KK", "AKo", "A2s", "55"].iter() {
+        assert!(solution.shove[class(hand)] > 0.99, "{}", hand);
+        assert!(solution.call[class(hand)] > 0.99, "{}", hand);
+    }
+    assert!(solution.shove[class("72o")] < 0.01);
+    assert!(solution.call[class("T5o")] < 0.01);
+
+    // Played hands are at least as good as folding
+    let spot = solution.spot;
+    for index in 0..169 {
+        if solution.shove[index] > 0.99 {
+            assert!(solution.shove_ev[index] >= spot.small_blind_fold_ev());
+        }
+        if solution.call[index] > 0.99 {
+            assert!(solution.call_ev[index] >= spot.big_blind_fold_ev());
+        }
+    }
+    assert!(solution.shove_ev[class("AA")] > 1.0);
+}
+
+#[test]
+fn test_deeper_stacks() {
+    let table = table();
+    let short = pushfold::solve(table, &Spot::new(5.0), 300);
+    let deep = pushfold::solve(table, &Spot::new(20.0), 300);
+    assert!(short.shove_percentage() > deep.shove_percentage());
+    assert!(short.call_percentage() > deep.call_percentage());
+
+    // Antes make the pot bigger, so ranges get wider
+    let mut spot = Spot::new(20.0);
+    spot.ante = 0.25;
+    let antes = pushfold::solve(table, &spot, 300);
+    assert!(antes.shove_percentage() > deep.shove_percentage());
+}
+
+#[test]
+fn test_grid() {
+    let values: Vec<f64> = (0..169).map(|index| index as f64).collect();
+    let grid = pushfold::grid(&values, |value| format!("{}", value));
+    let lines: Vec<&str> = grid.lines().collect();
+    assert_eq!(lines.len(), 13);
+    assert!(lines[0].starts_with("AA      0  AKs     1  AQs     2"));
+    assert!(lines[12].ends_with("22    168"));
+}
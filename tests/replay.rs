@@ -0,0 +1,92 @@
+extern crate poker;
+
+use poker::pokerstars;
+use poker::replay;
+use poker::replay::Discrepancy;
+use poker::replay::Replayer;
+use poker::types::Street;
+
+const CASH: &str = include_str!("data/pokerstars/cash.txt");
+const TOURNAMENT: &str = include_str!("data/pokerstars/tournament.txt");
+const ALL_IN: &str = include_str!("data/pokerstars/allin.txt");
+
+#[test]
+fn test_step_through() {
+    let hand = pokerstars::parse_hand(TOURNAMENT).unwrap();
+    let mut replayer = Replayer::new(&hand);
+
+    while replayer.street() == Street::Preflop {
+        replayer.step().unwrap();
+    }
+    // The first action of the flop was applied
+    assert_eq!(replayer.pot(), 37000);
+    assert_eq!(replayer.stack("Erin"), Some(134500));
+    assert_eq!(replayer.stack("Frank"), Some(144500));
+    assert!(replayer.is_folded("Dave"));
+    assert!(!replayer.is_folded("Grace"));
+
+    let replay = replayer.finish();
+    assert!(replay.verified);
+    assert!(replay.discrepancies.is_empty());
+    assert_eq!(replay.stacks[1], (String::from("Erin"), 171500));
+}
+
+#[test]
+fn test_cash_showdown() {
+    for hand in pokerstars::parse(CASH).unwrap() {
+        let replay = replay::replay(&hand);
+        assert!(replay.verified);
+        assert_eq!(replay.discrepancies, vec![]);
+    }
+
+    let hand = pokerstars::parse(CASH).unwrap().remove(1);
+    let replay = replay::replay(&hand);
+    assert_eq!(replay.pots.len(), 1);
+    assert_eq!(replay.pots[0].amount, 173);
+    assert_eq!(replay.pots[0].winners, vec![String::from("Alice")]);
+    assert_eq!(replay.pots[0].description,
+               Some(String::from("Three of a Kind, Sevens, King-Ten kickers")));
+    assert_eq!(replay.winnings, vec![(String::from("Alice"), 164)]);
+    assert_eq!(replay.stacks[0], (String::from("Alice"), 285));
+}
+
+#[test]
+fn test_side_pots() {
+    let hand = pokerstars::parse_hand(ALL_IN).unwrap();
+    let replay = replay::replay(&hand);
+
+    assert!(replay.discrepancies.is_empty());
+    assert_eq!(replay.pots.len(), 2);
+    assert_eq!(replay.pots[0].amount, 187500);
+    assert_eq!(replay.pots[0].players, vec!["Dave", "Erin", "Grace"]);
+    assert_eq!(replay.pots[0].winners, vec!["Grace"]);
+    assert_eq!(replay.pots[1].amount, 199000);
+    assert_eq!(replay.pots[1].winners, vec!["Dave"]);
+}
+
+#[test]
+fn test_discrepancies() {
+    let tampered = ALL_IN.replace("Grace collected 1875 from main pot",
+                                  "Dave collected 1875 from main pot");
+    let hand = pokerstars::parse_hand(&tampered).unwrap();
+    let replay = replay::replay(&hand);
+
+    assert_eq!(replay.discrepancies,
+               vec![Discrepancy::Winnings {
+                        player: String::from("Grace"),
+                        recorded: 0,
+                        computed: 187500,
+                    },
+                    Discrepancy::Winnings {
+                        player: String::from("Dave"),
+                        recorded: 386500,
+                        computed: 199000,
+                    }]);
+    assert_eq!(replay.discrepancies[0].to_string(),
+               "Grace collected 0 but should have won 1875");
+
+    let tampered = CASH.replace("Total pot $1.73", "Total pot $1.83");
+    let hand = pokerstars::parse(&tampered).unwrap().remove(1);
+    assert_eq!(replay::replay(&hand).discrepancies[0].to_string(),
+               "total pot is 1.83 but the players put 1.73");
+}
@@ -0,0 +1,108 @@
+extern crate poker;
+
+use poker::calculator;
+use poker::cfr::Variant;
+use poker::range::Range;
+use poker::river::RiverAction;
+use poker::river::RiverSolver;
+use poker::river::RiverSpot;
+
+fn spot(board: &str, first: &str, second: &str, bet_sizes: Vec<f64>) -> RiverSpot {
+    let board = calculator::parse_cards(board).unwrap();
+    RiverSpot {
+        board: [board[0], board[1], board[2], board[3], board[4]],
+        ranges: [Range::parse(first).unwrap(), Range::parse(second).unwrap()],
+        pot: 100.0,
+        stack: 100.0,
+        bet_sizes,
+        max_raises: 1,
+    }
+}
+
+/**
+ * frequency gets the mean frequency of an action over all the combos of a node
+ */
+fn frequency(solver: &RiverSolver, node: usize, action: usize) -> f64 {
+    let strategy = solver.strategy(node);
+    strategy.iter().map(|combo| combo[action]).sum::<f64>() / strategy.len() as f64
+}
+
+#[test]
+fn test_polarized_spot() {
+    // A set of Kings or nothing against a pair of Queens that only can catch bluffs
+    let spot = spot("2c 3d 7h 9s Ks", "QQ", "KK, 54", vec![1.0]);
+    let mut solver = RiverSolver::new(spot, Variant::Plus).unwrap();
+    solver.train(2000);
+    assert!(solver.exploitability() < 0.5, "{}", solver.exploitability());
+
+    // Betting the Queens only gets called by the Kings
+    let root = solver.root();
+    assert_eq!(solver.actions(root), &[RiverAction::Check, RiverAction::Bet(100.0)]);
+    assert!(frequency(&solver, root, 0) > 0.95);
+
+    // The Kings always bet and 1 of every 3 bets is a bluff for a pot sized bet
+    let check = solver.child(root, 0);
+    assert_eq!(solver.player(check), Some(1));
+    let strategy = solver.strategy(check);
+    let mut value = 0.0;
+    let mut bluffs = 0.0;
+    for (combo, frequencies) in solver.combos(1).iter().zip(&strategy) {
+        if combo.cards[0].value == combo.cards[1].value {
+            assert!(frequencies[1] > 0.95);
+            value += frequencies[1];
+        } else {
+            bluffs += frequencies[1];
+        }
+    }
+    assert!((bluffs / (value + bluffs) - 1.0 / 3.0).abs() < 0.03, "{} {}", value, bluffs);
+
+    // The Queens call half of the times
+    let bet = solver.child(check, 1);
+    assert_eq!(solver.actions(bet)[..2], [RiverAction::Fold, RiverAction::Call]);
+    assert!((frequency(&solver, bet, 1) - 0.5).abs() < 0.05);
+}
+
+#[test]
+fn test_values() {
+    let spot = spot("Ah 8d 5c 4s 2h", "AK, 88, 76s, QJs", "AQ, 55, 33, KQs", vec![0.5, 1.0, 2.0]);
+    let mut solver = RiverSolver::new(spot, Variant::Plus).unwrap();
+    solver.train(300);
+    assert!(solver.exploitability() < 1.0);
+
+    // The players split the pot
+    assert!((solver.value(0) + solver.value(1) - 100.0).abs() < 1e-6);
+    let ev = solver.ev(0);
+    assert_eq!(ev.len(), solver.combos(0).len());
+
+    // The straight of the out of position player wins everything it can, and no combo wins
+    // more than the pot and the whole stack
+    for (combo, ev) in solver.combos(0).iter().zip(&ev) {
+        assert!(*ev <= 200.0 + 1e-9);
+        if combo.cards[0].value == poker::card::CardValue::Value7 {
+            assert!(*ev > 100.0);
+        }
+    }
+}
+
+#[test]
+fn test_tree() {
+    let spot = spot("2c 3d 7h 9s Ks", "QQ", "KK, 54", vec![0.5, 5.0]);
+    let solver = RiverSolver::new(spot, Variant::Vanilla).unwrap();
+
+    // Oversized bets are capped at the stack, and no raise is possible after an all-in
+    let root = solver.root();
+    assert_eq!(solver.actions(root),
+               &[RiverAction::Check, RiverAction::Bet(50.0), RiverAction::Bet(100.0)]);
+    let half = solver.child(root, 1);
+    assert_eq!(solver.actions(half),
+               &[RiverAction::Fold, RiverAction::Call, RiverAction::Raise(100.0)]);
+    let all_in = solver.child(root, 2);
+    assert_eq!(solver.actions(all_in), &[RiverAction::Fold, RiverAction::Call]);
+    assert_eq!(solver.player(solver.child(all_in, 1)), None);
+}
+
+#[test]
+fn test_invalid_spot() {
+    assert!(RiverSolver::new(spot("2c 3d 7h 9s Ks", "KsKh", "QQ", vec![1.0]), Variant::Plus)
+        .is_err());
+}
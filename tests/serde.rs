@@ -0,0 +1,103 @@
+#![cfg(feature = "serde")]
+
+extern crate poker;
+extern crate serde;
+extern crate serde_json;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use poker::card;
+use poker::card::Card;
+use poker::card::CardSuit;
+use poker::card::CardValue;
+use poker::hand::Hand;
+use poker::hand::HandType;
+use poker::serialization::OwnedHand;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Deal {
+    #[serde(with = "poker::serialization::numeric")]
+    first: Card,
+    #[serde(with = "poker::serialization::numeric::cards")]
+    board: Vec<Card>,
+}
+
+#[test]
+fn test_cards() {
+    assert_eq!(serde_json::to_string(&Card::new('A', 'S')).unwrap(), "\"As\"");
+    assert_eq!(serde_json::to_string(&Card::new('T', 'D')).unwrap(), "\"Td\"");
+    assert_eq!(serde_json::from_str::<Card>("\"KH\"").unwrap(), Card::new('K', 'H'));
+
+    for card in card::deck() {
+        let json = serde_json::to_string(&card).unwrap();
+        assert_eq!(serde_json::from_str::<Card>(&json).unwrap(), card);
+    }
+
+    let error = serde_json::from_str::<Card>("\"1s\"").unwrap_err();
+    assert_eq!(error.to_string(),
+               "invalid value: string \"1s\", expected a card like \"As\" at line 1 column 4");
+}
+
+#[test]
+fn test_values_and_suits() {
+    assert_eq!(serde_json::to_string(&CardValue::ValueT).unwrap(), "\"T\"");
+    assert_eq!(serde_json::to_string(&CardSuit::Hearts).unwrap(), "\"h\"");
+
+    for value in card::VALUES.iter() {
+        let json = serde_json::to_string(value).unwrap();
+        assert_eq!(serde_json::from_str::<CardValue>(&json).unwrap(), *value);
+    }
+    for suit in card::SUITS.iter() {
+        let json = serde_json::to_string(suit).unwrap();
+        assert_eq!(serde_json::from_str::<CardSuit>(&json).unwrap(), *suit);
+    }
+    assert_eq!(serde_json::from_str::<CardValue>("\"t\"").unwrap(), CardValue::ValueT);
+    assert_eq!(serde_json::from_str::<CardSuit>("\"H\"").unwrap(), CardSuit::Hearts);
+    assert!(serde_json::from_str::<CardValue>("\"Z\"").is_err());
+}
+
+#[test]
+fn test_hands() {
+    let types = ["HIGH_CARD", "PAIR", "TWO_PAIR", "THREE_OF_A_KIND", "STRAIGHT", "FLUSH",
+                 "FULL_HOUSE", "FOUR_OF_A_KIND", "STRAIGHT_FLUSH"];
+    for name in types.iter() {
+        let hand_type: HandType = serde_json::from_str(&format!("\"{}\"", name)).unwrap();
+        assert_eq!(hand_type.name(), *name);
+        assert_eq!(serde_json::to_string(&hand_type).unwrap(), format!("\"{}\"", name));
+    }
+
+    let cards: Vec<Card> = serde_json::from_str("[\"Jc\", \"Ts\", \"Td\", \"Ks\", \"Qh\", \"2c\"]")
+        .unwrap();
+    let refs: Vec<&Card> = cards.iter().collect();
+    let hand = Hand::from_cards(&refs);
+    let json = serde_json::to_string(&hand).unwrap();
+    assert_eq!(json, "{\"hand_type\":\"PAIR\",\"cards\":[\"Ts\",\"Td\",\"Ks\",\"Qh\",\"Jc\"]}");
+
+    // Hands are read back as owned hands
+    let owned: OwnedHand = serde_json::from_str(&json).unwrap();
+    assert_eq!(owned, OwnedHand::new(&hand));
+    assert_eq!(owned.hand_type(), HandType::Pair);
+    assert!(owned.hand() == hand);
+    assert_eq!(serde_json::to_string(&owned).unwrap(), json);
+
+    let wrong = json.replace("PAIR", "FLUSH");
+    assert!(serde_json::from_str::<OwnedHand>(&wrong).unwrap_err()
+        .to_string()
+        .starts_with("the cards build a PAIR, not a FLUSH"));
+    let short = json.replace(",\"Jc\"", "");
+    assert!(serde_json::from_str::<OwnedHand>(&short).is_err());
+}
+
+#[test]
+fn test_numeric_encoding() {
+    let deal = Deal {
+        first: Card::new('A', 'S'),
+        board: vec![Card::new('2', 'C'), Card::new('T', 'D')],
+    };
+    let json = serde_json::to_string(&deal).unwrap();
+    assert_eq!(json, "{\"first\":51,\"board\":[0,33]}");
+    assert_eq!(serde_json::from_str::<Deal>(&json).unwrap(), deal);
+
+    assert!(serde_json::from_str::<Deal>("{\"first\":52,\"board\":[]}").is_err());
+}
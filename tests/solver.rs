@@ -0,0 +1,48 @@
+use std::env;
+use std::io::Write;
+use std::process::Command;
+use std::process::Stdio;
+
+const INPUT: &str = "TC JC\nAD 4S\n2H 7H TH QS KC\n";
+
+fn run(program: &str, input: &str) -> String {
+    let mut child = Command::new(program)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_solver() {
+    assert_eq!(run(env!("CARGO_BIN_EXE_solver"), INPUT), "1 PAIR TTKQJ\n");
+}
+
+#[test]
+fn test_bundle_compiles() {
+    let dir = env::temp_dir().join(format!("poker-bundle-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let source = dir.join("solution.rs");
+    let binary = dir.join("solution");
+
+    let status = Command::new(env!("CARGO_BIN_EXE_bundle")).arg(&source).status().unwrap();
+    assert!(status.success());
+
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| String::from("rustc"));
+    let status = Command::new(rustc)
+        .arg("--edition=2015")
+        .arg("-o")
+        .arg(&binary)
+        .arg(&source)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    assert_eq!(run(binary.to_str().unwrap(), INPUT), "1 PAIR TTKQJ\n");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
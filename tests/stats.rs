@@ -0,0 +1,137 @@
+extern crate poker;
+
+use poker::history::HandHistory;
+use poker::pokerstars;
+use poker::stats;
+use poker::stats::Filter;
+use poker::stats::Position;
+use poker::stats::Stats;
+
+const CASH: &str = include_str!("data/pokerstars/cash.txt");
+const TOURNAMENT: &str = include_str!("data/pokerstars/tournament.txt");
+const ALL_IN: &str = include_str!("data/pokerstars/allin.txt");
+
+const FOLD_TO_THREE_BET: &str = "\
+PokerStars Hand #210000000003:  Hold'em No Limit ($0.01/$0.02 USD) - 2020/03/05 10:00:00 ET
+Table 'Aaltje III' 6-max Seat #3 is the button
+Seat 1: Alice ($2 in chips)
+Seat 2: Bob ($2 in chips)
+Seat 3: Carol ($2 in chips)
+Alice: posts small blind $0.01
+Bob: posts big blind $0.02
+*** HOLE CARDS ***
+Carol: raises $0.04 to $0.06
+Alice: raises $0.12 to $0.18
+Bob: folds
+Carol: folds
+Uncalled bet ($0.12) returned to Alice
+Alice collected $0.14 from pot
+*** SUMMARY ***
+Total pot $0.14 | Rake $0
+";
+
+fn cash() -> Vec<HandHistory> {
+    pokerstars::parse(CASH).unwrap()
+}
+
+fn find<'a>(stats: &'a [Stats], player: &str) -> &'a Stats {
+    stats.iter().find(|stats| stats.player == player).unwrap()
+}
+
+#[test]
+fn test_cash_stats() {
+    let stats = stats::aggregate(&cash(), &Filter::default());
+    assert_eq!(stats.iter().map(|stats| stats.player.as_str()).collect::<Vec<_>>(),
+               vec!["Alice", "Bob", "Carol"]);
+
+    let alice = find(&stats, "Alice");
+    assert_eq!(alice.hands, 2);
+    assert_eq!(alice.vpip(), Some(100.0));
+    assert_eq!(alice.pfr(), Some(50.0));
+    assert_eq!(alice.three_bet(), Some(0.0));
+    assert_eq!(alice.cbet(), Some(100.0));
+    assert_eq!(alice.wtsd(), Some(50.0));
+    assert_eq!(alice.wsd(), Some(100.0));
+    assert_eq!(alice.aggression_factor(), Some(2.0));
+    assert_eq!(alice.big_blinds_per_100(), Some(2125.0));
+
+    let bob = find(&stats, "Bob");
+    assert_eq!(bob.vpip(), Some(50.0));
+    assert_eq!(bob.wsd(), Some(0.0));
+    assert_eq!(bob.aggression_factor(), Some(2.0));
+    assert_eq!(bob.big_blinds_per_100(), Some(-2175.0));
+
+    let carol = find(&stats, "Carol");
+    assert_eq!((carol.three_bets, carol.three_bet_chances), (0, 2));
+    assert_eq!(carol.aggression_factor(), None);
+    assert_eq!(carol.fold_to_three_bet(), None);
+}
+
+#[test]
+fn test_three_bets() {
+    let hands = [pokerstars::parse_hand(ALL_IN).unwrap(),
+                     pokerstars::parse_hand(FOLD_TO_THREE_BET).unwrap()];
+    let stats = stats::aggregate(&hands[..1], &Filter::default());
+
+    let grace = find(&stats, "Grace");
+    assert_eq!((grace.three_bets, grace.three_bet_chances), (1, 1));
+    let dave = find(&stats, "Dave");
+    assert_eq!((dave.folds_to_three_bet, dave.faced_three_bets), (0, 1));
+    assert_eq!(dave.cbet_chances, 0);
+    assert_eq!((dave.went_to_showdown, dave.won_at_showdown), (1, 1));
+    let erin = find(&stats, "Erin");
+    assert_eq!((erin.went_to_showdown, erin.won_at_showdown), (1, 0));
+
+    let stats = stats::aggregate(&hands[1..], &Filter::default());
+    let carol = find(&stats, "Carol");
+    assert_eq!(carol.fold_to_three_bet(), Some(100.0));
+    assert_eq!(find(&stats, "Alice").three_bet(), Some(100.0));
+}
+
+#[test]
+fn test_positions() {
+    let hand = pokerstars::parse_hand(ALL_IN).unwrap();
+    assert_eq!(stats::positions(&hand),
+               vec![(String::from("Frank"), Position::SmallBlind),
+                    (String::from("Grace"), Position::BigBlind),
+                    (String::from("Dave"), Position::Cutoff),
+                    (String::from("Erin"), Position::Button)]);
+
+    let filter = Filter { positions: vec![Position::Button], ..Filter::default() };
+    let stats = stats::aggregate(&cash(), &filter);
+    assert_eq!(stats.iter().map(|stats| (stats.player.as_str(), stats.hands)).collect::<Vec<_>>(),
+               vec![("Alice", 1), ("Bob", 1)]);
+    assert_eq!(Position::from_name("btn"), Some(Position::Button));
+}
+
+#[test]
+fn test_filters() {
+    let mut hands = cash();
+    hands.push(pokerstars::parse_hand(TOURNAMENT).unwrap());
+    hands.push(pokerstars::parse_hand(FOLD_TO_THREE_BET).unwrap());
+
+    let micro = Filter { big_blind: Some(2), ..Filter::default() };
+    assert_eq!(find(&stats::aggregate(&hands, &micro), "Alice").hands, 3);
+
+    let dates = Filter {
+        from: Some(String::from("2020/03/01")),
+        to: Some(String::from("2020/03/02")),
+        ..Filter::default()
+    };
+    let stats = stats::aggregate(&hands, &dates);
+    assert_eq!(find(&stats, "Alice").hands, 2);
+    assert_eq!(find(&stats, "Dave").hands, 1);
+}
+
+#[test]
+fn test_csv() {
+    let stats = stats::aggregate(&cash(), &Filter::default());
+    let csv = stats::to_csv(&stats);
+    let lines: Vec<&str> = csv.lines().collect();
+
+    assert_eq!(lines[0],
+               "player,hands,vpip,pfr,three_bet,fold_to_three_bet,cbet,wtsd,wsd,\
+                aggression_factor,bb_per_100");
+    assert_eq!(lines[1], "Alice,2,100.0,50.0,0.0,,100.0,50.0,100.0,2.0,2125.0");
+    assert_eq!(lines[3], "Carol,2,50.0,0.0,0.0,,,0.0,,,-175.0");
+}